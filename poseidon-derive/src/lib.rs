@@ -0,0 +1,359 @@
+#![forbid(unsafe_code)]
+
+//! Derive macros for `poseidon-client`
+
+use heck::ToSnakeCase;
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Expr, ExprLit, Fields,
+    Ident, Lit,
+};
+
+/// ### Generate a builder function for each instruction of a program
+/// The enum must also derive `BorshSerialize`, its Borsh encoding is the instruction data.
+/// `#[program_id(..)]` takes a `PublicKey` expression or a Base58 string. Each variant lists
+/// its accounts in order with `#[account(name, signer, writable)]`. Accounts with a fixed
+/// address, like the System Program, take `address = <PublicKey expression>`.
+///
+/// The builder of each variant is named after the variant in snake case and takes the
+/// accounts followed by the fields of the variant, `arg0`, `arg1`.. for tuple variants.
+/// ```ignore
+/// #[derive(BorshSerialize, InstructionBuilder)]
+/// #[program_id(COUNTER_PROGRAM_ID)]
+/// pub enum CounterInstruction {
+///     #[account(counter, writable)]
+///     #[account(authority, signer, writable)]
+///     #[account(system_program, address = SYSTEM_PROGRAM_ID)]
+///     Initialize { start: u64 },
+///     #[account(counter, writable)]
+///     #[account(authority, signer)]
+///     Increment(u64),
+/// }
+///
+/// let instruction = CounterInstruction::increment(counter, authority, 3)?;
+/// ```
+#[proc_macro_derive(InstructionBuilder, attributes(program_id, account))]
+pub fn derive_instruction_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// ### Decode the error enum of a program from `InstructionError::Custom` codes
+/// The variants must be unit variants, their discriminants are the error codes.
+/// `#[msg(..)]` sets the message of a variant, it defaults to the variant name.
+/// ```ignore
+/// #[derive(Debug, ProgramErrorDecoder)]
+/// pub enum LendingError {
+///     #[msg("Not enough collateral")]
+///     InsufficientCollateral = 6000,
+///     #[msg("The oracle price is stale")]
+///     StaleOracle,
+/// }
+///
+/// registry.add_decoder::<LendingError>(LENDING_PROGRAM_ID);
+/// ```
+#[proc_macro_derive(ProgramErrorDecoder, attributes(msg))]
+pub fn derive_program_error_decoder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_program_error(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct InstructionAccount {
+    name: Ident,
+    signer: bool,
+    writable: bool,
+    address: Option<Expr>,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "`InstructionBuilder` can only be derived for enums",
+            ))
+        }
+    };
+
+    let program_id = program_id(&input.attrs, input.span())?;
+    let enum_name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let builders = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_name = &variant.ident;
+            let builder_name = format_ident!("{}", variant_name.to_string().to_snake_case());
+            let doc = format!("Build the `{}` instruction", variant_name);
+
+            let accounts = variant
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("account"))
+                .map(parse_account)
+                .collect::<syn::Result<Vec<InstructionAccount>>>()?;
+
+            let account_params = accounts
+                .iter()
+                .filter(|account| account.address.is_none())
+                .map(|account| {
+                    let name = &account.name;
+                    quote! { #name: ::poseidon_client::PublicKey }
+                });
+
+            let account_metas = accounts.iter().map(|account| {
+                let pubkey = match &account.address {
+                    Some(address) => quote! { #address },
+                    None => {
+                        let name = &account.name;
+                        quote! { #name }
+                    }
+                };
+                let is_signer = account.signer;
+                let is_writable = account.writable;
+
+                quote! {
+                    ::poseidon_client::AccountMeta {
+                        pubkey: #pubkey,
+                        is_signer: #is_signer,
+                        is_writable: #is_writable,
+                    }
+                }
+            });
+
+            let (field_params, construct) = match &variant.fields {
+                Fields::Named(fields) => {
+                    let names = fields
+                        .named
+                        .iter()
+                        .map(|field| field.ident.clone())
+                        .collect::<Vec<_>>();
+                    let params = fields.named.iter().map(|field| {
+                        let name = &field.ident;
+                        let ty = &field.ty;
+                        quote! { #name: #ty }
+                    });
+
+                    (
+                        params.collect::<Vec<_>>(),
+                        quote! { #enum_name::#variant_name { #(#names),* } },
+                    )
+                }
+                Fields::Unnamed(fields) => {
+                    let names = (0..fields.unnamed.len())
+                        .map(|index| format_ident!("arg{}", index))
+                        .collect::<Vec<_>>();
+                    let params = fields.unnamed.iter().zip(&names).map(|(field, name)| {
+                        let ty = &field.ty;
+                        quote! { #name: #ty }
+                    });
+
+                    (
+                        params.collect::<Vec<_>>(),
+                        quote! { #enum_name::#variant_name(#(#names),*) },
+                    )
+                }
+                Fields::Unit => (Vec::new(), quote! { #enum_name::#variant_name }),
+            };
+
+            Ok(quote! {
+                #[doc = #doc]
+                #[allow(clippy::too_many_arguments)]
+                pub fn #builder_name(
+                    #(#account_params,)*
+                    #(#field_params,)*
+                ) -> ::poseidon_client::PoseidonResult<::poseidon_client::Instruction> {
+                    let data = ::poseidon_client::borsh::BorshSerialize::try_to_vec(&#construct)?;
+
+                    Ok(::poseidon_client::Instruction {
+                        program_id: #program_id,
+                        accounts: ::std::vec![#(#account_metas),*],
+                        data,
+                    })
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<TokenStream2>>>()?;
+
+    Ok(quote! {
+        impl #impl_generics #enum_name #type_generics #where_clause {
+            #(#builders)*
+        }
+    })
+}
+
+fn expand_program_error(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "`ProgramErrorDecoder` can only be derived for enums",
+            ))
+        }
+    };
+
+    let enum_name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut from_code = Vec::new();
+    let mut names = Vec::new();
+    let mut messages = Vec::new();
+
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.span(),
+                "`ProgramErrorDecoder` variants can not have fields",
+            ));
+        }
+
+        let variant_name = &variant.ident;
+        let name = variant_name.to_string();
+        let message = match variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("msg"))
+        {
+            Some(attr) => attr.parse_args::<syn::LitStr>()?.value(),
+            None => name.clone(),
+        };
+
+        from_code.push(quote! {
+            code if code == #enum_name::#variant_name as u32 => {
+                ::core::option::Option::Some(#enum_name::#variant_name)
+            }
+        });
+        names.push(quote! { #enum_name::#variant_name => #name });
+        messages.push(quote! { #enum_name::#variant_name => #message });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::poseidon_client::ProgramErrorDecoder for #enum_name #type_generics #where_clause {
+            fn from_code(code: u32) -> ::core::option::Option<Self> {
+                match code {
+                    #(#from_code)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    #(#names,)*
+                }
+            }
+
+            fn message(&self) -> ::std::string::String {
+                match self {
+                    #(#messages,)*
+                }
+                .to_owned()
+            }
+        }
+    })
+}
+
+// `#[program_id(EXPR)]` or `#[program_id("Base58")]`
+fn program_id(attrs: &[Attribute], span: Span) -> syn::Result<TokenStream2> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("program_id"))
+        .ok_or_else(|| syn::Error::new(span, "missing `#[program_id(..)]` attribute"))?;
+
+    match attr.parse_args::<Expr>()? {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(base58),
+            ..
+        }) => Ok(quote! { ::poseidon_client::Utilities::base58_to_u32_array(#base58)? }),
+        expr => Ok(quote! { #expr }),
+    }
+}
+
+const RESERVED_ACCOUNT_NAMES: [&str; 3] = ["signer", "writable", "address"];
+
+// `#[account(name, signer, writable, address = EXPR)]`
+fn parse_account(attr: &Attribute) -> syn::Result<InstructionAccount> {
+    let mut name = Option::<Ident>::None;
+    let mut account = InstructionAccount {
+        name: Ident::new("account", attr.span()),
+        signer: false,
+        writable: false,
+        address: None,
+    };
+
+    attr.parse_nested_meta(|meta| {
+        if name.is_none() {
+            let ident = meta.path.require_ident()?;
+            if RESERVED_ACCOUNT_NAMES
+                .iter()
+                .any(|reserved| ident == reserved)
+            {
+                return Err(meta.error(format!(
+                    "the account name must come first, found `{}`",
+                    ident
+                )));
+            }
+
+            name = Some(ident.clone());
+        } else if meta.path.is_ident("signer") {
+            account.signer = true;
+        } else if meta.path.is_ident("writable") {
+            account.writable = true;
+        } else if meta.path.is_ident("address") {
+            account.address = Some(meta.value()?.parse::<Expr>()?);
+        } else {
+            return Err(meta.error("expected `signer`, `writable` or `address = ..`"));
+        }
+
+        Ok(())
+    })?;
+
+    account.name = name.ok_or_else(|| syn::Error::new(attr.span(), "missing account name"))?;
+
+    Ok(account)
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn account_attributes() {
+        let account =
+            parse_account(&parse_quote!(#[account(authority, signer, writable)])).unwrap();
+        assert_eq!(account.name, "authority");
+        assert!(account.signer && account.writable);
+        assert!(account.address.is_none());
+
+        let account =
+            parse_account(&parse_quote!(#[account(system_program, address = SYSTEM_PROGRAM_ID)]))
+                .unwrap();
+        assert!(!account.signer && !account.writable);
+        assert!(account.address.is_some());
+
+        assert!(parse_account(&parse_quote!(#[account(signer, writable)])).is_err());
+        assert!(parse_account(&parse_quote!(#[account(writable)])).is_err());
+        assert!(parse_account(&parse_quote!(#[account(address = SYSTEM_PROGRAM_ID)])).is_err());
+        assert!(parse_account(&parse_quote!(#[account(counter, mutable)])).is_err());
+    }
+
+    #[test]
+    fn builder_names() {
+        assert_eq!("Initialize".to_snake_case(), "initialize");
+        assert_eq!("InitializeNFT".to_snake_case(), "initialize_nft");
+        assert_eq!("SetAuthorityV2".to_snake_case(), "set_authority_v2");
+    }
+}
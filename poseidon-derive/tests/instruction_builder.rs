@@ -0,0 +1,65 @@
+use borsh::BorshSerialize;
+use poseidon_client::{AccountMeta, InstructionBuilder, PublicKey, SYSTEM_PROGRAM_ID};
+
+const COUNTER_PROGRAM_ID: PublicKey = [7; 32];
+
+#[derive(BorshSerialize, InstructionBuilder)]
+#[program_id(COUNTER_PROGRAM_ID)]
+enum CounterInstruction {
+    #[account(counter, writable)]
+    #[account(authority, signer, writable)]
+    #[account(system_program, address = SYSTEM_PROGRAM_ID)]
+    Initialize { start: u64, label: String },
+    #[account(counter, writable)]
+    #[account(authority, signer)]
+    Increment(u64),
+    #[account(counter, writable)]
+    Reset,
+}
+
+#[derive(BorshSerialize, InstructionBuilder)]
+#[program_id("11111111111111111111111111111111")]
+enum SystemLikeInstruction {
+    #[account(from, signer, writable)]
+    #[account(to, writable)]
+    Transfer { lamports: u64 },
+}
+
+#[test]
+fn build_instructions() {
+    let counter = [1; 32];
+    let authority = [2; 32];
+
+    let initialize =
+        CounterInstruction::initialize(counter, authority, 10, "ab".to_owned()).unwrap();
+    assert_eq!(initialize.program_id, COUNTER_PROGRAM_ID);
+    assert_eq!(
+        initialize.accounts,
+        vec![
+            AccountMeta::new(counter, false),
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ]
+    );
+    assert_eq!(
+        initialize.data,
+        vec![0, 10, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, b'a', b'b']
+    );
+
+    let increment = CounterInstruction::increment(counter, authority, 3).unwrap();
+    assert_eq!(
+        increment.accounts,
+        vec![
+            AccountMeta::new(counter, false),
+            AccountMeta::new_readonly(authority, true),
+        ]
+    );
+    assert_eq!(increment.data, vec![1, 3, 0, 0, 0, 0, 0, 0, 0]);
+
+    let reset = CounterInstruction::reset(counter).unwrap();
+    assert_eq!(reset.data, vec![2]);
+
+    let transfer = SystemLikeInstruction::transfer(authority, counter, 5).unwrap();
+    assert_eq!(transfer.program_id, SYSTEM_PROGRAM_ID);
+    assert_eq!(transfer.data, vec![0, 5, 0, 0, 0, 0, 0, 0, 0]);
+}
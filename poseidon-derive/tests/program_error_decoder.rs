@@ -0,0 +1,34 @@
+use poseidon_client::{ProgramErrorDecoder, ProgramErrorRegistry, PublicKey};
+
+const LENDING_PROGRAM_ID: PublicKey = [9; 32];
+
+#[derive(Debug, PartialEq, ProgramErrorDecoder)]
+enum LendingError {
+    #[msg("Not enough collateral")]
+    InsufficientCollateral = 6000,
+    #[msg("The oracle price is stale")]
+    StaleOracle,
+    Paused = 6100,
+}
+
+#[test]
+fn decode_program_errors() {
+    assert_eq!(
+        LendingError::from_code(6001),
+        Some(LendingError::StaleOracle)
+    );
+    assert_eq!(LendingError::from_code(6002), None);
+    assert_eq!(LendingError::Paused.name(), "Paused");
+    assert_eq!(LendingError::Paused.message(), "Paused");
+
+    let mut registry = ProgramErrorRegistry::new();
+    registry.add_decoder::<LendingError>(LENDING_PROGRAM_ID);
+
+    let decoded = registry.decode(LENDING_PROGRAM_ID, 6000).unwrap();
+    assert_eq!(decoded.name, "InsufficientCollateral");
+    assert_eq!(decoded.message, "Not enough collateral");
+    assert_eq!(
+        decoded.downcast_ref::<LendingError>(),
+        Some(&LendingError::InsufficientCollateral)
+    );
+}
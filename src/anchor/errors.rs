@@ -0,0 +1,132 @@
+use crate::InstructionError;
+use std::collections::HashMap;
+
+/// The code of the first error defined with `#[error_code]` in an Anchor program
+pub const ANCHOR_ERROR_CODE_OFFSET: u32 = 6000;
+
+/// An error defined with `#[error_code]` in an Anchor program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorErrorDefinition {
+    pub code: u32,
+    pub name: String,
+    pub message: String,
+}
+
+/// ### Map the custom error codes of an Anchor program to its error names and messages
+/// The errors are in the order they are defined in the program, the same as in its IDL.
+/// Register them in a `ProgramErrorRegistry` to decode the errors of transactions.
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn example(program_id: PublicKey) {
+/// let mut errors = AnchorErrors::new();
+/// errors
+///     .add_error("SlippageExceeded", "Slippage tolerance exceeded")
+///     .add_error("PoolClosed", "The pool is closed");
+///
+/// let mut registry = ProgramErrorRegistry::new();
+/// registry.add_anchor_errors(program_id, errors);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorErrors {
+    errors: HashMap<u32, AnchorErrorDefinition>,
+    next_code: u32,
+}
+
+impl Default for AnchorErrors {
+    fn default() -> Self {
+        AnchorErrors::new()
+    }
+}
+
+impl AnchorErrors {
+    pub fn new() -> Self {
+        AnchorErrors {
+            errors: HashMap::default(),
+            next_code: ANCHOR_ERROR_CODE_OFFSET,
+        }
+    }
+
+    /// Add the next error, numbered after the previous one starting at `6000`
+    pub fn add_error(&mut self, name: &str, message: &str) -> &mut Self {
+        let code = self.next_code;
+
+        self.add_error_with_code(code, name, message)
+    }
+
+    /// Add an error with an explicit code, for errors defined with `#[error_code(offset = ...)]`
+    /// or with a discriminant. Errors added afterwards are numbered after it.
+    pub fn add_error_with_code(&mut self, code: u32, name: &str, message: &str) -> &mut Self {
+        self.errors.insert(
+            code,
+            AnchorErrorDefinition {
+                code,
+                name: name.to_owned(),
+                message: message.to_owned(),
+            },
+        );
+        self.next_code = code.saturating_add(1);
+
+        self
+    }
+
+    pub fn get(&self, code: u32) -> Option<&AnchorErrorDefinition> {
+        self.errors.get(&code)
+    }
+
+    /// The error of an `InstructionError::Custom` code
+    pub fn decode_instruction_error(
+        &self,
+        error: &InstructionError,
+    ) -> Option<&AnchorErrorDefinition> {
+        match error {
+            InstructionError::Custom(code) if *code >= ANCHOR_ERROR_CODE_OFFSET => self.get(*code),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+
+    #[test]
+    fn decode_errors() {
+        let mut errors = AnchorErrors::new();
+        errors
+            .add_error("SlippageExceeded", "Slippage tolerance exceeded")
+            .add_error("PoolClosed", "The pool is closed")
+            .add_error_with_code(6100, "Unauthorized", "The signer is not the pool authority")
+            .add_error("InvalidFee", "The fee is larger than the amount");
+
+        assert_eq!(errors.get(6001).unwrap().name, "PoolClosed");
+        assert_eq!(errors.get(6101).unwrap().name, "InvalidFee");
+        assert!(errors.get(6002).is_none());
+
+        assert_eq!(
+            errors
+                .decode_instruction_error(&InstructionError::Custom(6000))
+                .unwrap()
+                .message,
+            "Slippage tolerance exceeded"
+        );
+        assert!(errors
+            .decode_instruction_error(&InstructionError::Custom(1))
+            .is_none());
+        assert!(errors
+            .decode_instruction_error(&InstructionError::InvalidAccountData)
+            .is_none());
+    }
+
+    #[test]
+    fn default_starts_at_offset() {
+        let mut errors = AnchorErrors::default();
+        errors.add_error("SlippageExceeded", "Slippage tolerance exceeded");
+
+        assert_eq!(
+            errors.get(ANCHOR_ERROR_CODE_OFFSET).unwrap().name,
+            "SlippageExceeded"
+        );
+        assert!(errors.get(0).is_none());
+    }
+}
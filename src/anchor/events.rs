@@ -0,0 +1,282 @@
+use crate::{Base58PublicKey, PoseidonError, PoseidonResult, ProgramLogs};
+use borsh::BorshDeserialize;
+use core::{any::Any, fmt};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// The length of the discriminator that prefixes the data of Anchor events
+pub const ANCHOR_DISCRIMINATOR_LEN: usize = 8;
+
+/// An event emitted with `emit!` by an Anchor program.
+/// `NAME` is the name of the event struct in the program.
+/// ```no_run
+/// # use borsh::BorshDeserialize;
+/// # use poseidon_client::*;
+/// #[derive(BorshDeserialize)]
+/// pub struct SwapEvent {
+///     pub amount_in: u64,
+///     pub amount_out: u64,
+/// }
+///
+/// impl AnchorEvent for SwapEvent {
+///     const NAME: &'static str = "SwapEvent";
+/// }
+/// ```
+pub trait AnchorEvent: BorshDeserialize + Send + Sync + 'static {
+    const NAME: &'static str;
+
+    /// The first 8 bytes of `sha256("event:<NAME>")`
+    fn discriminator() -> [u8; ANCHOR_DISCRIMINATOR_LEN] {
+        anchor_discriminator("event", Self::NAME)
+    }
+}
+
+/// The first 8 bytes of `sha256("<namespace>:<name>")` used by Anchor
+/// to identify events, accounts and instructions
+pub fn anchor_discriminator(namespace: &str, name: &str) -> [u8; ANCHOR_DISCRIMINATOR_LEN] {
+    let mut hasher = Sha256::new();
+    hasher.update(namespace.as_bytes());
+    hasher.update(b":");
+    hasher.update(name.as_bytes());
+    let hash: [u8; 32] = hasher.finalize().into();
+
+    let mut discriminator = [0u8; ANCHOR_DISCRIMINATOR_LEN];
+    discriminator.copy_from_slice(&hash[..ANCHOR_DISCRIMINATOR_LEN]);
+
+    discriminator
+}
+
+type EventDeserializer = fn(&[u8]) -> PoseidonResult<Box<dyn Any + Send + Sync>>;
+
+fn deserialize_event<T: AnchorEvent>(data: &[u8]) -> PoseidonResult<Box<dyn Any + Send + Sync>> {
+    let event = T::try_from_slice(data).map_err(|_| PoseidonError::InvalidEventData)?;
+
+    Ok(Box::new(event))
+}
+
+/// An event decoded by the `AnchorEventDecoder`
+pub struct DecodedAnchorEvent {
+    pub name: &'static str,
+    /// The program that emitted the event
+    pub program_id: Base58PublicKey,
+    event: Box<dyn Any + Send + Sync>,
+}
+
+impl DecodedAnchorEvent {
+    pub fn is<T: AnchorEvent>(&self) -> bool {
+        self.event.is::<T>()
+    }
+
+    /// The event if it is a `T`
+    pub fn downcast_ref<T: AnchorEvent>(&self) -> Option<&T> {
+        self.event.downcast_ref::<T>()
+    }
+
+    pub fn downcast<T: AnchorEvent>(self) -> Result<T, Self> {
+        match self.event.downcast::<T>() {
+            Ok(event) => Ok(*event),
+            Err(event) => Err(DecodedAnchorEvent {
+                name: self.name,
+                program_id: self.program_id,
+                event,
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for DecodedAnchorEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodedAnchorEvent")
+            .field("name", &self.name)
+            .field("program_id", &self.program_id)
+            .finish()
+    }
+}
+
+/// ### Decode the Anchor events in the `Program data:` lines of transaction logs
+/// Lines whose discriminator does not match a registered event are skipped.
+/// ```no_run
+/// # use borsh::BorshDeserialize;
+/// # use poseidon_client::*;
+/// # #[derive(BorshDeserialize)]
+/// # pub struct SwapEvent {
+/// #     pub amount_in: u64,
+/// #     pub amount_out: u64,
+/// # }
+/// # impl AnchorEvent for SwapEvent {
+/// #     const NAME: &'static str = "SwapEvent";
+/// # }
+/// # const SWAP_PROGRAM_ID: &str = "11111111111111111111111111111111";
+/// # fn example(logs: Vec<String>) -> PoseidonResult<()> {
+/// let mut decoder = AnchorEventDecoder::new();
+/// decoder.add_event::<SwapEvent>().add_program_id(SWAP_PROGRAM_ID);
+///
+/// for event in decoder.decode_logs(&ProgramLogs::parse(&logs))? {
+///     if let Some(swap) = event.downcast_ref::<SwapEvent>() {
+///         println!("{} in, {} out", swap.amount_in, swap.amount_out);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct AnchorEventDecoder {
+    events: HashMap<[u8; ANCHOR_DISCRIMINATOR_LEN], (&'static str, EventDeserializer)>,
+    program_ids: Vec<Base58PublicKey>,
+}
+
+impl AnchorEventDecoder {
+    pub fn new() -> Self {
+        AnchorEventDecoder::default()
+    }
+
+    pub fn add_event<T: AnchorEvent>(&mut self) -> &mut Self {
+        self.events
+            .insert(T::discriminator(), (T::NAME, deserialize_event::<T>));
+
+        self
+    }
+
+    /// Only decode the events emitted by these programs.
+    /// Events from every program are decoded if none are added.
+    pub fn add_program_id(&mut self, program_id: &str) -> &mut Self {
+        self.program_ids.push(program_id.to_owned());
+
+        self
+    }
+
+    /// Decode the data of a single event, `None` if the event is not registered
+    pub fn decode(
+        &self,
+        program_id: &str,
+        data: &[u8],
+    ) -> PoseidonResult<Option<DecodedAnchorEvent>> {
+        if data.len() < ANCHOR_DISCRIMINATOR_LEN {
+            return Ok(None);
+        }
+
+        let (discriminator, event_data) = data.split_at(ANCHOR_DISCRIMINATOR_LEN);
+        let (name, deserialize) = match self.events.get(discriminator) {
+            Some(event) => *event,
+            None => return Ok(None),
+        };
+
+        Ok(Some(DecodedAnchorEvent {
+            name,
+            program_id: program_id.to_owned(),
+            event: deserialize(event_data)?,
+        }))
+    }
+
+    /// The registered events of each invocation, depth first
+    pub fn decode_logs(&self, logs: &ProgramLogs) -> PoseidonResult<Vec<DecodedAnchorEvent>> {
+        let mut events = Vec::new();
+
+        for invocation in logs.iter() {
+            if !self.program_ids.is_empty() && !self.program_ids.contains(&invocation.program_id) {
+                continue;
+            }
+
+            for fields in &invocation.data {
+                if let Some(data) = fields.first() {
+                    if let Some(event) = self.decode(&invocation.program_id, data)? {
+                        events.push(event);
+                    }
+                }
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use borsh::BorshSerialize;
+
+    const PROGRAM_ID: &str = "SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8";
+
+    #[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+    struct SwapEvent {
+        amount_in: u64,
+        amount_out: u64,
+    }
+
+    impl AnchorEvent for SwapEvent {
+        const NAME: &'static str = "SwapEvent";
+    }
+
+    #[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+    struct FeeEvent {
+        fee: u64,
+    }
+
+    impl AnchorEvent for FeeEvent {
+        const NAME: &'static str = "FeeEvent";
+    }
+
+    fn program_data<T: AnchorEvent + BorshSerialize>(event: &T) -> String {
+        let mut data = T::discriminator().to_vec();
+        data.extend_from_slice(&event.try_to_vec().unwrap());
+
+        format!("Program data: {}", base64::encode(data))
+    }
+
+    #[test]
+    fn decode_events() {
+        // sha256("event:SwapEvent")
+        assert_eq!(
+            SwapEvent::discriminator(),
+            [64, 198, 205, 232, 38, 8, 113, 226]
+        );
+
+        let swap = SwapEvent {
+            amount_in: 100,
+            amount_out: 95,
+        };
+        let logs = ProgramLogs::parse(&[
+            format!("Program {} invoke [1]", PROGRAM_ID),
+            program_data(&swap),
+            program_data(&FeeEvent { fee: 5 }),
+            "Program data: AAAAAAAAAAA=".to_owned(),
+            format!("Program {} success", PROGRAM_ID),
+        ]);
+
+        let mut decoder = AnchorEventDecoder::new();
+        decoder.add_event::<SwapEvent>().add_program_id(PROGRAM_ID);
+
+        let events = decoder.decode_logs(&logs).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "SwapEvent");
+        assert!(events[0].is::<SwapEvent>());
+        assert!(events[0].downcast_ref::<FeeEvent>().is_none());
+        assert_eq!(events[0].downcast_ref::<SwapEvent>(), Some(&swap));
+
+        decoder.add_event::<FeeEvent>();
+        let events = decoder.decode_logs(&logs).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events
+                .into_iter()
+                .nth(1)
+                .unwrap()
+                .downcast::<FeeEvent>()
+                .unwrap(),
+            FeeEvent { fee: 5 }
+        );
+
+        let mut other_program = AnchorEventDecoder::new();
+        other_program
+            .add_event::<SwapEvent>()
+            .add_program_id("11111111111111111111111111111111");
+        assert!(other_program.decode_logs(&logs).unwrap().is_empty());
+
+        let mut truncated = SwapEvent::discriminator().to_vec();
+        truncated.push(1);
+        assert!(matches!(
+            decoder.decode(PROGRAM_ID, &truncated),
+            Err(PoseidonError::InvalidEventData)
+        ));
+    }
+}
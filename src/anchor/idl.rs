@@ -0,0 +1,341 @@
+use crate::{
+    anchor_discriminator, AnchorErrors, PoseidonError, PoseidonResult, PublicKey, Utilities,
+    ANCHOR_DISCRIMINATOR_LEN,
+};
+use heck::ToSnakeCase;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// ### An Anchor IDL
+/// Both the IDL format of Anchor `0.30` and later and the legacy format
+/// of earlier versions can be loaded.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorIdl {
+    /// The program address. Legacy IDLs keep it in `metadata.address`.
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<IdlMetadata>,
+    pub instructions: Vec<IdlInstruction>,
+    #[serde(default)]
+    pub accounts: Vec<IdlAccount>,
+    #[serde(default)]
+    pub events: Vec<IdlEvent>,
+    #[serde(default)]
+    pub errors: Vec<IdlErrorCode>,
+    #[serde(default)]
+    pub types: Vec<IdlTypeDef>,
+}
+
+impl AnchorIdl {
+    pub fn from_json(idl: &str) -> PoseidonResult<Self> {
+        Ok(serde_json::from_str::<AnchorIdl>(idl)?)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> PoseidonResult<Self> {
+        AnchorIdl::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// The program address declared in the IDL
+    pub fn program_id(&self) -> PoseidonResult<PublicKey> {
+        let address = self
+            .address
+            .as_deref()
+            .or_else(|| {
+                self.metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.address.as_deref())
+            })
+            .ok_or_else(|| PoseidonError::MissingIdlValue("address".to_owned()))?;
+
+        Utilities::base58_to_u32_array(address)
+    }
+
+    pub fn instruction(&self, name: &str) -> PoseidonResult<&IdlInstruction> {
+        self.instructions
+            .iter()
+            .find(|instruction| instruction.name == name)
+            .ok_or_else(|| PoseidonError::IdlItemNotFound(name.to_owned()))
+    }
+
+    pub fn type_def(&self, name: &str) -> PoseidonResult<&IdlTypeDef> {
+        self.types
+            .iter()
+            .find(|type_def| type_def.name == name)
+            .ok_or_else(|| PoseidonError::IdlItemNotFound(name.to_owned()))
+    }
+
+    /// The layout of an account. Legacy IDLs declare it with the account
+    /// while newer IDLs declare it in `types`.
+    pub fn account_type<'a>(&'a self, account: &'a IdlAccount) -> PoseidonResult<&'a IdlTypeDefTy> {
+        match &account.ty {
+            Some(ty) => Ok(ty),
+            None => Ok(&self.type_def(&account.name)?.ty),
+        }
+    }
+
+    /// The errors of the program to decode `InstructionError::Custom` codes
+    pub fn anchor_errors(&self) -> AnchorErrors {
+        let mut anchor_errors = AnchorErrors::new();
+        self.errors.iter().for_each(|error| {
+            anchor_errors.add_error_with_code(
+                error.code,
+                &error.name,
+                error.msg.as_deref().unwrap_or_default(),
+            );
+        });
+
+        anchor_errors
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlMetadata {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlInstruction {
+    pub name: String,
+    /// Legacy IDLs leave it out, it is then derived from the instruction name
+    #[serde(default)]
+    pub discriminator: Option<Vec<u8>>,
+    pub accounts: Vec<IdlInstructionAccountItem>,
+    pub args: Vec<IdlField>,
+}
+
+impl IdlInstruction {
+    /// The first 8 bytes of `sha256("global:<snake_case_name>")` unless the IDL declares one
+    pub fn discriminator(&self) -> Vec<u8> {
+        match &self.discriminator {
+            Some(discriminator) => discriminator.clone(),
+            None => anchor_discriminator("global", &self.name.to_snake_case()).to_vec(),
+        }
+    }
+
+    /// The accounts of the instruction with the accounts of composite
+    /// account groups flattened in order
+    pub fn flat_accounts(&self) -> Vec<&IdlInstructionAccount> {
+        fn flatten<'a>(
+            items: &'a [IdlInstructionAccountItem],
+            accounts: &mut Vec<&'a IdlInstructionAccount>,
+        ) {
+            items.iter().for_each(|item| match item {
+                IdlInstructionAccountItem::Single(account) => accounts.push(account),
+                IdlInstructionAccountItem::Composite(composite) => {
+                    flatten(&composite.accounts, accounts)
+                }
+            })
+        }
+
+        let mut accounts = Vec::new();
+        flatten(&self.accounts, &mut accounts);
+
+        accounts
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IdlInstructionAccountItem {
+    Composite(IdlInstructionAccounts),
+    Single(IdlInstructionAccount),
+}
+
+/// A group of accounts declared with a nested `#[derive(Accounts)]` struct
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlInstructionAccounts {
+    pub name: String,
+    pub accounts: Vec<IdlInstructionAccountItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlInstructionAccount {
+    pub name: String,
+    #[serde(default, alias = "isMut")]
+    pub writable: bool,
+    #[serde(default, alias = "isSigner")]
+    pub signer: bool,
+    #[serde(default, alias = "isOptional")]
+    pub optional: bool,
+    /// A fixed address like the System Program
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub pda: Option<IdlPda>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlPda {
+    pub seeds: Vec<IdlSeed>,
+    /// The program the address is derived from, defaults to the program of the IDL
+    #[serde(default, alias = "programId")]
+    pub program: Option<IdlSeed>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum IdlSeed {
+    /// Bytes in newer IDLs, a value of `type` in legacy IDLs
+    Const {
+        #[serde(default, rename = "type")]
+        ty: Option<IdlType>,
+        value: serde_json::Value,
+    },
+    /// An argument of the instruction
+    Arg {
+        #[serde(default, rename = "type")]
+        ty: Option<IdlType>,
+        path: String,
+    },
+    /// The address of another account of the instruction
+    Account {
+        #[serde(default, rename = "type")]
+        ty: Option<IdlType>,
+        path: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlAccount {
+    pub name: String,
+    /// Legacy IDLs leave it out, it is then derived from the account name
+    #[serde(default)]
+    pub discriminator: Option<Vec<u8>>,
+    /// Only present in legacy IDLs
+    #[serde(default, rename = "type")]
+    pub ty: Option<IdlTypeDefTy>,
+}
+
+impl IdlAccount {
+    /// The first 8 bytes of `sha256("account:<Name>")` unless the IDL declares one
+    pub fn discriminator(&self) -> Vec<u8> {
+        match &self.discriminator {
+            Some(discriminator) => discriminator.clone(),
+            None => anchor_discriminator("account", &self.name).to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlEvent {
+    pub name: String,
+    #[serde(default)]
+    pub discriminator: Option<[u8; ANCHOR_DISCRIMINATOR_LEN]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlErrorCode {
+    pub code: u32,
+    pub name: String,
+    #[serde(default)]
+    pub msg: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlTypeDef {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlTypeDefTy,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum IdlTypeDefTy {
+    Struct {
+        #[serde(default)]
+        fields: Option<IdlDefinedFields>,
+    },
+    Enum {
+        variants: Vec<IdlEnumVariant>,
+    },
+    Type {
+        alias: IdlType,
+    },
+}
+
+/// The fields of a struct or enum variant, either named or a tuple
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IdlDefinedFields {
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlEnumVariant {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Option<IdlDefinedFields>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    U64,
+    I64,
+    F64,
+    U128,
+    I128,
+    Bytes,
+    String,
+    #[serde(alias = "publicKey")]
+    Pubkey,
+    Option(Box<IdlType>),
+    Vec(Box<IdlType>),
+    Array(Box<IdlType>, usize),
+    Defined(IdlDefined),
+}
+
+/// A type defined in `types`. Legacy IDLs only give the name.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IdlDefined {
+    Name(String),
+    Generic {
+        name: String,
+        #[serde(default)]
+        generics: Vec<serde_json::Value>,
+    },
+}
+
+impl IdlDefined {
+    pub fn name(&self) -> &str {
+        match self {
+            IdlDefined::Name(name) => name,
+            IdlDefined::Generic { name, .. } => name,
+        }
+    }
+}
@@ -0,0 +1,807 @@
+use crate::{
+    AccountMeta, AnchorIdl, IdlDefinedFields, IdlInstruction, IdlInstructionAccount, IdlSeed,
+    IdlType, IdlTypeDefTy, Instruction, PdaBuilder, PoseidonError, PoseidonResult, PublicKey,
+    Utilities,
+};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// ### A client for any Anchor program driven by its IDL
+/// Instruction arguments and account data are `serde_json::Value`s:
+/// - integers are numbers, `u128` and `i128` can also be strings and are decoded as strings
+/// - public keys are Base58 strings and `bytes` are arrays of numbers
+/// - structs are objects, or arrays for tuple structs
+/// - enum variants without fields are strings like `"Active"`, variants
+///   with fields are objects like `{ "Closed": { "at": 10 } }`
+/// - `Option`s are `null` when they are `None`
+///
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn main() -> PoseidonResult<()> {
+/// # let authority = [0; 32];
+/// # let account_data = Vec::new();
+/// let client = AnchorIdlClient::new(AnchorIdl::from_file("target/idl/counter.json")?)?;
+///
+/// let instruction = client
+///     .instruction("increment")?
+///     .add_arg("by", serde_json::json!(3))
+///     .add_account("authority", authority)
+///     .build()?;
+///
+/// let (name, counter) = client.decode_account(&account_data)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnchorIdlClient {
+    idl: AnchorIdl,
+    program_id: PublicKey,
+}
+
+impl AnchorIdlClient {
+    /// Use the program address declared in the IDL
+    pub fn new(idl: AnchorIdl) -> PoseidonResult<Self> {
+        let program_id = idl.program_id()?;
+
+        Ok(AnchorIdlClient { idl, program_id })
+    }
+
+    /// Use the program deployed at `program_id`, for IDLs without an address
+    /// or programs deployed at a different address
+    pub fn new_with_program_id(idl: AnchorIdl, program_id: PublicKey) -> Self {
+        AnchorIdlClient { idl, program_id }
+    }
+
+    pub fn idl(&self) -> &AnchorIdl {
+        &self.idl
+    }
+
+    pub fn program_id(&self) -> PublicKey {
+        self.program_id
+    }
+
+    pub fn instruction(&self, name: &str) -> PoseidonResult<AnchorInstructionBuilder<'_>> {
+        Ok(AnchorInstructionBuilder {
+            client: self,
+            instruction: self.idl.instruction(name)?,
+            args: HashMap::default(),
+            accounts: HashMap::default(),
+            remaining_accounts: Vec::default(),
+        })
+    }
+
+    /// Decode the data of any account of the program, returning the
+    /// account name and its fields
+    pub fn decode_account(&self, data: &[u8]) -> PoseidonResult<(String, Value)> {
+        let account = self
+            .idl
+            .accounts
+            .iter()
+            .find(|account| data.starts_with(&account.discriminator()))
+            .ok_or_else(|| PoseidonError::IdlItemNotFound("account discriminator".to_owned()))?;
+
+        let value = self.decode_account_as(&account.name, data)?;
+
+        Ok((account.name.clone(), value))
+    }
+
+    /// Decode the data of an account named `name`. Data following
+    /// the fields, like unused space, is ignored.
+    pub fn decode_account_as(&self, name: &str, data: &[u8]) -> PoseidonResult<Value> {
+        let account = self
+            .idl
+            .accounts
+            .iter()
+            .find(|account| account.name == name)
+            .ok_or_else(|| PoseidonError::IdlItemNotFound(name.to_owned()))?;
+
+        let discriminator = account.discriminator();
+        let mut data = match data.strip_prefix(discriminator.as_slice()) {
+            Some(data) => data,
+            None => return Err(PoseidonError::InvalidAccountData),
+        };
+
+        IdlCodec { idl: &self.idl }.decode_type_def(self.idl.account_type(account)?, &mut data)
+    }
+
+    /// Borsh encode `value` as the type `ty` of the IDL
+    pub fn encode(&self, ty: &IdlType, value: &Value) -> PoseidonResult<Vec<u8>> {
+        let mut buffer = Vec::new();
+        IdlCodec { idl: &self.idl }.encode(ty, value, &mut buffer)?;
+
+        Ok(buffer)
+    }
+
+    /// Borsh decode a value of type `ty`, advancing `data` past it
+    pub fn decode(&self, ty: &IdlType, data: &mut &[u8]) -> PoseidonResult<Value> {
+        IdlCodec { idl: &self.idl }.decode(ty, data)
+    }
+}
+
+/// Build an instruction of an Anchor program. Accounts with a fixed address
+/// or PDA seeds in the IDL are resolved unless they are added explicitly.
+pub struct AnchorInstructionBuilder<'a> {
+    client: &'a AnchorIdlClient,
+    instruction: &'a IdlInstruction,
+    args: HashMap<String, Value>,
+    accounts: HashMap<String, PublicKey>,
+    remaining_accounts: Vec<AccountMeta>,
+}
+
+impl<'a> AnchorInstructionBuilder<'a> {
+    pub fn add_arg(&mut self, name: &str, value: Value) -> &mut Self {
+        self.args.insert(name.to_owned(), value);
+
+        self
+    }
+
+    pub fn add_account(&mut self, name: &str, public_key: PublicKey) -> &mut Self {
+        self.accounts.insert(name.to_owned(), public_key);
+
+        self
+    }
+
+    /// Accounts passed after the accounts declared in the IDL
+    pub fn add_remaining_account(&mut self, account_meta: AccountMeta) -> &mut Self {
+        self.remaining_accounts.push(account_meta);
+
+        self
+    }
+
+    /// The address of an account of the instruction, resolving it if it was not added
+    pub fn account(&self, name: &str) -> PoseidonResult<PublicKey> {
+        let account = self
+            .instruction
+            .flat_accounts()
+            .into_iter()
+            .find(|account| account.name == name)
+            .ok_or_else(|| PoseidonError::IdlItemNotFound(name.to_owned()))?;
+
+        self.resolve(account, 0)
+    }
+
+    pub fn build(&self) -> PoseidonResult<Instruction> {
+        let codec = IdlCodec {
+            idl: &self.client.idl,
+        };
+
+        let mut data = self.instruction.discriminator();
+        for arg in &self.instruction.args {
+            let value = self
+                .args
+                .get(&arg.name)
+                .ok_or_else(|| PoseidonError::MissingIdlValue(arg.name.clone()))?;
+            codec.encode(&arg.ty, value, &mut data)?;
+        }
+
+        let mut accounts = Vec::new();
+        for account in self.instruction.flat_accounts() {
+            let account_meta = match self.resolve(account, 0) {
+                Ok(pubkey) => AccountMeta {
+                    pubkey,
+                    is_signer: account.signer,
+                    is_writable: account.writable,
+                },
+                // Anchor expects the program ID in place of an optional account that is left out
+                Err(PoseidonError::MissingIdlValue(_)) if account.optional => {
+                    AccountMeta::new_readonly(self.client.program_id, false)
+                }
+                Err(error) => return Err(error),
+            };
+            accounts.push(account_meta);
+        }
+        accounts.extend_from_slice(&self.remaining_accounts);
+
+        Ok(Instruction {
+            program_id: self.client.program_id,
+            accounts,
+            data,
+        })
+    }
+
+    // PDA seeds can refer to other accounts, `depth` stops seeds that refer to each other
+    fn resolve(&self, account: &IdlInstructionAccount, depth: usize) -> PoseidonResult<PublicKey> {
+        if let Some(public_key) = self.accounts.get(&account.name) {
+            return Ok(*public_key);
+        }
+
+        if let Some(address) = &account.address {
+            return Utilities::base58_to_u32_array(address);
+        }
+
+        let pda = match &account.pda {
+            Some(pda) if depth < 8 => pda,
+            _ => return Err(PoseidonError::MissingIdlValue(account.name.clone())),
+        };
+
+        let seeds = pda
+            .seeds
+            .iter()
+            .map(|seed| self.seed_bytes(seed, depth))
+            .collect::<PoseidonResult<Vec<Vec<u8>>>>()?;
+        let seeds = seeds
+            .iter()
+            .map(|seed| seed.as_slice())
+            .collect::<Vec<&[u8]>>();
+
+        let program_id = match &pda.program {
+            Some(program) => {
+                let mut program_id = [0u8; 32];
+                let bytes = self.seed_bytes(program, depth)?;
+                if bytes.len() != program_id.len() {
+                    return Err(PoseidonError::InvalidIdl(format!(
+                        "The PDA program of `{}` is not a public key",
+                        account.name
+                    )));
+                }
+                program_id.copy_from_slice(&bytes);
+
+                program_id
+            }
+            None => self.client.program_id,
+        };
+
+        Ok(PdaBuilder::find_program_address(&seeds, &program_id)?.0)
+    }
+
+    fn seed_bytes(&self, seed: &IdlSeed, depth: usize) -> PoseidonResult<Vec<u8>> {
+        match seed {
+            // Like arguments, strings and bytes are used without their length prefix
+            IdlSeed::Const { ty, value } => match (ty, value) {
+                (Some(IdlType::String) | None, Value::String(value)) => {
+                    Ok(value.as_bytes().to_vec())
+                }
+                (Some(IdlType::Bytes) | None, value) => {
+                    serde_json::from_value::<Vec<u8>>(value.clone())
+                        .map_err(|_| PoseidonError::InvalidIdl(format!("Invalid seed {}", value)))
+                }
+                (Some(ty), value) => self.client.encode(ty, value),
+            },
+            IdlSeed::Arg { path, .. } => {
+                let arg = self
+                    .instruction
+                    .args
+                    .iter()
+                    .find(|arg| &arg.name == path)
+                    .ok_or_else(|| {
+                        PoseidonError::InvalidIdl(format!(
+                            "Seeds from `{}` are not supported",
+                            path
+                        ))
+                    })?;
+                let value = self
+                    .args
+                    .get(path)
+                    .ok_or_else(|| PoseidonError::MissingIdlValue(path.clone()))?;
+
+                // Strings and bytes are used as seeds without their length prefix
+                match (&arg.ty, value) {
+                    (IdlType::String, Value::String(value)) => Ok(value.as_bytes().to_vec()),
+                    (IdlType::Bytes, value) => serde_json::from_value::<Vec<u8>>(value.clone())
+                        .map_err(|_| PoseidonError::InvalidIdlValue(path.clone())),
+                    (ty, value) => self.client.encode(ty, value),
+                }
+            }
+            IdlSeed::Account { path, .. } => {
+                let account = self
+                    .instruction
+                    .flat_accounts()
+                    .into_iter()
+                    .find(|account| &account.name == path)
+                    .ok_or_else(|| {
+                        PoseidonError::InvalidIdl(format!(
+                            "Seeds from `{}` are not supported",
+                            path
+                        ))
+                    })?;
+
+                Ok(self.resolve(account, depth + 1)?.to_vec())
+            }
+        }
+    }
+}
+
+// Borsh encoding and decoding of `serde_json::Value`s
+struct IdlCodec<'a> {
+    idl: &'a AnchorIdl,
+}
+
+impl<'a> IdlCodec<'a> {
+    fn encode(&self, ty: &IdlType, value: &Value, buffer: &mut Vec<u8>) -> PoseidonResult<()> {
+        let invalid = || PoseidonError::InvalidIdlValue(value.to_string());
+
+        match ty {
+            IdlType::Bool => buffer.push(value.as_bool().ok_or_else(invalid)? as u8),
+            IdlType::U8 => buffer.push(integer::<u8>(value)?),
+            IdlType::I8 => buffer.extend_from_slice(&integer::<i8>(value)?.to_le_bytes()),
+            IdlType::U16 => buffer.extend_from_slice(&integer::<u16>(value)?.to_le_bytes()),
+            IdlType::I16 => buffer.extend_from_slice(&integer::<i16>(value)?.to_le_bytes()),
+            IdlType::U32 => buffer.extend_from_slice(&integer::<u32>(value)?.to_le_bytes()),
+            IdlType::I32 => buffer.extend_from_slice(&integer::<i32>(value)?.to_le_bytes()),
+            IdlType::U64 => buffer.extend_from_slice(&integer::<u64>(value)?.to_le_bytes()),
+            IdlType::I64 => buffer.extend_from_slice(&integer::<i64>(value)?.to_le_bytes()),
+            IdlType::U128 => buffer.extend_from_slice(&integer::<u128>(value)?.to_le_bytes()),
+            IdlType::I128 => buffer.extend_from_slice(&integer::<i128>(value)?.to_le_bytes()),
+            IdlType::F32 => {
+                let float = value.as_f64().ok_or_else(invalid)? as f32;
+                buffer.extend_from_slice(&float.to_le_bytes())
+            }
+            IdlType::F64 => {
+                buffer.extend_from_slice(&value.as_f64().ok_or_else(invalid)?.to_le_bytes())
+            }
+            IdlType::Bytes => {
+                let bytes =
+                    serde_json::from_value::<Vec<u8>>(value.clone()).map_err(|_| invalid())?;
+                buffer.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+                buffer.extend_from_slice(&bytes);
+            }
+            IdlType::String => {
+                let string = value.as_str().ok_or_else(invalid)?;
+                buffer.extend_from_slice(&(string.len() as u32).to_le_bytes());
+                buffer.extend_from_slice(string.as_bytes());
+            }
+            IdlType::Pubkey => buffer.extend_from_slice(&Utilities::base58_to_u32_array(
+                value.as_str().ok_or_else(invalid)?,
+            )?),
+            IdlType::Option(ty) => match value {
+                Value::Null => buffer.push(0),
+                value => {
+                    buffer.push(1);
+                    self.encode(ty, value, buffer)?;
+                }
+            },
+            IdlType::Vec(ty) => {
+                let items = value.as_array().ok_or_else(invalid)?;
+                buffer.extend_from_slice(&(items.len() as u32).to_le_bytes());
+                for item in items {
+                    self.encode(ty, item, buffer)?;
+                }
+            }
+            IdlType::Array(ty, len) => {
+                let items = value.as_array().ok_or_else(invalid)?;
+                if items.len() != *len {
+                    return Err(invalid());
+                }
+                for item in items {
+                    self.encode(ty, item, buffer)?;
+                }
+            }
+            IdlType::Defined(defined) => match &self.idl.type_def(defined.name())?.ty {
+                IdlTypeDefTy::Struct { fields } => {
+                    self.encode_fields(fields.as_ref(), value, buffer)?
+                }
+                IdlTypeDefTy::Enum { variants } => {
+                    let (name, fields_value) = match value {
+                        Value::String(name) => (name.as_str(), &Value::Null),
+                        Value::Object(object) if object.len() == 1 => object
+                            .iter()
+                            .next()
+                            .map(|(name, fields)| (name.as_str(), fields))
+                            .ok_or_else(invalid)?,
+                        _ => return Err(invalid()),
+                    };
+                    let (index, variant) = variants
+                        .iter()
+                        .enumerate()
+                        .find(|(_, variant)| variant.name == name)
+                        .ok_or_else(invalid)?;
+
+                    buffer.push(index as u8);
+                    self.encode_fields(variant.fields.as_ref(), fields_value, buffer)?;
+                }
+                IdlTypeDefTy::Type { alias } => self.encode(alias, value, buffer)?,
+            },
+        }
+
+        Ok(())
+    }
+
+    fn encode_fields(
+        &self,
+        fields: Option<&IdlDefinedFields>,
+        value: &Value,
+        buffer: &mut Vec<u8>,
+    ) -> PoseidonResult<()> {
+        let invalid = || PoseidonError::InvalidIdlValue(value.to_string());
+
+        match fields {
+            None => Ok(()),
+            Some(IdlDefinedFields::Named(fields)) => {
+                let object = value.as_object().ok_or_else(invalid)?;
+                fields.iter().try_for_each(|field| {
+                    let value = object
+                        .get(&field.name)
+                        .ok_or_else(|| PoseidonError::MissingIdlValue(field.name.clone()))?;
+                    self.encode(&field.ty, value, buffer)
+                })
+            }
+            Some(IdlDefinedFields::Tuple(types)) => {
+                let items = value.as_array().ok_or_else(invalid)?;
+                if items.len() != types.len() {
+                    return Err(invalid());
+                }
+                types
+                    .iter()
+                    .zip(items)
+                    .try_for_each(|(ty, item)| self.encode(ty, item, buffer))
+            }
+        }
+    }
+
+    fn decode(&self, ty: &IdlType, data: &mut &[u8]) -> PoseidonResult<Value> {
+        let value = match ty {
+            IdlType::Bool => match take::<1>(data)?[0] {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                _ => return Err(PoseidonError::InvalidAccountData),
+            },
+            IdlType::U8 => u8::from_le_bytes(take(data)?).into(),
+            IdlType::I8 => i8::from_le_bytes(take(data)?).into(),
+            IdlType::U16 => u16::from_le_bytes(take(data)?).into(),
+            IdlType::I16 => i16::from_le_bytes(take(data)?).into(),
+            IdlType::U32 => u32::from_le_bytes(take(data)?).into(),
+            IdlType::I32 => i32::from_le_bytes(take(data)?).into(),
+            IdlType::U64 => u64::from_le_bytes(take(data)?).into(),
+            IdlType::I64 => i64::from_le_bytes(take(data)?).into(),
+            // Larger than JSON numbers can hold exactly
+            IdlType::U128 => u128::from_le_bytes(take(data)?).to_string().into(),
+            IdlType::I128 => i128::from_le_bytes(take(data)?).to_string().into(),
+            IdlType::F32 => f32::from_le_bytes(take(data)?).into(),
+            IdlType::F64 => f64::from_le_bytes(take(data)?).into(),
+            IdlType::Bytes => take_slice(data)?.to_vec().into(),
+            IdlType::String => String::from_utf8(take_slice(data)?.to_vec())
+                .map_err(|_| PoseidonError::InvalidAccountData)?
+                .into(),
+            IdlType::Pubkey => bs58::encode(take::<32>(data)?).into_string().into(),
+            IdlType::Option(ty) => match take::<1>(data)?[0] {
+                0 => Value::Null,
+                1 => self.decode(ty, data)?,
+                _ => return Err(PoseidonError::InvalidAccountData),
+            },
+            IdlType::Vec(ty) => {
+                let len = u32::from_le_bytes(take(data)?);
+                (0..len)
+                    .map(|_| self.decode(ty, data))
+                    .collect::<PoseidonResult<Vec<Value>>>()?
+                    .into()
+            }
+            IdlType::Array(ty, len) => (0..*len)
+                .map(|_| self.decode(ty, data))
+                .collect::<PoseidonResult<Vec<Value>>>()?
+                .into(),
+            IdlType::Defined(defined) => {
+                self.decode_type_def(&self.idl.type_def(defined.name())?.ty, data)?
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn decode_type_def(&self, ty: &IdlTypeDefTy, data: &mut &[u8]) -> PoseidonResult<Value> {
+        match ty {
+            IdlTypeDefTy::Struct { fields } => self.decode_fields(fields.as_ref(), data),
+            IdlTypeDefTy::Enum { variants } => {
+                let index = take::<1>(data)?[0] as usize;
+                let variant = variants
+                    .get(index)
+                    .ok_or(PoseidonError::InvalidAccountData)?;
+
+                match &variant.fields {
+                    None => Ok(Value::String(variant.name.clone())),
+                    Some(fields) => {
+                        let mut object = Map::new();
+                        object.insert(
+                            variant.name.clone(),
+                            self.decode_fields(Some(fields), data)?,
+                        );
+
+                        Ok(Value::Object(object))
+                    }
+                }
+            }
+            IdlTypeDefTy::Type { alias } => self.decode(alias, data),
+        }
+    }
+
+    fn decode_fields(
+        &self,
+        fields: Option<&IdlDefinedFields>,
+        data: &mut &[u8],
+    ) -> PoseidonResult<Value> {
+        match fields {
+            None => Ok(Value::Null),
+            Some(IdlDefinedFields::Named(fields)) => {
+                let mut object = Map::new();
+                for field in fields {
+                    object.insert(field.name.clone(), self.decode(&field.ty, data)?);
+                }
+
+                Ok(Value::Object(object))
+            }
+            Some(IdlDefinedFields::Tuple(types)) => Ok(types
+                .iter()
+                .map(|ty| self.decode(ty, data))
+                .collect::<PoseidonResult<Vec<Value>>>()?
+                .into()),
+        }
+    }
+}
+
+// Integers can be JSON numbers or strings, since `u64` and larger
+// values do not always fit in the numbers of other JSON libraries
+fn integer<T: TryFrom<i128> + core::str::FromStr>(value: &Value) -> PoseidonResult<T> {
+    let invalid = || PoseidonError::InvalidIdlValue(value.to_string());
+
+    match value {
+        Value::Number(number) => number
+            .as_u64()
+            .map(i128::from)
+            .or_else(|| number.as_i64().map(i128::from))
+            .and_then(|number| T::try_from(number).ok())
+            .ok_or_else(invalid),
+        Value::String(string) => string.parse::<T>().map_err(|_| invalid()),
+        _ => Err(invalid()),
+    }
+}
+
+fn take<const N: usize>(data: &mut &[u8]) -> PoseidonResult<[u8; N]> {
+    if data.len() < N {
+        return Err(PoseidonError::InvalidAccountData);
+    }
+
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&data[..N]);
+    *data = &data[N..];
+
+    Ok(bytes)
+}
+
+// A slice prefixed with its `u32` length
+fn take_slice<'d>(data: &mut &'d [u8]) -> PoseidonResult<&'d [u8]> {
+    let len = u32::from_le_bytes(take(data)?) as usize;
+    if data.len() < len {
+        return Err(PoseidonError::InvalidAccountData);
+    }
+
+    let (slice, rest) = data.split_at(len);
+    *data = rest;
+
+    Ok(slice)
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::{anchor_discriminator, InstructionError, SYSTEM_PROGRAM_ID};
+    use serde_json::json;
+
+    const PROGRAM_ID: PublicKey = [7; 32];
+    const AUTHORITY: PublicKey = [1; 32];
+
+    fn client() -> AnchorIdlClient {
+        AnchorIdlClient::new(
+            AnchorIdl::from_json(include_str!("fixtures/counter_idl.json")).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn build_instructions() {
+        let client = client();
+        assert_eq!(client.program_id(), PROGRAM_ID);
+
+        let mut initialize = client.instruction("initialize").unwrap();
+        initialize
+            .add_arg("start", json!(10))
+            .add_arg("label", json!("ab"))
+            .add_arg("mode", json!({ "Capped": { "max": "300" } }))
+            .add_arg("limit", Value::Null)
+            .add_account("authority", AUTHORITY);
+        let instruction = initialize.build().unwrap();
+
+        let mut data = anchor_discriminator("global", "initialize").to_vec();
+        data.extend_from_slice(&10u64.to_le_bytes());
+        data.extend_from_slice(&[2, 0, 0, 0, b'a', b'b']);
+        data.push(1);
+        data.extend_from_slice(&300u64.to_le_bytes());
+        data.push(0);
+        assert_eq!(instruction.data, data);
+
+        let (counter, _) =
+            PdaBuilder::find_program_address(&[b"counter", &AUTHORITY, b"ab"], &PROGRAM_ID)
+                .unwrap();
+        assert_eq!(initialize.account("counter").unwrap(), counter);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(counter, false),
+                AccountMeta::new(AUTHORITY, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ]
+        );
+
+        let mut increment = client.instruction("increment").unwrap();
+        increment
+            .add_arg("by", json!(1))
+            .add_account("authority", AUTHORITY);
+        assert!(matches!(
+            increment.build(),
+            Err(PoseidonError::MissingIdlValue(account)) if account == "counter"
+        ));
+
+        increment.add_account("counter", counter);
+        let instruction = increment.build().unwrap();
+        assert_eq!(&instruction.data[..8], &[11, 18, 104, 9, 104, 174, 59, 33]);
+        // The program ID stands in for optional accounts that are left out
+        assert_eq!(
+            instruction.accounts[2],
+            AccountMeta::new_readonly(PROGRAM_ID, false)
+        );
+
+        assert!(matches!(
+            client
+                .instruction("increment")
+                .unwrap()
+                .add_arg("by", json!(-1))
+                .add_account("counter", counter)
+                .add_account("authority", AUTHORITY)
+                .build(),
+            Err(PoseidonError::InvalidIdlValue(_))
+        ));
+        assert!(matches!(
+            client.instruction("reset"),
+            Err(PoseidonError::IdlItemNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn decode_accounts() {
+        let client = client();
+
+        let mut data = anchor_discriminator("account", "Counter").to_vec();
+        data.extend_from_slice(&AUTHORITY);
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.extend_from_slice(&[2, 0, 0, 0, b'a', b'b']);
+        data.extend_from_slice(&[2, 3, 4]);
+        data.extend_from_slice(&[2, 0, 0, 0, 0xff, 0xff, 5, 0]);
+        data.extend_from_slice(&u128::MAX.to_le_bytes());
+        data.push(254);
+        // Unused space at the end of the account
+        data.extend_from_slice(&[0; 16]);
+
+        let (name, counter) = client.decode_account(&data).unwrap();
+        assert_eq!(name, "Counter");
+        assert_eq!(
+            counter,
+            json!({
+                "authority": bs58::encode(AUTHORITY).into_string(),
+                "count": 42,
+                "label": "ab",
+                "mode": { "Ratio": [3, 4] },
+                "history": [-1, 5],
+                "total": u128::MAX.to_string(),
+                "bump": [254],
+            })
+        );
+
+        assert!(matches!(
+            client.decode_account(&[0; 8]),
+            Err(PoseidonError::IdlItemNotFound(_))
+        ));
+        assert!(matches!(
+            client.decode_account_as("Counter", &data[..20]),
+            Err(PoseidonError::InvalidAccountData)
+        ));
+
+        let errors = client.idl().anchor_errors();
+        assert_eq!(
+            errors
+                .decode_instruction_error(&InstructionError::Custom(6000))
+                .unwrap()
+                .name,
+            "Overflow"
+        );
+    }
+
+    #[test]
+    fn legacy_idl() {
+        let idl = AnchorIdl::from_json(
+            r#"{
+                "version": "0.1.0",
+                "name": "vault",
+                "instructions": [{
+                    "name": "depositTokens",
+                    "accounts": [
+                        { "name": "vault", "isMut": true, "isSigner": false },
+                        { "name": "owner", "isMut": false, "isSigner": true }
+                    ],
+                    "args": [{ "name": "owner", "type": "publicKey" }, { "name": "kind", "type": { "defined": "Kind" } }]
+                }],
+                "accounts": [{
+                    "name": "Vault",
+                    "type": { "kind": "struct", "fields": [{ "name": "amount", "type": "u64" }] }
+                }],
+                "types": [{ "name": "Kind", "type": { "kind": "enum", "variants": [{ "name": "Spl" }, { "name": "Native" }] } }],
+                "metadata": { "address": "US517G5965aydkZ46HS38QLi7UQiSojurfbQfKCELFx" }
+            }"#,
+        )
+        .unwrap();
+        let client = AnchorIdlClient::new(idl).unwrap();
+
+        let instruction = client
+            .instruction("depositTokens")
+            .unwrap()
+            .add_arg("owner", json!(bs58::encode(AUTHORITY).into_string()))
+            .add_arg("kind", json!("Native"))
+            .add_account("vault", [2; 32])
+            .add_account("owner", AUTHORITY)
+            .build()
+            .unwrap();
+        assert_eq!(
+            &instruction.data[..8],
+            &anchor_discriminator("global", "deposit_tokens")
+        );
+        assert_eq!(&instruction.data[8..40], &AUTHORITY);
+        assert_eq!(instruction.data[40], 1);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new([2; 32], false),
+                AccountMeta::new_readonly(AUTHORITY, true),
+            ]
+        );
+
+        let mut data = anchor_discriminator("account", "Vault").to_vec();
+        data.extend_from_slice(&7u64.to_le_bytes());
+        assert_eq!(
+            client.decode_account(&data).unwrap(),
+            ("Vault".to_owned(), json!({ "amount": 7 }))
+        );
+    }
+
+    #[test]
+    fn legacy_discriminators() {
+        let instruction = |name: &str| IdlInstruction {
+            name: name.to_owned(),
+            discriminator: None,
+            accounts: Vec::new(),
+            args: Vec::new(),
+        };
+
+        assert_eq!(
+            instruction("initializeNFT").discriminator(),
+            anchor_discriminator("global", "initialize_nft")
+        );
+        assert_eq!(
+            instruction("setAuthorityV2").discriminator(),
+            anchor_discriminator("global", "set_authority_v2")
+        );
+    }
+
+    #[test]
+    fn const_seeds() {
+        let client = client();
+        let builder = client.instruction("increment").unwrap();
+        let seed = |ty: Option<IdlType>, value: Value| {
+            builder.seed_bytes(&IdlSeed::Const { ty, value }, 0)
+        };
+
+        assert_eq!(
+            seed(
+                Some(IdlType::Pubkey),
+                json!(bs58::encode(AUTHORITY).into_string())
+            )
+            .unwrap(),
+            AUTHORITY
+        );
+        assert_eq!(
+            seed(Some(IdlType::String), json!("vault")).unwrap(),
+            b"vault"
+        );
+        assert_eq!(seed(None, json!("vault")).unwrap(), b"vault");
+        assert_eq!(seed(Some(IdlType::Bytes), json!([1, 2])).unwrap(), [1, 2]);
+        assert_eq!(seed(None, json!([1, 2])).unwrap(), [1, 2]);
+        assert_eq!(seed(Some(IdlType::U16), json!(258)).unwrap(), [2, 1]);
+        assert!(seed(None, json!({ "seed": 1 })).is_err());
+    }
+}
@@ -0,0 +1,11 @@
+mod events;
+pub use events::*;
+
+mod errors;
+pub use errors::*;
+
+mod idl;
+pub use idl::*;
+
+mod idl_client;
+pub use idl_client::*;
@@ -1,8 +1,11 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, BorshSerialize, BorshDeserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, BorshSerialize, BorshDeserialize, Default,
+)]
 pub enum Cluster {
     LocalNet,
+    #[default]
     DevNet,
     TestNet,
     MainNetBeta,
@@ -18,9 +21,3 @@ impl Cluster {
         }
     }
 }
-
-impl Default for Cluster {
-    fn default() -> Self {
-        Cluster::DevNet
-    }
-}
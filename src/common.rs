@@ -30,20 +30,16 @@ pub type UnixTimestamp = i64;
     Eq,
     Copy,
     Clone,
+    Default,
 )]
 pub enum Commitment {
     Processed,
     Confirmed,
+    #[default]
     Finalized,
     Unspecified,
 }
 
-impl Default for Commitment {
-    fn default() -> Self {
-        Commitment::Finalized
-    }
-}
-
 impl From<&str> for Commitment {
     fn from(value: &str) -> Self {
         match value.to_lowercase().as_str() {
@@ -55,9 +51,9 @@ impl From<&str> for Commitment {
     }
 }
 
-impl Into<&str> for Commitment {
-    fn into(self) -> &'static str {
-        match self {
+impl From<Commitment> for &str {
+    fn from(commitment: Commitment) -> Self {
+        match commitment {
             Commitment::Processed => "processed",
             Commitment::Confirmed => "confirmed",
             Commitment::Finalized => "finalized",
@@ -1,3 +1,4 @@
+use crate::{Commitment, RpcTxError, TransactionError};
 use borsh::{BorshDeserialize, BorshSerialize};
 use core::fmt;
 use serde::{Deserialize, Serialize};
@@ -16,6 +17,12 @@ pub enum PoseidonError {
     /// The maximum length of the `seed` provided has been exceeded
     /// as indicated by `[MAX_SEED_LEN]`
     MaxSeedLengthExceeded,
+    /// More seeds than `[MAX_SEEDS]` were provided to derive a program address
+    MaxSeedsExceeded,
+    /// The seeds provided yield an address that lies on the ed25519 curve
+    InvalidSeeds,
+    /// No bump seed yielded an address that lies off the ed25519 curve
+    UnableToFindViableProgramAddress,
     /// The `owner` public key of the PDA provided is the same as the
     /// `[PDA_MARKER]` address. This is not allowed.
     IllegalOwner,
@@ -35,8 +42,70 @@ pub enum PoseidonError {
     Bs58Decode(bs58::decode::Error),
     /// Error encoding to base58 format
     Bs58Encode(bs58::encode::Error),
+    /// Error decoding string as Base64 format
+    Base64Decode(base64::DecodeError),
+    /// The encoding of the account data returned by the RPC is not supported
+    UnsupportedAccountEncoding(String),
+    /// The account data does not match the layout of the account being decoded
+    InvalidAccountData,
     /// The transaction was not found in the Cluster
     TransactionNotFoundInCluster,
+    /// The instruction data is too large for its offsets to be encoded
+    InstructionDataTooLarge,
+    /// The instruction data does not match the layout expected by the program
+    InvalidInstructionData,
+    /// The signature does not match the public key and message
+    SignatureVerificationFailed,
+    /// A keypair required to sign the `Message` was not provided
+    MissingSigner,
+    /// The RPC node rejected the transaction
+    TransactionRejected(Box<RpcTxError>),
+    /// The transaction was processed by the cluster but failed
+    TransactionFailed(TransactionError),
+    /// The transaction was not confirmed before the confirmation attempts ran out
+    TransactionConfirmationTimeout,
+    /// The blockhash has expired or is unknown to the RPC node
+    BlockhashNotFound,
+    /// The RPC node responded with a JSON-RPC error
+    JsonRpc(JsonRpcError),
+    /// The transaction was returned in an encoding that cannot be decoded into a `Transaction`
+    UnsupportedTransactionEncoding(String),
+    /// Only legacy transactions can be decoded into a `Transaction`
+    UnsupportedTransactionVersion(u8),
+    /// The block of the slot was still not available after all the retries
+    BlockNotAvailable(u64),
+    /// The event data does not match the layout of the registered event
+    InvalidEventData,
+    /// The IDL uses a feature that is not supported
+    InvalidIdl(String),
+    /// The instruction, account or type was not found in the IDL
+    IdlItemNotFound(String),
+    /// The argument or account required by the IDL was not provided
+    MissingIdlValue(String),
+    /// The value does not match the type declared in the IDL
+    InvalidIdlValue(String),
+    /// The bytes are not a 64 byte ed25519 secret key followed by its public key
+    InvalidKeypair,
+    /// The entropy of a mnemonic must be 16, 20, 24, 28 or 32 bytes
+    InvalidEntropyLength(usize),
+    /// The phrase has a word count other than 12, 15, 18, 21 or 24
+    /// or a word that is not in the BIP39 wordlist
+    InvalidMnemonic(String),
+    /// The checksum of the mnemonic does not match its entropy
+    InvalidMnemonicChecksum,
+    /// The derivation path is not a path of hardened indexes like `m/44'/501'/0'/0'`
+    InvalidDerivationPath(String),
+    /// Unable to get random bytes from the operating system
+    RandomnessUnavailable,
+    /// The vanity pattern is empty, longer than an address or has a character that is not
+    /// in the Base58 alphabet
+    InvalidVanityPattern(String),
+    /// The chunk size used to split program data into `Write` instructions is zero
+    InvalidChunkSize,
+    /// A value does not fit in the integer type required by the instruction layout
+    IntegerOverflow,
+    /// The `BlockhashCache` failed to reach the cluster for each of these commitments
+    BlockhashCacheFailed(Vec<(Commitment, PoseidonError)>),
 }
 
 impl std::error::Error for PoseidonError {}
@@ -59,6 +128,12 @@ impl From<bs58::decode::Error> for PoseidonError {
     }
 }
 
+impl From<base64::DecodeError> for PoseidonError {
+    fn from(error: base64::DecodeError) -> Self {
+        PoseidonError::Base64Decode(error)
+    }
+}
+
 impl From<bincode::Error> for PoseidonError {
     fn from(error: bincode::Error) -> Self {
         PoseidonError::BincodeError(*error)
@@ -71,6 +146,12 @@ impl From<std::io::Error> for PoseidonError {
     }
 }
 
+impl From<std::num::TryFromIntError> for PoseidonError {
+    fn from(_: std::num::TryFromIntError) -> Self {
+        PoseidonError::IntegerOverflow
+    }
+}
+
 impl From<serde_json::Error> for PoseidonError {
     fn from(error: serde_json::Error) -> Self {
         PoseidonError::SerdeJsonDeser(error.to_string())
@@ -178,15 +259,15 @@ pub enum Minreq {
     Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, PartialOrd, Clone,
 )]
 pub struct RpcResponseJsonError {
-    jsonrpc: String,
-    error: JsonRpcError,
-    id: u8,
+    pub jsonrpc: String,
+    pub error: JsonRpcError,
+    pub id: u8,
 }
 
 #[derive(
     Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, PartialOrd, Clone,
 )]
 pub struct JsonRpcError {
-    code: i16,
-    message: String,
+    pub code: i16,
+    pub message: String,
 }
@@ -0,0 +1,286 @@
+use crate::{PoseidonError, PoseidonResult, PublicKey};
+use core::{fmt, str::FromStr};
+use ed25519_dalek::{Keypair, SecretKey};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+/// Indexes at or above it are hardened, written with a `'` in derivation paths
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// The BIP44 coin type of Solana
+pub const SOLANA_COIN_TYPE: u32 = 501;
+
+/// ### A path of hardened indexes like `m/44'/501'/0'/0'`
+/// SLIP-0010 only defines hardened derivation for ed25519 so every
+/// index is hardened. Indexes can be given with or without the hardened
+/// offset and are kept without it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DerivationPath {
+    indexes: Vec<u32>,
+}
+
+impl DerivationPath {
+    pub fn new(indexes: &[u32]) -> Self {
+        DerivationPath {
+            indexes: indexes
+                .iter()
+                .map(|index| index & !HARDENED_OFFSET)
+                .collect(),
+        }
+    }
+
+    /// `m/44'/501'/<account>'/<change>'`, the path of Phantom and Solflare is
+    /// `m/44'/501'/0'/0'` and the Solana CLI uses `m/44'/501'` by default
+    pub fn solana(account: u32, change: Option<u32>) -> Self {
+        let mut indexes = vec![44, SOLANA_COIN_TYPE, account];
+        indexes.extend(change);
+
+        DerivationPath::new(&indexes)
+    }
+
+    pub fn indexes(&self) -> &[u32] {
+        &self.indexes
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = PoseidonError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let invalid = || PoseidonError::InvalidDerivationPath(path.to_owned());
+
+        let mut segments = path.trim().split('/');
+        if segments.next() != Some("m") {
+            return Err(invalid());
+        }
+
+        let indexes = segments
+            .map(|segment| {
+                segment
+                    .strip_suffix('\'')
+                    .or_else(|| segment.strip_suffix('h'))
+                    .and_then(|index| index.parse::<u32>().ok())
+                    .filter(|index| *index < HARDENED_OFFSET)
+                    .ok_or_else(invalid)
+            })
+            .collect::<PoseidonResult<Vec<u32>>>()?;
+
+        Ok(DerivationPath { indexes })
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        self.indexes
+            .iter()
+            .try_for_each(|index| write!(f, "/{}'", index))
+    }
+}
+
+/// ### An ed25519 secret key and chain code derived with SLIP-0010
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn main() -> PoseidonResult<()> {
+/// # let mnemonic = Mnemonic::generate(MnemonicWordCount::Words12)?;
+/// let master = ExtendedSecretKey::from_seed(&mnemonic.to_seed(""));
+/// let keypair = master.derive_path(&"m/44'/501'/0'/0'".parse()?).keypair();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExtendedSecretKey {
+    pub depth: u8,
+    /// The index the key was derived with, without the hardened offset
+    pub index: u32,
+    pub secret_key: [u8; 32],
+    pub chain_code: [u8; 32],
+}
+
+impl ExtendedSecretKey {
+    /// The master key of a seed, usually the BIP39 seed of a mnemonic
+    pub fn from_seed(seed: &[u8]) -> Self {
+        ExtendedSecretKey::from_hmac(0, 0, hmac_sha512(b"ed25519 seed", seed))
+    }
+
+    /// The hardened child at `index`, with or without the hardened offset
+    pub fn derive_child(&self, index: u32) -> Self {
+        let index = index & !HARDENED_OFFSET;
+
+        let mut data = Vec::with_capacity(37);
+        data.push(0);
+        data.extend_from_slice(&self.secret_key);
+        data.extend_from_slice(&(index | HARDENED_OFFSET).to_be_bytes());
+
+        ExtendedSecretKey::from_hmac(
+            self.depth.saturating_add(1),
+            index,
+            hmac_sha512(&self.chain_code, &data),
+        )
+    }
+
+    pub fn derive_path(&self, path: &DerivationPath) -> Self {
+        path.indexes()
+            .iter()
+            .fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.keypair().public.to_bytes()
+    }
+
+    pub fn keypair(&self) -> Keypair {
+        // Any 32 bytes are a valid ed25519 secret key
+        let secret =
+            SecretKey::from_bytes(&self.secret_key).expect("An ed25519 secret key is 32 bytes");
+        let public = (&secret).into();
+
+        Keypair { secret, public }
+    }
+
+    fn from_hmac(depth: u8, index: u32, hmac: [u8; 64]) -> Self {
+        let mut secret_key = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        secret_key.copy_from_slice(&hmac[..32]);
+        chain_code.copy_from_slice(&hmac[32..]);
+
+        ExtendedSecretKey {
+            depth,
+            index,
+            secret_key,
+            chain_code,
+        }
+    }
+}
+
+impl fmt::Debug for ExtendedSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedSecretKey")
+            .field("depth", &self.depth)
+            .field("index", &self.index)
+            .field("public_key", &bs58::encode(self.public_key()).into_string())
+            .finish()
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(data);
+
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+
+    // SLIP-0010 ed25519 test vector 1 as (path, chain code, private key, public key)
+    const VECTOR_1: &[(&str, &str, &str, &str)] = &[
+        (
+            "m",
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            "00a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+        ),
+        (
+            "m/0'",
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            "008c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+        ),
+        (
+            "m/0'/1'",
+            "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+            "001932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+        ),
+        (
+            "m/0'/1'/2'",
+            "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+            "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+            "00ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1",
+        ),
+        (
+            "m/0'/1'/2'/2'",
+            "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+            "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+            "008abae2d66361c879b900d204ad2cc4984fa2aa344dd7ddc46007329ac76c429c",
+        ),
+        (
+            "m/0'/1'/2'/2'/1000000000'",
+            "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+            "003c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a",
+        ),
+    ];
+
+    #[test]
+    fn slip10_vector_1() {
+        let master =
+            ExtendedSecretKey::from_seed(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap());
+
+        for (path, chain_code, secret_key, public_key) in VECTOR_1 {
+            let path = path.parse::<DerivationPath>().unwrap();
+            let key = master.derive_path(&path);
+
+            assert_eq!(hex::encode(key.chain_code), *chain_code);
+            assert_eq!(hex::encode(key.secret_key), *secret_key);
+            assert_eq!(hex::encode(key.public_key()), public_key[2..]);
+            assert_eq!(usize::from(key.depth), path.indexes().len());
+        }
+    }
+
+    #[test]
+    fn slip10_vector_2() {
+        let master = ExtendedSecretKey::from_seed(&hex::decode("fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542").unwrap());
+        assert_eq!(
+            hex::encode(master.secret_key),
+            "171cb88b1b3c1db25add599712e36245d75bc65a1a5c9e18d76f9f2b1eab4012"
+        );
+        assert_eq!(
+            hex::encode(master.chain_code),
+            "ef70a74db9c3a5af931b5fe73ed8e1a53464133654fd55e7a66f8570b8e33c3b"
+        );
+
+        let child = master.derive_child(0);
+        assert_eq!(
+            hex::encode(child.secret_key),
+            "1559eb2bbec5790b0c65d8693e4d0875b1747f4970ae8b650486ed7470845635"
+        );
+        assert_eq!(
+            hex::encode(child.chain_code),
+            "0b78a3226f915c082bf118f83618a618ab6dec793752624cbeb622acb562862d"
+        );
+    }
+
+    #[test]
+    fn derivation_paths() {
+        let path = "m/44'/501'/0'/0'".parse::<DerivationPath>().unwrap();
+        assert_eq!(path, DerivationPath::solana(0, Some(0)));
+        assert_eq!(path.to_string(), "m/44'/501'/0'/0'");
+        assert_eq!(
+            "m/44h/501h/2h".parse::<DerivationPath>().unwrap(),
+            DerivationPath::solana(2, None)
+        );
+
+        assert!(matches!(
+            "m/44'/501/0'".parse::<DerivationPath>(),
+            Err(PoseidonError::InvalidDerivationPath(_))
+        ));
+        assert!("44'/501'".parse::<DerivationPath>().is_err());
+        assert!("m/2147483648'".parse::<DerivationPath>().is_err());
+
+        // The hardened offset is optional for indexes given as numbers
+        assert_eq!(
+            DerivationPath::new(&[44 | HARDENED_OFFSET, 501 | HARDENED_OFFSET, 2]),
+            DerivationPath::solana(2 | HARDENED_OFFSET, None)
+        );
+        assert_eq!(path, DerivationPath::new(&[44, 501, 0, 0]));
+
+        let master = ExtendedSecretKey::from_seed(&[1; 64]);
+        assert_eq!(
+            master.derive_child(7),
+            master.derive_child(7 | HARDENED_OFFSET)
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    DerivationPath, ExtendedSecretKey, PoseidonError, PoseidonResult, BIP39_ENGLISH_WORDLIST,
+};
+use ed25519_dalek::Keypair;
+use sha2::{Digest, Sha256, Sha512};
+
+/// The number of PBKDF2 rounds BIP39 uses to derive the seed
+pub const BIP39_PBKDF2_ROUNDS: u32 = 2048;
+
+/// The number of words of a mnemonic, 12 and 24 are the most common
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum MnemonicWordCount {
+    #[default]
+    Words12,
+    Words15,
+    Words18,
+    Words21,
+    Words24,
+}
+
+impl MnemonicWordCount {
+    pub fn words(&self) -> usize {
+        match self {
+            MnemonicWordCount::Words12 => 12,
+            MnemonicWordCount::Words15 => 15,
+            MnemonicWordCount::Words18 => 18,
+            MnemonicWordCount::Words21 => 21,
+            MnemonicWordCount::Words24 => 24,
+        }
+    }
+
+    /// 4 bytes of entropy for every 3 words
+    pub fn entropy_len(&self) -> usize {
+        self.words() / 3 * 4
+    }
+}
+
+/// ### A BIP39 mnemonic with the English wordlist
+/// The seed of the mnemonic derives the same keypairs as Phantom, Solflare
+/// and the Solana CLI with `DerivationPath::solana`.
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn main() -> PoseidonResult<()> {
+/// let mnemonic = Mnemonic::generate(MnemonicWordCount::Words24)?;
+/// println!("{}", mnemonic.phrase());
+///
+/// let restored = Mnemonic::from_phrase(&mnemonic.phrase())?;
+/// let keypair = restored.to_keypair("", &DerivationPath::solana(0, Some(0)));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    entropy: Vec<u8>,
+    words: Vec<&'static str>,
+}
+
+impl Mnemonic {
+    /// A new mnemonic from the random number generator of the operating system
+    pub fn generate(word_count: MnemonicWordCount) -> PoseidonResult<Self> {
+        let mut entropy = vec![0u8; word_count.entropy_len()];
+        getrandom::getrandom(&mut entropy).map_err(|_| PoseidonError::RandomnessUnavailable)?;
+
+        Mnemonic::from_entropy(&entropy)
+    }
+
+    pub fn from_entropy(entropy: &[u8]) -> PoseidonResult<Self> {
+        if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+            return Err(PoseidonError::InvalidEntropyLength(entropy.len()));
+        }
+
+        // The checksum is the first bit of the SHA256 hash for every 4 bytes of entropy
+        let checksum_bits = entropy.len() / 4;
+        let mut bits = entropy.to_vec();
+        bits.push(Sha256::digest(entropy)[0]);
+
+        let words = (0..(entropy.len() * 8 + checksum_bits) / 11)
+            .map(|word| {
+                let index = (0..11).fold(0usize, |index, offset| {
+                    let bit = word * 11 + offset;
+                    (index << 1) | ((bits[bit / 8] >> (7 - bit % 8)) & 1) as usize
+                });
+
+                BIP39_ENGLISH_WORDLIST[index]
+            })
+            .collect();
+
+        Ok(Mnemonic {
+            entropy: entropy.to_vec(),
+            words,
+        })
+    }
+
+    /// Validate the words and checksum of a phrase. Words are separated
+    /// by whitespace and matched without regard to case.
+    pub fn from_phrase(phrase: &str) -> PoseidonResult<Self> {
+        let words = phrase
+            .split_whitespace()
+            .map(|word| word.to_lowercase())
+            .collect::<Vec<String>>();
+
+        if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+            return Err(PoseidonError::InvalidMnemonic(format!(
+                "{} words",
+                words.len()
+            )));
+        }
+
+        let mut bits = vec![0u8; (words.len() * 11).div_ceil(8)];
+        for (position, word) in words.iter().enumerate() {
+            let index = BIP39_ENGLISH_WORDLIST
+                .binary_search(&word.as_str())
+                .map_err(|_| PoseidonError::InvalidMnemonic(word.clone()))?;
+
+            (0..11).for_each(|offset| {
+                if index & (1 << (10 - offset)) != 0 {
+                    let bit = position * 11 + offset;
+                    bits[bit / 8] |= 1 << (7 - bit % 8);
+                }
+            });
+        }
+
+        let entropy_len = words.len() / 3 * 4;
+        let mnemonic = Mnemonic::from_entropy(&bits[..entropy_len])?;
+
+        if mnemonic.words.iter().ne(words.iter()) {
+            return Err(PoseidonError::InvalidMnemonicChecksum);
+        }
+
+        Ok(mnemonic)
+    }
+
+    /// The words separated by a single space
+    pub fn phrase(&self) -> String {
+        self.words.join(" ")
+    }
+
+    pub fn words(&self) -> &[&'static str] {
+        &self.words
+    }
+
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// The 64 byte BIP39 seed, `passphrase` is empty unless the wallet
+    /// was created with one. Passphrases with non ASCII characters must
+    /// already be NFKD normalized.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        let mut salt = b"mnemonic".to_vec();
+        salt.extend_from_slice(passphrase.as_bytes());
+
+        let mut seed = [0u8; 64];
+        pbkdf2::pbkdf2_hmac::<Sha512>(
+            self.phrase().as_bytes(),
+            &salt,
+            BIP39_PBKDF2_ROUNDS,
+            &mut seed,
+        );
+
+        seed
+    }
+
+    /// The keypair at `path` derived from the seed of the mnemonic
+    pub fn to_keypair(&self, passphrase: &str, path: &DerivationPath) -> Keypair {
+        ExtendedSecretKey::from_seed(&self.to_seed(passphrase))
+            .derive_path(path)
+            .keypair()
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+
+    // From the BIP39 test vectors, all with the passphrase `TREZOR`
+    const VECTORS: &[(&str, &str, &str)] = &[
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+            "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069",
+        ),
+        (
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+            "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+        ),
+    ];
+
+    #[test]
+    fn bip39_vectors() {
+        for (entropy, phrase, seed) in VECTORS {
+            let mnemonic = Mnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(&mnemonic.phrase(), phrase);
+            assert_eq!(hex::encode(mnemonic.to_seed("TREZOR")), *seed);
+
+            let restored = Mnemonic::from_phrase(&phrase.to_uppercase()).unwrap();
+            assert_eq!(restored.entropy(), mnemonic.entropy());
+        }
+    }
+
+    #[test]
+    fn invalid_phrases() {
+        assert!(matches!(
+            Mnemonic::from_phrase("abandon abandon abandon"),
+            Err(PoseidonError::InvalidMnemonic(_))
+        ));
+        assert!(matches!(
+            Mnemonic::from_phrase(
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon poseidon"
+            ),
+            Err(PoseidonError::InvalidMnemonic(word)) if word == "poseidon"
+        ));
+        assert!(matches!(
+            Mnemonic::from_phrase(
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"
+            ),
+            Err(PoseidonError::InvalidMnemonicChecksum)
+        ));
+        assert!(matches!(
+            Mnemonic::from_entropy(&[0; 15]),
+            Err(PoseidonError::InvalidEntropyLength(15))
+        ));
+    }
+
+    #[test]
+    fn generate() {
+        let mnemonic = Mnemonic::generate(MnemonicWordCount::Words24).unwrap();
+        assert_eq!(mnemonic.words().len(), 24);
+        assert_eq!(mnemonic.entropy().len(), 32);
+        assert!(Mnemonic::from_phrase(&mnemonic.phrase()).unwrap() == mnemonic);
+    }
+}
@@ -0,0 +1,8 @@
+mod wordlist;
+pub use wordlist::*;
+
+mod mnemonic;
+pub use mnemonic::*;
+
+mod derivation;
+pub use derivation::*;
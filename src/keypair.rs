@@ -0,0 +1,192 @@
+use crate::{Base58SecretKey, PoseidonError, PoseidonResult};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, KEYPAIR_LENGTH, SECRET_KEY_LENGTH};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// ### Import and export keypairs in the formats of the Solana CLI
+/// Keypair files like `~/.config/solana/id.json` hold a JSON array of the 64 bytes
+/// of the secret key followed by the public key. Wallets export the same 64 bytes
+/// as a Base58 string. Paths can also be `camino::Utf8Path`s.
+/// ```no_run
+/// # use ed25519_dalek::Keypair;
+/// # use poseidon_client::*;
+/// # fn main() -> PoseidonResult<()> {
+/// let keypair = Keypair::read_from_file(default_keypair_path()?)?;
+/// keypair.write_to_file("backup.json")?;
+///
+/// let imported = Keypair::from_base58_string(&keypair.to_base58_string())?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait KeypairExt: Sized {
+    fn read_from_file(path: impl AsRef<Path>) -> PoseidonResult<Self>;
+
+    /// Write the keypair as a JSON array. On Unix the file is only
+    /// readable and writable by its owner.
+    fn write_to_file(&self, path: impl AsRef<Path>) -> PoseidonResult<()>;
+
+    fn from_json(json: &str) -> PoseidonResult<Self>;
+
+    fn to_json(&self) -> String;
+
+    fn from_base58_string(secret_key: &str) -> PoseidonResult<Self>;
+
+    fn to_base58_string(&self) -> Base58SecretKey;
+}
+
+impl KeypairExt for Keypair {
+    fn read_from_file(path: impl AsRef<Path>) -> PoseidonResult<Self> {
+        Keypair::from_json(&fs::read_to_string(path)?)
+    }
+
+    fn write_to_file(&self, path: impl AsRef<Path>) -> PoseidonResult<()> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let mut file = create_owner_only(path)?;
+        file.write_all(self.to_json().as_bytes())?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    fn from_json(json: &str) -> PoseidonResult<Self> {
+        let bytes = serde_json::from_str::<Vec<u8>>(json)?;
+
+        keypair_from_bytes(&bytes)
+    }
+
+    fn to_json(&self) -> String {
+        // Serializing a byte array to JSON cannot fail
+        serde_json::to_string(&self.to_bytes().to_vec()).unwrap_or_default()
+    }
+
+    fn from_base58_string(secret_key: &str) -> PoseidonResult<Self> {
+        keypair_from_bytes(&bs58::decode(secret_key).into_vec()?)
+    }
+
+    fn to_base58_string(&self) -> Base58SecretKey {
+        bs58::encode(self.to_bytes()).into_string()
+    }
+}
+
+/// The keypair used by the Solana CLI, `~/.config/solana/id.json`
+pub fn default_keypair_path() -> PoseidonResult<PathBuf> {
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .ok_or(PoseidonError::Io(std::io::ErrorKind::NotFound))?;
+
+    Ok(PathBuf::from(home)
+        .join(".config")
+        .join("solana")
+        .join("id.json"))
+}
+
+// `ed25519_dalek` does not check that the public key belongs to the secret key
+fn keypair_from_bytes(bytes: &[u8]) -> PoseidonResult<Keypair> {
+    if bytes.len() != KEYPAIR_LENGTH {
+        return Err(PoseidonError::InvalidKeypair);
+    }
+
+    let secret = SecretKey::from_bytes(&bytes[..SECRET_KEY_LENGTH])
+        .map_err(|_| PoseidonError::InvalidKeypair)?;
+    let public: PublicKey = (&secret).into();
+
+    if public.as_bytes()[..] != bytes[SECRET_KEY_LENGTH..] {
+        return Err(PoseidonError::InvalidKeypair);
+    }
+
+    Ok(Keypair { secret, public })
+}
+
+#[cfg(unix)]
+fn create_owner_only(path: &Path) -> PoseidonResult<File> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // The mode only applies to new files
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+fn create_owner_only(path: &Path) -> PoseidonResult<File> {
+    Ok(File::create(path)?)
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::test_utils::keypair;
+
+    #[test]
+    fn keypair_formats() {
+        let keypair = keypair(3);
+
+        let json = keypair.to_json();
+        assert!(json.starts_with("[3,3,3,"));
+        assert_eq!(
+            Keypair::from_json(&json).unwrap().to_bytes(),
+            keypair.to_bytes()
+        );
+
+        let base58 = keypair.to_base58_string();
+        assert_eq!(
+            bs58::decode(&base58).into_vec().unwrap(),
+            keypair.to_bytes()
+        );
+        assert_eq!(
+            Keypair::from_base58_string(&base58).unwrap().to_bytes(),
+            keypair.to_bytes()
+        );
+
+        let mut mismatched = keypair.to_bytes();
+        mismatched[63] ^= 1;
+        assert!(matches!(
+            Keypair::from_base58_string(&bs58::encode(mismatched).into_string()),
+            Err(PoseidonError::InvalidKeypair)
+        ));
+        assert!(matches!(
+            Keypair::from_json("[1,2,3]"),
+            Err(PoseidonError::InvalidKeypair)
+        ));
+    }
+
+    #[test]
+    fn keypair_file() {
+        let keypair = keypair(3);
+        let path = std::env::temp_dir()
+            .join(format!("poseidon-keypair-{}", std::process::id()))
+            .join("id.json");
+
+        keypair.write_to_file(&path).unwrap();
+        assert_eq!(
+            Keypair::read_from_file(&path).unwrap().to_bytes(),
+            keypair.to_bytes()
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -11,8 +11,25 @@ mod errors;
 pub use errors::*;
 mod utilities;
 pub use utilities::*;
+mod keypair;
+pub use keypair::*;
+mod hd_wallet;
+pub use hd_wallet::*;
+mod vanity;
+pub use vanity::*;
 mod rpc_client;
 pub use rpc_client::*;
+mod anchor;
+pub use anchor::*;
+#[cfg(feature = "derive")]
+pub use poseidon_derive::{InstructionBuilder, ProgramErrorDecoder};
+
+// The code generated by `poseidon-derive` serializes instructions with it
+#[doc(hidden)]
+pub use borsh;
+
+#[cfg(test)]
+mod test_utils;
 
 #[cfg(test)]
 mod sanity_checks {
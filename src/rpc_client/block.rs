@@ -0,0 +1,418 @@
+use crate::{
+    request, request_with_result, Base58BlockHash, Base58PublicKey, Base58TxSignature, Commitment,
+    EncodedTransaction, PoseidonResult, Reward, RpcMeta, RpcResponse, RpcResponseWithResult,
+    Transaction, TransactionEncoding, TransactionVersion, UnixTimestamp,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The level of transaction detail returned by `GetBlock`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransactionDetails {
+    /// The transactions and their status meta
+    #[default]
+    Full,
+    /// Only the signatures of the transactions
+    Signatures,
+    /// The signatures and account keys of the transactions and a subset of their status meta
+    Accounts,
+    /// No transaction details
+    None,
+}
+
+impl From<TransactionDetails> for &str {
+    fn from(transaction_details: TransactionDetails) -> Self {
+        match transaction_details {
+            TransactionDetails::Full => "full",
+            TransactionDetails::Signatures => "signatures",
+            TransactionDetails::Accounts => "accounts",
+            TransactionDetails::None => "none",
+        }
+    }
+}
+
+/// ### Fetch a confirmed block
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example() -> PoseidonResult<()> {
+/// let block = GetBlock::new(200_000_000)
+///     .add_transaction_details(TransactionDetails::Signatures)
+///     .add_rewards(false)
+///     .add_max_supported_transaction_version(0)
+///     .process()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetBlock {
+    pub(crate) slot: u64,
+    pub(crate) commitment: Commitment,
+    encoding: TransactionEncoding,
+    transaction_details: TransactionDetails,
+    rewards: bool,
+    max_supported_transaction_version: Option<u8>,
+}
+
+impl GetBlock {
+    pub fn new(slot: u64) -> Self {
+        GetBlock {
+            slot,
+            commitment: Commitment::Finalized,
+            encoding: TransactionEncoding::default(),
+            transaction_details: TransactionDetails::default(),
+            rewards: true,
+            max_supported_transaction_version: Option::default(),
+        }
+    }
+
+    /// `Commitment::Processed` is not supported for blocks
+    pub fn change_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.commitment = commitment;
+
+        self
+    }
+
+    /// Defaults to `TransactionEncoding::Base64` so the transactions can be decoded
+    pub fn add_encoding(&mut self, encoding: TransactionEncoding) -> &mut Self {
+        self.encoding = encoding;
+
+        self
+    }
+
+    pub fn add_transaction_details(
+        &mut self,
+        transaction_details: TransactionDetails,
+    ) -> &mut Self {
+        self.transaction_details = transaction_details;
+
+        self
+    }
+
+    /// Whether to include the rewards of the block, defaults to `true`
+    pub fn add_rewards(&mut self, rewards: bool) -> &mut Self {
+        self.rewards = rewards;
+
+        self
+    }
+
+    /// Return versioned transactions up to this version. Without it the request
+    /// fails if the block contains a versioned transaction.
+    pub fn add_max_supported_transaction_version(&mut self, version: u8) -> &mut Self {
+        self.max_supported_transaction_version = Some(version);
+
+        self
+    }
+
+    /// The block is `None` if it has not been confirmed yet
+    pub async fn process(&self) -> PoseidonResult<RpcResponse<Option<Block>>> {
+        let commitment: &str = self.commitment.into();
+        let encoding: &str = self.encoding.into();
+        let transaction_details: &str = self.transaction_details.into();
+
+        let mut config = json::object! {
+            commitment: commitment,
+            encoding: encoding,
+            transactionDetails: transaction_details,
+            rewards: self.rewards,
+        };
+        if let Some(version) = self.max_supported_transaction_version {
+            config["maxSupportedTransactionVersion"] = version.into();
+        }
+
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getBlock",
+            params: json::array![self.slot, config]
+        };
+
+        request::<Option<Block>>(body).await
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Block {
+    pub blockhash: Base58BlockHash,
+    pub previous_blockhash: Base58BlockHash,
+    pub parent_slot: u64,
+    /// Estimated production time, `None` if not available
+    pub block_time: Option<UnixTimestamp>,
+    /// The number of blocks beneath this block
+    pub block_height: Option<u64>,
+    /// Present with `TransactionDetails::Full` and `TransactionDetails::Accounts`
+    pub transactions: Option<Vec<BlockTransaction>>,
+    /// Present with `TransactionDetails::Signatures`
+    pub signatures: Option<Vec<Base58TxSignature>>,
+    /// Present when rewards are requested
+    pub rewards: Option<Vec<Reward>>,
+}
+
+impl Block {
+    /// Decode the binary encoded transactions of the block, in order. Each transaction
+    /// is decoded on its own so a versioned transaction, which can not be decoded yet,
+    /// only fails its own entry with `PoseidonError::UnsupportedTransactionVersion`.
+    pub fn decode_transactions(&self) -> Vec<PoseidonResult<Transaction>> {
+        self.transactions
+            .iter()
+            .flatten()
+            .map(|transaction| transaction.decode())
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransaction {
+    pub transaction: EncodedTransaction,
+    pub meta: Option<RpcMeta>,
+    /// Only present when `maxSupportedTransactionVersion` is set
+    pub version: Option<TransactionVersion>,
+}
+
+impl BlockTransaction {
+    pub fn decode(&self) -> PoseidonResult<Transaction> {
+        self.transaction.decode_versioned(self.version)
+    }
+}
+
+pub struct GetBlocks;
+
+impl GetBlocks {
+    /// The confirmed blocks between `start_slot` and `end_slot` inclusive,
+    /// up to the latest confirmed block if `end_slot` is `None`
+    pub async fn process(
+        start_slot: u64,
+        end_slot: Option<u64>,
+        commitment: Commitment,
+    ) -> PoseidonResult<RpcResponse<Vec<u64>>> {
+        let commitment: &str = commitment.into();
+        let config = json::object! { commitment: commitment };
+        let params = match end_slot {
+            Some(end_slot) => json::array![start_slot, end_slot, config],
+            None => json::array![start_slot, config],
+        };
+
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getBlocks",
+            params: params
+        };
+
+        request::<Vec<u64>>(body).await
+    }
+}
+
+pub struct GetBlocksWithLimit;
+
+impl GetBlocksWithLimit {
+    /// Up to `limit` confirmed blocks starting at `start_slot`
+    pub async fn process(
+        start_slot: u64,
+        limit: u64,
+        commitment: Commitment,
+    ) -> PoseidonResult<RpcResponse<Vec<u64>>> {
+        let commitment: &str = commitment.into();
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getBlocksWithLimit",
+            params: json::array![start_slot, limit, json::object!{ commitment: commitment }]
+        };
+
+        request::<Vec<u64>>(body).await
+    }
+}
+
+pub struct GetBlockTime;
+
+impl GetBlockTime {
+    /// The estimated production time of the block at `slot`, `None` if not available
+    pub async fn process(slot: u64) -> PoseidonResult<RpcResponse<Option<UnixTimestamp>>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getBlockTime",
+            params: json::array![slot]
+        };
+
+        request::<Option<UnixTimestamp>>(body).await
+    }
+}
+
+/// ### Fetch the block production of validators in a range of slots
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example() -> PoseidonResult<()> {
+/// let production = GetBlockProduction::new()
+///     .add_identity("dv1ZAGvdsz5hHLwWXsVnM94hWf1pjbKVau1QVkaMJ92")
+///     .add_range(200_000_000, Some(200_000_432))
+///     .process()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GetBlockProduction {
+    commitment: Commitment,
+    identity: Option<Base58PublicKey>,
+    range: Option<(u64, Option<u64>)>,
+}
+
+impl GetBlockProduction {
+    pub fn new() -> Self {
+        GetBlockProduction {
+            commitment: Commitment::Finalized,
+            identity: Option::default(),
+            range: Option::default(),
+        }
+    }
+
+    pub fn change_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.commitment = commitment;
+
+        self
+    }
+
+    /// Only return results for this validator identity
+    pub fn add_identity(&mut self, identity: &str) -> &mut Self {
+        self.identity = Some(identity.to_owned());
+
+        self
+    }
+
+    /// The slots to return block production for, defaulting to the current epoch.
+    /// `last_slot` defaults to the highest slot.
+    pub fn add_range(&mut self, first_slot: u64, last_slot: Option<u64>) -> &mut Self {
+        self.range = Some((first_slot, last_slot));
+
+        self
+    }
+
+    pub async fn process(&self) -> PoseidonResult<RpcResponseWithResult<RpcBlockProduction>> {
+        let commitment: &str = self.commitment.into();
+
+        let mut config = json::object! {
+            commitment: commitment,
+        };
+        if let Some(identity) = &self.identity {
+            config["identity"] = identity.as_str().into();
+        }
+        if let Some((first_slot, last_slot)) = self.range {
+            let mut range = json::object! { firstSlot: first_slot };
+            if let Some(last_slot) = last_slot {
+                range["lastSlot"] = last_slot.into();
+            }
+            config["range"] = range;
+        }
+
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getBlockProduction",
+            params: json::array![config]
+        };
+
+        request_with_result::<RpcBlockProduction>(body).await
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockProduction {
+    /// The number of leader slots and the number of blocks produced by each validator identity
+    pub by_identity: HashMap<Base58PublicKey, (usize, usize)>,
+    pub range: RpcBlockProductionRange,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockProductionRange {
+    pub first_slot: u64,
+    pub last_slot: u64,
+}
+
+pub struct GetBlockCommitment;
+
+impl GetBlockCommitment {
+    pub async fn process(slot: u64) -> PoseidonResult<RpcResponse<RpcBlockCommitment>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getBlockCommitment",
+            params: json::array![slot]
+        };
+
+        request::<RpcBlockCommitment>(body).await
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockCommitment {
+    /// The amount of cluster stake in lamports that has voted on the block
+    /// at each depth from 0 to `MAX_LOCKOUT_HISTORY`, `None` for unknown blocks
+    pub commitment: Option<Vec<u64>>,
+    /// The total active stake in lamports of the current epoch
+    pub total_stake: u64,
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::{parse_response, Legacy, Message, PoseidonError};
+
+    #[test]
+    fn decode_block_responses() {
+        let mut message = Message::new();
+        message.header.num_required_signatures = 1;
+        message.account_keys = vec![[3u8; 32]];
+        message.add_recent_blockhash([4u8; 32]);
+        let mut transaction = Transaction::new(message);
+        transaction.add_signature([5u8; 64]);
+        let encoded = base64::encode(transaction.to_bytes().unwrap());
+
+        let response = format!(
+            r#"{{"jsonrpc":"2.0","result":{{"blockHeight":428,"blockTime":null,"blockhash":"3Eq21vXNB5s86c62bVuUfTeaMif1N2kUqRPBmGRJhyTA","parentSlot":429,"previousBlockhash":"mfcyqEXB3DnHXki6KjjmZck6YjmZLvpAByy2fj4nh6B","transactions":[{{"meta":{{"err":null,"fee":5000,"postBalances":[499998932500,26858640,1,1,1],"postTokenBalances":[],"preBalances":[499998937500,26858640,1,1,1],"preTokenBalances":[],"status":{{"Ok":null}}}},"transaction":["{encoded}","base64"],"version":"legacy"}},{{"meta":null,"transaction":["{encoded}","base64"],"version":0}}]}},"id":1}}"#
+        );
+
+        let block = parse_response::<RpcResponse<Option<Block>>>(&response)
+            .unwrap()
+            .result
+            .unwrap();
+        assert_eq!(block.parent_slot, 429);
+        assert!(block.block_time.is_none());
+        assert!(block.rewards.is_none());
+
+        let transactions = block.transactions.as_ref().unwrap();
+        assert_eq!(transactions[0].meta.as_ref().unwrap().fee, 5000);
+        assert_eq!(
+            transactions[0].version,
+            Some(TransactionVersion::Legacy(Legacy::Legacy))
+        );
+        let decoded = block.decode_transactions();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].as_ref().unwrap(), &transaction);
+        assert!(matches!(
+            decoded[1],
+            Err(PoseidonError::UnsupportedTransactionVersion(0))
+        ));
+
+        let signatures = parse_response::<RpcResponse<Option<Block>>>(
+            r#"{"jsonrpc":"2.0","result":{"blockHeight":428,"blockTime":1691000000,"blockhash":"3Eq21vXNB5s86c62bVuUfTeaMif1N2kUqRPBmGRJhyTA","parentSlot":429,"previousBlockhash":"mfcyqEXB3DnHXki6KjjmZck6YjmZLvpAByy2fj4nh6B","signatures":["2EBVM6cB8vAAD93Ktr6Vd8p67XPbQzCJX47MpReuiCXJAtcjaxpvWpcg9Ege1Nr5Tk3a2GFrByT7WPBjdsTycY9b"]},"id":1}"#,
+        )
+        .unwrap()
+        .result
+        .unwrap();
+        assert_eq!(signatures.signatures.as_ref().unwrap().len(), 1);
+        assert!(signatures.decode_transactions().is_empty());
+
+        let block_commitment = parse_response::<RpcResponse<RpcBlockCommitment>>(
+            r#"{"jsonrpc":"2.0","result":{"commitment":null,"totalStake":42},"id":1}"#,
+        )
+        .unwrap();
+        assert!(block_commitment.result.commitment.is_none());
+    }
+}
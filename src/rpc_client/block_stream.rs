@@ -0,0 +1,368 @@
+use crate::{Block, GetBlock, GetSlot, PoseidonError, PoseidonResult, RpcContextConfig};
+use std::{collections::VecDeque, time::Duration};
+
+/// The JSON-RPC error code for a block that is not available yet
+pub const BLOCK_NOT_AVAILABLE_ERROR_CODE: i16 = -32004;
+/// The JSON-RPC error code for a slot that was skipped by its leader
+pub const SLOT_SKIPPED_ERROR_CODE: i16 = -32007;
+/// The JSON-RPC error code for a skipped slot served from long-term storage
+pub const LONG_TERM_STORAGE_SLOT_SKIPPED_ERROR_CODE: i16 = -32009;
+
+/// The RPC methods needed to stream blocks. `GetBlock` implements it, fetching every
+/// block with its configuration, and tests can provide a mock.
+pub trait BlockSource {
+    /// The highest slot that blocks can be requested for
+    fn latest_slot(&self) -> smol::Task<PoseidonResult<u64>>;
+
+    /// The block at `slot`, `None` if it is not available yet
+    fn block(&self, slot: u64) -> smol::Task<PoseidonResult<Option<Block>>>;
+}
+
+impl BlockSource for GetBlock {
+    fn latest_slot(&self) -> smol::Task<PoseidonResult<u64>> {
+        let mut config = RpcContextConfig::new();
+        config.change_commitment(self.commitment);
+
+        smol::spawn(async move { Ok(GetSlot::process(&config).await?.result) })
+    }
+
+    fn block(&self, slot: u64) -> smol::Task<PoseidonResult<Option<Block>>> {
+        let mut request = self.clone();
+        request.slot = slot;
+
+        smol::spawn(async move { Ok(request.process().await?.result) })
+    }
+}
+
+/// The slot, the number of retries so far and the pending `getBlock` call
+type InFlightBlock = (u64, usize, smol::Task<PoseidonResult<Option<Block>>>);
+
+/// The outcome of fetching the block of a slot
+enum SlotOutcome {
+    Block(Block),
+    Skipped,
+    NotAvailable,
+}
+
+/// ### Stream confirmed blocks in slot order
+/// Up to `concurrency` blocks are fetched at the same time. Skipped slots are
+/// passed over and blocks that are not available yet are retried with an
+/// exponential backoff. Once the stream catches up with the cluster it polls
+/// for new slots every `poll_interval`.
+///
+/// The `checkpoint()` is the last slot processed so an indexer can resume
+/// from the slot after it. When `next()` returns an error the slot that failed
+/// stays at the front of the stream and calling `next()` again retries it.
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example(checkpoint: u64) -> PoseidonResult<()> {
+/// # fn index(slot: u64, block: Block) {}
+/// # fn save_checkpoint(checkpoint: Option<u64>) {}
+/// let mut get_block = GetBlock::new(0);
+/// get_block
+///     .change_commitment(Commitment::Confirmed)
+///     .add_max_supported_transaction_version(0);
+///
+/// let mut stream = BlockStream::new(get_block, checkpoint + 1);
+/// stream.add_concurrency(16);
+///
+/// while let Some((slot, block)) = stream.next().await? {
+///     index(slot, block);
+///     save_checkpoint(stream.checkpoint());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct BlockStream<S: BlockSource> {
+    source: S,
+    next_slot: u64,
+    end_slot: Option<u64>,
+    latest_slot: u64,
+    checkpoint: Option<u64>,
+    concurrency: usize,
+    initial_backoff: Duration,
+    max_retries: usize,
+    poll_interval: Duration,
+    in_flight: VecDeque<InFlightBlock>,
+}
+
+impl<S: BlockSource> BlockStream<S> {
+    pub fn new(source: S, start_slot: u64) -> Self {
+        BlockStream {
+            source,
+            next_slot: start_slot,
+            end_slot: Option::default(),
+            latest_slot: u64::default(),
+            checkpoint: start_slot.checked_sub(1),
+            concurrency: 8,
+            initial_backoff: Duration::from_millis(500),
+            max_retries: 10,
+            poll_interval: Duration::from_millis(400),
+            in_flight: VecDeque::default(),
+        }
+    }
+
+    /// Stop the stream after the block of `end_slot`
+    pub fn add_end_slot(&mut self, end_slot: u64) -> &mut Self {
+        self.end_slot = Some(end_slot);
+
+        self
+    }
+
+    /// The number of `getBlock` calls in flight at the same time
+    pub fn add_concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+
+        self
+    }
+
+    /// Retry a block that is not available yet after `initial_backoff`, doubling the
+    /// wait on each attempt and giving up after `max_retries` retries
+    pub fn add_backoff(&mut self, initial_backoff: Duration, max_retries: usize) -> &mut Self {
+        self.initial_backoff = initial_backoff;
+        self.max_retries = max_retries;
+
+        self
+    }
+
+    /// How long to wait before checking for new slots once the stream has caught up
+    pub fn add_poll_interval(&mut self, poll_interval: Duration) -> &mut Self {
+        self.poll_interval = poll_interval;
+
+        self
+    }
+
+    /// The last slot processed, either returned by `next()` or skipped
+    pub fn checkpoint(&self) -> Option<u64> {
+        self.checkpoint
+    }
+
+    /// The next block in slot order, `None` once the end slot has been processed
+    pub async fn next(&mut self) -> PoseidonResult<Option<(u64, Block)>> {
+        loop {
+            self.fill().await?;
+
+            let (slot, attempt, task) = match self.in_flight.pop_front() {
+                Some(in_flight) => in_flight,
+                None => return Ok(None),
+            };
+
+            let outcome = match BlockStream::<S>::outcome(task.await) {
+                Ok(outcome) => outcome,
+                Err(error) => {
+                    self.retry(slot, attempt);
+
+                    return Err(error);
+                }
+            };
+
+            match outcome {
+                SlotOutcome::Block(block) => {
+                    self.checkpoint = Some(slot);
+
+                    return Ok(Some((slot, block)));
+                }
+                SlotOutcome::Skipped => self.checkpoint = Some(slot),
+                SlotOutcome::NotAvailable => {
+                    if attempt >= self.max_retries {
+                        self.retry(slot, attempt);
+
+                        return Err(PoseidonError::BlockNotAvailable(slot));
+                    }
+
+                    let backoff = self
+                        .initial_backoff
+                        .saturating_mul(2u32.saturating_pow(attempt as u32));
+                    smol::Timer::after(backoff).await;
+
+                    self.retry(slot, attempt + 1);
+                }
+            }
+        }
+    }
+
+    // Request the block of `slot` again ahead of the blocks in flight
+    fn retry(&mut self, slot: u64, attempt: usize) {
+        self.in_flight
+            .push_front((slot, attempt, self.source.block(slot)));
+    }
+
+    // Request blocks up to the concurrency limit, waiting for new
+    // slots when the stream has caught up with the cluster
+    async fn fill(&mut self) -> PoseidonResult<()> {
+        loop {
+            while self.in_flight.len() < self.concurrency
+                && self.next_slot <= self.latest_slot
+                && !self.is_past_end(self.next_slot)
+            {
+                self.in_flight
+                    .push_back((self.next_slot, 0, self.source.block(self.next_slot)));
+                self.next_slot += 1;
+            }
+
+            if !self.in_flight.is_empty() || self.is_past_end(self.next_slot) {
+                return Ok(());
+            }
+
+            let latest_slot = self.source.latest_slot().await?;
+            if latest_slot < self.next_slot {
+                smol::Timer::after(self.poll_interval).await;
+            }
+            self.latest_slot = latest_slot;
+        }
+    }
+
+    fn is_past_end(&self, slot: u64) -> bool {
+        matches!(self.end_slot, Some(end_slot) if slot > end_slot)
+    }
+
+    fn outcome(result: PoseidonResult<Option<Block>>) -> PoseidonResult<SlotOutcome> {
+        match result {
+            Ok(Some(block)) => Ok(SlotOutcome::Block(block)),
+            Ok(None) => Ok(SlotOutcome::NotAvailable),
+            Err(PoseidonError::JsonRpc(error)) => match error.code {
+                SLOT_SKIPPED_ERROR_CODE | LONG_TERM_STORAGE_SLOT_SKIPPED_ERROR_CODE => {
+                    Ok(SlotOutcome::Skipped)
+                }
+                BLOCK_NOT_AVAILABLE_ERROR_CODE => Ok(SlotOutcome::NotAvailable),
+                _ => Err(PoseidonError::JsonRpc(error)),
+            },
+            Err(error) => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::JsonRpcError;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    #[derive(Default)]
+    struct MockSource {
+        attempts: Arc<Mutex<HashMap<u64, usize>>>,
+        // The first request for this slot fails with an internal error
+        fail_once: Option<u64>,
+    }
+
+    impl BlockSource for MockSource {
+        fn latest_slot(&self) -> smol::Task<PoseidonResult<u64>> {
+            smol::spawn(async { Ok(14) })
+        }
+
+        fn block(&self, slot: u64) -> smol::Task<PoseidonResult<Option<Block>>> {
+            let attempt = {
+                let mut attempts = self.attempts.lock().unwrap();
+                let attempt = attempts.entry(slot).or_default();
+                *attempt += 1;
+                *attempt
+            };
+
+            let fail = self.fail_once == Some(slot) && attempt == 1;
+
+            smol::spawn(async move {
+                match slot {
+                    _ if fail => Err(PoseidonError::JsonRpc(JsonRpcError {
+                        code: -32603,
+                        message: "Internal error".to_owned(),
+                    })),
+                    12 => Err(PoseidonError::JsonRpc(JsonRpcError {
+                        code: SLOT_SKIPPED_ERROR_CODE,
+                        message: "Slot 12 was skipped".to_owned(),
+                    })),
+                    13 if attempt < 3 => Err(PoseidonError::JsonRpc(JsonRpcError {
+                        code: BLOCK_NOT_AVAILABLE_ERROR_CODE,
+                        message: "Block not available for slot 13".to_owned(),
+                    })),
+                    _ => Ok(Some(Block {
+                        blockhash: slot.to_string(),
+                        previous_blockhash: (slot - 1).to_string(),
+                        parent_slot: slot - 1,
+                        block_time: None,
+                        block_height: Some(slot),
+                        transactions: None,
+                        signatures: None,
+                        rewards: None,
+                    })),
+                }
+            })
+        }
+    }
+
+    #[test]
+    fn stream_blocks_in_order() {
+        let source = MockSource::default();
+        let attempts = source.attempts.clone();
+
+        let mut stream = BlockStream::new(source, 10);
+        stream
+            .add_end_slot(14)
+            .add_concurrency(3)
+            .add_backoff(Duration::from_millis(1), 5);
+        assert_eq!(stream.checkpoint(), Some(9));
+
+        let slots = smol::block_on(async {
+            let mut slots = Vec::new();
+            while let Some((slot, block)) = stream.next().await.unwrap() {
+                assert_eq!(block.block_height, Some(slot));
+                slots.push(slot);
+            }
+            slots
+        });
+
+        assert_eq!(slots, vec![10, 11, 13, 14]);
+        assert_eq!(stream.checkpoint(), Some(14));
+        assert_eq!(attempts.lock().unwrap()[&13], 3);
+        assert_eq!(attempts.lock().unwrap()[&12], 1);
+    }
+
+    #[test]
+    fn errors_keep_the_slot() {
+        let source = MockSource {
+            fail_once: Some(11),
+            ..MockSource::default()
+        };
+        let attempts = source.attempts.clone();
+
+        let mut stream = BlockStream::new(source, 10);
+        stream.add_end_slot(11).add_concurrency(2);
+
+        smol::block_on(async {
+            assert_eq!(stream.next().await.unwrap().unwrap().0, 10);
+            assert!(matches!(
+                stream.next().await,
+                Err(PoseidonError::JsonRpc(error)) if error.code == -32603
+            ));
+            assert_eq!(stream.checkpoint(), Some(10));
+
+            assert_eq!(stream.next().await.unwrap().unwrap().0, 11);
+            assert!(stream.next().await.unwrap().is_none());
+        });
+
+        assert_eq!(stream.checkpoint(), Some(11));
+        assert_eq!(attempts.lock().unwrap()[&11], 2);
+    }
+
+    #[test]
+    fn unavailable_blocks_keep_the_slot() {
+        let mut stream = BlockStream::new(MockSource::default(), 13);
+        stream
+            .add_end_slot(13)
+            .add_backoff(Duration::from_millis(1), 0);
+
+        smol::block_on(async {
+            assert!(matches!(
+                stream.next().await,
+                Err(PoseidonError::BlockNotAvailable(13))
+            ));
+            assert!(matches!(
+                stream.next().await,
+                Err(PoseidonError::BlockNotAvailable(13))
+            ));
+            assert_eq!(stream.next().await.unwrap().unwrap().0, 13);
+        });
+    }
+}
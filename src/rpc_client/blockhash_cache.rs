@@ -0,0 +1,386 @@
+use crate::{
+    Commitment, GetLatestBlockhash, IsBlockhashValid, PoseidonError, PoseidonResult,
+    RecentBlockHash, RpcClient,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A blockhash held by the `BlockhashCache`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedBlockhash {
+    pub blockhash: RecentBlockHash,
+    /// The last block height at which a transaction using the blockhash is accepted
+    pub last_valid_block_height: u64,
+    /// When the blockhash was fetched from the RPC
+    pub fetched_at: Instant,
+}
+
+/// ### Cache the latest blockhash of each `Commitment`
+/// Blockhashes are fetched the first time a `Commitment` is requested and then
+/// refreshed in the background every `refresh_interval`. Entries older than
+/// `max_age` are fetched again instead of being handed out, and `evict_expired`,
+/// which the background task also runs, removes the entries that the cluster
+/// reports as expired through `isBlockhashValid`.
+///
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example(message: &mut Message) -> PoseidonResult<()> {
+/// let cache = BlockhashCache::new();
+/// // Refreshing stops when the task is dropped
+/// let refresh_task = cache.start();
+///
+/// let blockhash = cache.get(Commitment::Confirmed).await?.blockhash;
+/// message.add_recent_blockhash(blockhash);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlockhashCache {
+    entries: Arc<Mutex<BTreeMap<Commitment, CachedBlockhash>>>,
+    rpc: RpcClient,
+    refresh_interval: Duration,
+    max_age: Duration,
+}
+
+impl Default for BlockhashCache {
+    fn default() -> Self {
+        BlockhashCache::new()
+    }
+}
+
+impl BlockhashCache {
+    pub fn new() -> Self {
+        BlockhashCache {
+            entries: Arc::default(),
+            rpc: RpcClient::new(),
+            refresh_interval: Duration::from_secs(10),
+            max_age: Duration::from_secs(30),
+        }
+    }
+
+    /// The client used to reach the cluster. Defaults to `RpcClient::new()`.
+    pub fn add_rpc_client(&mut self, rpc: RpcClient) -> &mut Self {
+        self.rpc = rpc;
+
+        self
+    }
+
+    /// How often the background task fetches new blockhashes. Defaults to 10 seconds.
+    pub fn add_refresh_interval(&mut self, refresh_interval: Duration) -> &mut Self {
+        self.refresh_interval = refresh_interval;
+
+        self
+    }
+
+    /// How long a blockhash is handed out after it was fetched. Defaults to 30 seconds,
+    /// leaving time for a transaction to land before the blockhash expires after 150 blocks.
+    pub fn add_max_age(&mut self, max_age: Duration) -> &mut Self {
+        self.max_age = max_age;
+
+        self
+    }
+
+    /// Spawn the task that refreshes the cache on a timer.
+    /// Refreshing stops when the returned task is dropped.
+    pub fn start(&self) -> smol::Task<()> {
+        let cache = self.clone();
+
+        smol::spawn(async move {
+            loop {
+                // A commitment that failed to refresh keeps its entry until a later
+                // refresh replaces it or the cluster reports it as expired
+                cache.refresh().await.ok();
+                cache.evict_expired().await.ok();
+
+                smol::Timer::after(cache.refresh_interval).await;
+            }
+        })
+    }
+
+    /// The freshest blockhash for `commitment`, fetching it if it is not cached or older than `max_age`
+    pub async fn get(&self, commitment: Commitment) -> PoseidonResult<CachedBlockhash> {
+        match self.cached(commitment) {
+            Some(cached) => Ok(cached),
+            None => self.fetch(commitment).await,
+        }
+    }
+
+    /// The blockhash cached for `commitment` without contacting the RPC,
+    /// `None` if it is older than `max_age`
+    pub fn cached(&self, commitment: Commitment) -> Option<CachedBlockhash> {
+        self.lock()
+            .get(&commitment)
+            .copied()
+            .filter(|cached| cached.fetched_at.elapsed() < self.max_age)
+    }
+
+    /// Fetch a new blockhash for every cached `Commitment`. Each commitment is refreshed
+    /// even if another fails, the failures are returned together in
+    /// `PoseidonError::BlockhashCacheFailed`.
+    pub async fn refresh(&self) -> PoseidonResult<()> {
+        let commitments = self.lock().keys().copied().collect::<Vec<Commitment>>();
+
+        let mut errors = Vec::new();
+        for commitment in commitments {
+            if let Err(error) = self.fetch(commitment).await {
+                errors.push((commitment, error));
+            }
+        }
+
+        BlockhashCache::outcome(errors)
+    }
+
+    /// Remove the blockhashes that `isBlockhashValid` reports as expired. Each entry
+    /// is checked even if another check fails, the failures are returned together in
+    /// `PoseidonError::BlockhashCacheFailed`.
+    pub async fn evict_expired(&self) -> PoseidonResult<()> {
+        let entries = self
+            .lock()
+            .iter()
+            .map(|(commitment, cached)| (*commitment, *cached))
+            .collect::<Vec<(Commitment, CachedBlockhash)>>();
+
+        let mut errors = Vec::new();
+        for (commitment, cached) in entries {
+            let blockhash = bs58::encode(&cached.blockhash).into_string();
+            let is_valid = self
+                .rpc
+                .request_with_result::<bool>(IsBlockhashValid::body(&blockhash, commitment))
+                .await;
+
+            match is_valid {
+                Ok(response) if !response.result.value => self.evict(commitment, &cached.blockhash),
+                Ok(_) => (),
+                Err(error) => errors.push((commitment, error)),
+            }
+        }
+
+        BlockhashCache::outcome(errors)
+    }
+
+    async fn fetch(&self, commitment: Commitment) -> PoseidonResult<CachedBlockhash> {
+        let latest = self
+            .rpc
+            .request_with_result::<GetLatestBlockhash>(GetLatestBlockhash::body(commitment))
+            .await?
+            .result
+            .value;
+
+        let cached = CachedBlockhash {
+            blockhash: latest.to_bytes()?,
+            last_valid_block_height: latest.last_valid_block_height,
+            fetched_at: Instant::now(),
+        };
+
+        Ok(self.insert(commitment, cached))
+    }
+
+    fn outcome(errors: Vec<(Commitment, PoseidonError)>) -> PoseidonResult<()> {
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(PoseidonError::BlockhashCacheFailed(errors))
+        }
+    }
+
+    // Keeps the existing entry if a concurrent fetch already stored a fresher blockhash
+    fn insert(&self, commitment: Commitment, cached: CachedBlockhash) -> CachedBlockhash {
+        let mut entries = self.lock();
+
+        match entries.get(&commitment) {
+            Some(current) if current.last_valid_block_height > cached.last_valid_block_height => {
+                *current
+            }
+            _ => {
+                entries.insert(commitment, cached);
+
+                cached
+            }
+        }
+    }
+
+    // Only evicts the entry if it was not replaced while its validity was being checked
+    fn evict(&self, commitment: Commitment, blockhash: &RecentBlockHash) {
+        let mut entries = self.lock();
+
+        if let Some(current) = entries.get(&commitment) {
+            if &current.blockhash == blockhash {
+                entries.remove(&commitment);
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<Commitment, CachedBlockhash>> {
+        // The map is always left in a consistent state so a poisoned lock is still usable
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::test_utils::mock_rpc_server;
+    use serde_json::json;
+
+    fn cached(byte: u8, last_valid_block_height: u64) -> CachedBlockhash {
+        CachedBlockhash {
+            blockhash: [byte; 32],
+            last_valid_block_height,
+            fetched_at: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn keeps_freshest_blockhash_per_commitment() {
+        let cache = BlockhashCache::new();
+
+        cache.insert(Commitment::Finalized, cached(1, 100));
+        cache.insert(Commitment::Confirmed, cached(2, 150));
+        assert_eq!(
+            cache.cached(Commitment::Finalized).unwrap().blockhash,
+            [1; 32]
+        );
+        assert_eq!(
+            cache.cached(Commitment::Confirmed).unwrap().blockhash,
+            [2; 32]
+        );
+        assert!(cache.cached(Commitment::Processed).is_none());
+
+        // A stale response arriving late does not replace a fresher blockhash
+        assert_eq!(
+            cache
+                .insert(Commitment::Confirmed, cached(3, 140))
+                .blockhash,
+            [2; 32]
+        );
+        cache.insert(Commitment::Confirmed, cached(4, 160));
+        assert_eq!(
+            cache.cached(Commitment::Confirmed).unwrap().blockhash,
+            [4; 32]
+        );
+
+        // Evicting a blockhash that was already replaced keeps the replacement
+        cache.evict(Commitment::Confirmed, &[2; 32]);
+        assert!(cache.cached(Commitment::Confirmed).is_some());
+        cache.evict(Commitment::Confirmed, &[4; 32]);
+        assert!(cache.cached(Commitment::Confirmed).is_none());
+
+        let shared = cache.clone();
+        assert_eq!(
+            shared.cached(Commitment::Finalized).unwrap().blockhash,
+            [1; 32]
+        );
+    }
+
+    #[test]
+    fn stale_blockhash_is_not_handed_out() {
+        let mut cache = BlockhashCache::new();
+        cache.add_max_age(Duration::from_secs(30));
+
+        let mut stale = cached(1, 100);
+        stale.fetched_at = Instant::now().checked_sub(Duration::from_secs(31)).unwrap();
+        cache.insert(Commitment::Finalized, stale);
+        assert!(cache.cached(Commitment::Finalized).is_none());
+
+        // A fresher blockhash replaces the stale entry
+        cache.insert(Commitment::Finalized, cached(2, 250));
+        assert_eq!(
+            cache.cached(Commitment::Finalized).unwrap().blockhash,
+            [2; 32]
+        );
+
+        cache.add_max_age(Duration::ZERO);
+        assert!(cache.cached(Commitment::Finalized).is_none());
+    }
+
+    #[test]
+    fn refresh_and_evict_each_commitment() {
+        // Fetching the finalized blockhash and checking the confirmed one fail
+        let (url, server) = mock_rpc_server(4, |request| {
+            let context = json!({ "apiVersion": "1.16.0", "slot": 1 });
+            let node_is_behind = json!({ "code": -32005, "message": "Node is behind" });
+            let params = &request["params"];
+
+            match request["method"].as_str().unwrap() {
+                "getLatestBlockhash" => match params[0]["commitment"].as_str().unwrap() {
+                    "finalized" => Err(node_is_behind),
+                    _ => Ok(json!({
+                        "context": context,
+                        "value": {
+                            "blockhash": bs58::encode([3u8; 32]).into_string(),
+                            "lastValidBlockHeight": 300,
+                        },
+                    })),
+                },
+                "isBlockhashValid" => match params[1]["commitment"].as_str().unwrap() {
+                    "confirmed" => Err(node_is_behind),
+                    _ => Ok(json!({ "context": context, "value": false })),
+                },
+                method => panic!("Unexpected method {}", method),
+            }
+        });
+        let mut rpc = RpcClient::new();
+        rpc.add_url(&url);
+        let mut cache = BlockhashCache::new();
+        cache.add_rpc_client(rpc);
+
+        cache.insert(Commitment::Confirmed, cached(1, 100));
+        cache.insert(Commitment::Finalized, cached(2, 100));
+
+        smol::block_on(async {
+            let failed_commitments = |outcome: PoseidonResult<()>| match outcome {
+                Err(PoseidonError::BlockhashCacheFailed(errors)) => errors
+                    .into_iter()
+                    .map(|(commitment, _)| commitment)
+                    .collect::<Vec<Commitment>>(),
+                outcome => panic!("Expected the cache to fail, got {:?}", outcome),
+            };
+
+            // The finalized failure does not stop the confirmed blockhash from being refreshed
+            assert_eq!(
+                failed_commitments(cache.refresh().await),
+                vec![Commitment::Finalized]
+            );
+            let confirmed = cache.cached(Commitment::Confirmed).unwrap();
+            assert_eq!(confirmed.blockhash, [3; 32]);
+            assert_eq!(confirmed.last_valid_block_height, 300);
+            assert_eq!(
+                cache.cached(Commitment::Finalized).unwrap().blockhash,
+                [2; 32]
+            );
+
+            // The failed confirmed check keeps its entry and the expired finalized
+            // blockhash is still evicted
+            assert_eq!(
+                failed_commitments(cache.evict_expired().await),
+                vec![Commitment::Confirmed]
+            );
+            assert_eq!(
+                cache.cached(Commitment::Confirmed).unwrap().blockhash,
+                [3; 32]
+            );
+            assert!(cache.cached(Commitment::Finalized).is_none());
+        });
+
+        let methods = server
+            .join()
+            .unwrap()
+            .into_iter()
+            .map(|(_, method)| method)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            methods,
+            vec![
+                "getLatestBlockhash",
+                "getLatestBlockhash",
+                "isBlockhashValid",
+                "isBlockhashValid",
+            ]
+        );
+    }
+}
@@ -0,0 +1,486 @@
+use crate::{
+    parse_response, request, Base58BlockHash, Base58PublicKey, Cluster, Commitment, EpochSchedule,
+    PoseidonError, PoseidonResult, RpcClient, RpcResponse,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The JSON-RPC error code of an unhealthy node
+pub const NODE_UNHEALTHY_ERROR_CODE: i16 = -32005;
+
+/// Configuration shared by the RPC methods that read the state of a bank
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example() -> PoseidonResult<()> {
+/// let slot = GetSlot::process(
+///     RpcContextConfig::new()
+///         .change_commitment(Commitment::Confirmed)
+///         .add_min_context_slot(200_000_000),
+/// )
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RpcContextConfig {
+    commitment: Commitment,
+    min_context_slot: Option<u64>,
+}
+
+impl RpcContextConfig {
+    pub fn new() -> Self {
+        RpcContextConfig {
+            commitment: Commitment::Finalized,
+            min_context_slot: Option::default(),
+        }
+    }
+
+    pub fn change_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.commitment = commitment;
+
+        self
+    }
+
+    /// The minimum slot the request can be evaluated at
+    pub fn add_min_context_slot(&mut self, min_context_slot: u64) -> &mut Self {
+        self.min_context_slot = Some(min_context_slot);
+
+        self
+    }
+
+    pub(crate) fn to_json(self) -> json::JsonValue {
+        let mut config = json::object! {};
+        if self.commitment != Commitment::Unspecified {
+            let commitment: &str = self.commitment.into();
+            config["commitment"] = commitment.into();
+        }
+        if let Some(min_context_slot) = self.min_context_slot {
+            config["minContextSlot"] = min_context_slot.into();
+        }
+
+        config
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcVersionInfo {
+    /// The software version of solana-core
+    #[serde(rename = "solana-core")]
+    pub solana_core: String,
+    /// The unique identifier of the current software's feature set
+    #[serde(rename = "feature-set")]
+    pub feature_set: Option<u32>,
+}
+
+pub struct GetVersion;
+
+impl GetVersion {
+    pub async fn process() -> PoseidonResult<RpcResponse<RpcVersionInfo>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getVersion",
+        };
+
+        request::<RpcVersionInfo>(body).await
+    }
+}
+
+pub struct GetHealth;
+
+impl GetHealth {
+    /// Returns `"ok"` for a healthy node, an unhealthy node responds with a
+    /// `PoseidonError::JsonRpc` error
+    pub async fn process() -> PoseidonResult<RpcResponse<String>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getHealth",
+        };
+
+        request::<String>(body).await
+    }
+
+    pub async fn is_healthy() -> PoseidonResult<bool> {
+        match GetHealth::process().await {
+            Ok(response) => Ok(response.result == "ok"),
+            Err(PoseidonError::JsonRpc(error)) if error.code == NODE_UNHEALTHY_ERROR_CODE => {
+                Ok(false)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcIdentity {
+    /// The identity public key of the node
+    pub identity: Base58PublicKey,
+}
+
+pub struct GetIdentity;
+
+impl GetIdentity {
+    pub async fn process() -> PoseidonResult<RpcResponse<RpcIdentity>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getIdentity",
+        };
+
+        request::<RpcIdentity>(body).await
+    }
+}
+
+pub struct GetGenesisHash;
+
+impl GetGenesisHash {
+    pub async fn process() -> PoseidonResult<RpcResponse<Base58BlockHash>> {
+        request::<Base58BlockHash>(GetGenesisHash::body()).await
+    }
+
+    fn body() -> json::JsonValue {
+        json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getGenesisHash",
+        }
+    }
+}
+
+/// A node participating in the cluster
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcContactInfo {
+    pub pubkey: Base58PublicKey,
+    /// Gossip network address
+    pub gossip: Option<String>,
+    /// TPU network address
+    pub tpu: Option<String>,
+    /// JSON RPC network address, if the JSON RPC service is enabled
+    pub rpc: Option<String>,
+    /// The software version of the node
+    pub version: Option<String>,
+    /// The unique identifier of the node's feature set
+    pub feature_set: Option<u32>,
+    /// The shred version the node has been configured to use
+    pub shred_version: Option<u16>,
+}
+
+pub struct GetClusterNodes;
+
+impl GetClusterNodes {
+    pub async fn process() -> PoseidonResult<RpcResponse<Vec<RpcContactInfo>>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getClusterNodes",
+        };
+
+        request::<Vec<RpcContactInfo>>(body).await
+    }
+}
+
+pub struct GetSlot;
+
+impl GetSlot {
+    pub async fn process(config: &RpcContextConfig) -> PoseidonResult<RpcResponse<u64>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getSlot",
+            params: json::array![config.to_json()]
+        };
+
+        request::<u64>(body).await
+    }
+}
+
+pub struct GetBlockHeight;
+
+impl GetBlockHeight {
+    pub async fn process(config: &RpcContextConfig) -> PoseidonResult<RpcResponse<u64>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getBlockHeight",
+            params: json::array![config.to_json()]
+        };
+
+        request::<u64>(body).await
+    }
+}
+
+pub struct GetSlotLeader;
+
+impl GetSlotLeader {
+    pub async fn process(
+        config: &RpcContextConfig,
+    ) -> PoseidonResult<RpcResponse<Base58PublicKey>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getSlotLeader",
+            params: json::array![config.to_json()]
+        };
+
+        request::<Base58PublicKey>(body).await
+    }
+}
+
+pub struct GetSlotLeaders;
+
+impl GetSlotLeaders {
+    /// The leaders of up to 5,000 slots starting at `start_slot`
+    pub async fn process(
+        start_slot: u64,
+        limit: u64,
+    ) -> PoseidonResult<RpcResponse<Vec<Base58PublicKey>>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getSlotLeaders",
+            params: json::array![start_slot, limit]
+        };
+
+        request::<Vec<Base58PublicKey>>(body).await
+    }
+}
+
+/// The slot indexes relative to the first slot of the epoch in which each validator is leader
+pub type RpcLeaderSchedule = HashMap<Base58PublicKey, Vec<usize>>;
+
+/// ### Fetch the leader schedule of an epoch
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example() -> PoseidonResult<()> {
+/// let leader_schedule = GetLeaderSchedule::new()
+///     .add_slot(200_000_000)
+///     .add_identity("dv1ZAGvdsz5hHLwWXsVnM94hWf1pjbKVau1QVkaMJ92")
+///     .process()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GetLeaderSchedule {
+    slot: Option<u64>,
+    commitment: Commitment,
+    identity: Option<Base58PublicKey>,
+}
+
+impl GetLeaderSchedule {
+    pub fn new() -> Self {
+        GetLeaderSchedule {
+            slot: Option::default(),
+            commitment: Commitment::Finalized,
+            identity: Option::default(),
+        }
+    }
+
+    /// Fetch the schedule of the epoch holding `slot` instead of the current epoch
+    pub fn add_slot(&mut self, slot: u64) -> &mut Self {
+        self.slot = Some(slot);
+
+        self
+    }
+
+    pub fn change_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.commitment = commitment;
+
+        self
+    }
+
+    /// Only return the schedule of this validator identity
+    pub fn add_identity(&mut self, identity: &str) -> &mut Self {
+        self.identity = Some(identity.to_owned());
+
+        self
+    }
+
+    /// The schedule is `None` if the epoch is not known yet
+    pub async fn process(&self) -> PoseidonResult<RpcResponse<Option<RpcLeaderSchedule>>> {
+        request::<Option<RpcLeaderSchedule>>(self.body()).await
+    }
+
+    fn body(&self) -> json::JsonValue {
+        let mut config = RpcContextConfig::new()
+            .change_commitment(self.commitment)
+            .to_json();
+        if let Some(identity) = &self.identity {
+            config["identity"] = identity.as_str().into();
+        }
+
+        json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getLeaderSchedule",
+            params: json::array![self.slot, config]
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochInfo {
+    /// The current slot
+    pub absolute_slot: u64,
+    /// The current block height
+    pub block_height: u64,
+    /// The current epoch
+    pub epoch: u64,
+    /// The current slot relative to the start of the current epoch
+    pub slot_index: u64,
+    /// The number of slots in this epoch
+    pub slots_in_epoch: u64,
+    /// The total number of transactions processed without error since genesis
+    pub transaction_count: Option<u64>,
+}
+
+pub struct GetEpochInfo;
+
+impl GetEpochInfo {
+    pub async fn process(config: &RpcContextConfig) -> PoseidonResult<RpcResponse<EpochInfo>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getEpochInfo",
+            params: json::array![config.to_json()]
+        };
+
+        request::<EpochInfo>(body).await
+    }
+}
+
+pub struct GetEpochSchedule;
+
+impl GetEpochSchedule {
+    pub async fn process() -> PoseidonResult<RpcResponse<EpochSchedule>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getEpochSchedule",
+        };
+
+        request::<EpochSchedule>(body).await
+    }
+}
+
+pub struct GetTransactionCount;
+
+impl GetTransactionCount {
+    pub async fn process(config: &RpcContextConfig) -> PoseidonResult<RpcResponse<u64>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getTransactionCount",
+            params: json::array![config.to_json()]
+        };
+
+        request::<u64>(body).await
+    }
+}
+
+pub struct GetFirstAvailableBlock;
+
+impl GetFirstAvailableBlock {
+    /// The slot of the lowest confirmed block that has not been purged from the ledger
+    pub async fn process() -> PoseidonResult<RpcResponse<u64>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getFirstAvailableBlock",
+        };
+
+        request::<u64>(body).await
+    }
+}
+
+impl Cluster {
+    /// Identify the cluster the RPC node at `url` belongs to from its genesis hash.
+    /// Returns `None` for clusters other than the public ones, like a local test validator.
+    pub async fn detect(url: &str) -> PoseidonResult<Option<Cluster>> {
+        let mut rpc = RpcClient::new();
+        rpc.add_url(url).common_methods(GetGenesisHash::body());
+        let response = rpc.send().await?;
+        let genesis_hash =
+            parse_response::<RpcResponse<Base58BlockHash>>(response.as_str()?)?.result;
+
+        Ok(Cluster::from_genesis_hash(&genesis_hash))
+    }
+
+    pub fn from_genesis_hash(genesis_hash: &str) -> Option<Cluster> {
+        match genesis_hash {
+            "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d" => Some(Cluster::MainNetBeta),
+            "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG" => Some(Cluster::DevNet),
+            "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY" => Some(Cluster::TestNet),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+
+    #[test]
+    fn context_config_and_responses() {
+        let config = RpcContextConfig::new()
+            .change_commitment(Commitment::Confirmed)
+            .add_min_context_slot(42)
+            .to_json();
+        assert_eq!(config["commitment"], "confirmed");
+        assert_eq!(config["minContextSlot"], 42);
+
+        let config = RpcContextConfig::new()
+            .change_commitment(Commitment::Unspecified)
+            .to_json();
+        assert!(config["commitment"].is_null());
+        assert!(config["minContextSlot"].is_null());
+
+        let mut leader_schedule = GetLeaderSchedule::new();
+        leader_schedule.add_identity("4Qkev8aNZcqFNSRhQzwyLMFSsi94jHqE8WNVTJzTP99F");
+        let body = leader_schedule.body();
+        assert_eq!(body["params"][1]["commitment"], "finalized");
+        assert_eq!(
+            body["params"][1]["identity"],
+            "4Qkev8aNZcqFNSRhQzwyLMFSsi94jHqE8WNVTJzTP99F"
+        );
+
+        let body = leader_schedule
+            .change_commitment(Commitment::Unspecified)
+            .add_slot(42)
+            .body();
+        assert_eq!(body["params"][0], 42);
+        assert!(body["params"][1]["commitment"].is_null());
+
+        let epoch_info = parse_response::<RpcResponse<EpochInfo>>(
+            r#"{"jsonrpc":"2.0","result":{"absoluteSlot":166598,"blockHeight":166500,"epoch":27,"slotIndex":2790,"slotsInEpoch":8192,"transactionCount":22661093},"id":1}"#,
+        )
+        .unwrap();
+        assert_eq!(epoch_info.result.slot_index, 2790);
+
+        let version = parse_response::<RpcResponse<RpcVersionInfo>>(
+            r#"{"jsonrpc":"2.0","result":{"feature-set":2891131721,"solana-core":"1.16.7"},"id":1}"#,
+        )
+        .unwrap();
+        assert_eq!(version.result.solana_core, "1.16.7");
+
+        let unhealthy = parse_response::<RpcResponse<String>>(
+            r#"{"jsonrpc":"2.0","error":{"code":-32005,"message":"Node is behind by 42 slots","data":{"numSlotsBehind":42}},"id":1}"#,
+        );
+        assert!(matches!(
+            unhealthy,
+            Err(PoseidonError::JsonRpc(error)) if error.code == NODE_UNHEALTHY_ERROR_CODE
+        ));
+
+        assert_eq!(
+            Cluster::from_genesis_hash("EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG"),
+            Some(Cluster::DevNet)
+        );
+        assert_eq!(
+            Cluster::from_genesis_hash("11111111111111111111111111111111"),
+            None
+        );
+    }
+}
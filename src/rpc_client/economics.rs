@@ -0,0 +1,287 @@
+use crate::{
+    request, request_with_result, Base58PublicKey, Commitment, PoseidonResult, RpcContextConfig,
+    RpcResponse, RpcResponseWithResult,
+};
+use serde::{Deserialize, Serialize};
+
+pub struct GetSupply;
+
+impl GetSupply {
+    /// The supply of lamports. The non circulating accounts are
+    /// left out of the response when `exclude_non_circulating_accounts_list` is `true`.
+    pub async fn process(
+        commitment: Commitment,
+        exclude_non_circulating_accounts_list: bool,
+    ) -> PoseidonResult<RpcResponseWithResult<RpcSupply>> {
+        let commitment: &str = commitment.into();
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getSupply",
+            params: json::array![json::object!{
+                commitment: commitment,
+                excludeNonCirculatingAccountsList: exclude_non_circulating_accounts_list,
+            }]
+        };
+
+        request_with_result::<RpcSupply>(body).await
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSupply {
+    /// Total supply in lamports
+    pub total: u64,
+    /// Circulating supply in lamports
+    pub circulating: u64,
+    /// Non-circulating supply in lamports
+    pub non_circulating: u64,
+    /// The addresses of the non-circulating accounts
+    pub non_circulating_accounts: Vec<Base58PublicKey>,
+}
+
+/// Filter the accounts returned by `GetLargestAccounts` by their circulation status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LargestAccountsFilter {
+    Circulating,
+    NonCirculating,
+}
+
+impl From<LargestAccountsFilter> for &str {
+    fn from(filter: LargestAccountsFilter) -> Self {
+        match filter {
+            LargestAccountsFilter::Circulating => "circulating",
+            LargestAccountsFilter::NonCirculating => "nonCirculating",
+        }
+    }
+}
+
+pub struct GetLargestAccounts;
+
+impl GetLargestAccounts {
+    /// The 20 largest accounts by lamport balance. The results may be cached for up to two hours.
+    pub async fn process(
+        commitment: Commitment,
+        filter: Option<LargestAccountsFilter>,
+    ) -> PoseidonResult<RpcResponseWithResult<Vec<RpcAccountBalance>>> {
+        let commitment: &str = commitment.into();
+
+        let mut config = json::object! {
+            commitment: commitment,
+        };
+        if let Some(filter) = filter {
+            let filter: &str = filter.into();
+            config["filter"] = filter.into();
+        }
+
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getLargestAccounts",
+            params: json::array![config]
+        };
+
+        request_with_result::<Vec<RpcAccountBalance>>(body).await
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountBalance {
+    pub address: Base58PublicKey,
+    pub lamports: u64,
+}
+
+pub struct GetInflationGovernor;
+
+impl GetInflationGovernor {
+    pub async fn process(
+        commitment: Commitment,
+    ) -> PoseidonResult<RpcResponse<RpcInflationGovernor>> {
+        let commitment: &str = commitment.into();
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getInflationGovernor",
+            params: json::array![json::object!{
+                commitment: commitment,
+            }]
+        };
+
+        request::<RpcInflationGovernor>(body).await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcInflationGovernor {
+    /// The initial inflation percentage from time 0
+    pub initial: f64,
+    /// The terminal inflation percentage
+    pub terminal: f64,
+    /// The rate per year at which inflation is lowered
+    pub taper: f64,
+    /// The percentage of total inflation allocated to the foundation
+    pub foundation: f64,
+    /// The duration of the foundation pool inflation in years
+    pub foundation_term: f64,
+}
+
+pub struct GetInflationRate;
+
+impl GetInflationRate {
+    /// The inflation values of the current epoch
+    pub async fn process() -> PoseidonResult<RpcResponse<RpcInflationRate>> {
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getInflationRate",
+        };
+
+        request::<RpcInflationRate>(body).await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcInflationRate {
+    /// Total inflation
+    pub total: f64,
+    /// Inflation allocated to validators
+    pub validator: f64,
+    /// Inflation allocated to the foundation
+    pub foundation: f64,
+    /// The epoch the values are valid for
+    pub epoch: u64,
+}
+
+pub struct GetInflationReward;
+
+impl GetInflationReward {
+    /// The inflation or staking rewards of `addresses` for `epoch`, defaulting to the
+    /// previous epoch. A reward is `None` if the address did not receive one.
+    pub async fn process(
+        addresses: &[Base58PublicKey],
+        epoch: Option<u64>,
+        config: &RpcContextConfig,
+    ) -> PoseidonResult<RpcResponse<Vec<Option<RpcInflationReward>>>> {
+        let mut config = config.to_json();
+        if let Some(epoch) = epoch {
+            config["epoch"] = epoch.into();
+        }
+
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getInflationReward",
+            params: json::array![addresses.to_vec(), config]
+        };
+
+        request::<Vec<Option<RpcInflationReward>>>(body).await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcInflationReward {
+    /// The epoch the reward was credited for
+    pub epoch: u64,
+    /// The slot in which the reward was credited
+    pub effective_slot: u64,
+    /// The reward in lamports
+    pub amount: u64,
+    /// The balance of the account in lamports after the reward was credited
+    pub post_balance: u64,
+    /// The vote account commission when the reward was credited
+    pub commission: Option<u8>,
+}
+
+pub struct GetStakeMinimumDelegation;
+
+impl GetStakeMinimumDelegation {
+    /// The minimum delegation of a Stake account in lamports
+    pub async fn process(commitment: Commitment) -> PoseidonResult<RpcResponseWithResult<u64>> {
+        let commitment: &str = commitment.into();
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getStakeMinimumDelegation",
+            params: json::array![json::object!{
+                commitment: commitment,
+            }]
+        };
+
+        request_with_result::<u64>(body).await
+    }
+}
+
+pub struct GetRecentPerformanceSamples;
+
+impl GetRecentPerformanceSamples {
+    /// Up to `limit` performance samples, newest first. Samples are taken every 60 seconds
+    /// and the RPC returns at most 720 of them.
+    pub async fn process(limit: Option<usize>) -> PoseidonResult<RpcResponse<Vec<RpcPerfSample>>> {
+        let params = match limit {
+            Some(limit) => json::array![limit],
+            None => json::array![],
+        };
+
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getRecentPerformanceSamples",
+            params: params
+        };
+
+        request::<Vec<RpcPerfSample>>(body).await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPerfSample {
+    /// The slot the sample was taken at
+    pub slot: u64,
+    /// The number of transactions processed during the sample period
+    pub num_transactions: u64,
+    /// The number of slots completed during the sample period
+    pub num_slots: u64,
+    /// The number of seconds in the sample window
+    pub sample_period_secs: u16,
+    /// The number of non-vote transactions processed during the sample period
+    pub num_non_vote_transactions: Option<u64>,
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::parse_response;
+
+    #[test]
+    fn decode_economics_responses() {
+        let supply = parse_response::<RpcResponseWithResult<RpcSupply>>(
+            r#"{"jsonrpc":"2.0","result":{"context":{"apiVersion":"1.16.7","slot":1114},"value":{"circulating":16000,"nonCirculating":1000000,"nonCirculatingAccounts":["FEy8pTbP5fEoqMV1GdTz83byuJ8nRYrPZyKaDkJgQUsN"],"total":1016000}},"id":1}"#,
+        )
+        .unwrap();
+        assert_eq!(supply.result.value.non_circulating, 1_000_000);
+        assert_eq!(supply.result.value.non_circulating_accounts.len(), 1);
+
+        let rewards = parse_response::<RpcResponse<Vec<Option<RpcInflationReward>>>>(
+            r#"{"jsonrpc":"2.0","result":[{"amount":2500,"effectiveSlot":224,"epoch":2,"postBalance":499999442500,"commission":null},null],"id":1}"#,
+        )
+        .unwrap();
+        assert_eq!(rewards.result[0].unwrap().effective_slot, 224);
+        assert!(rewards.result[0].unwrap().commission.is_none());
+        assert!(rewards.result[1].is_none());
+
+        let samples = parse_response::<RpcResponse<Vec<RpcPerfSample>>>(
+            r#"{"jsonrpc":"2.0","result":[{"numSlots":126,"numTransactions":126,"numNonVoteTransactions":1,"samplePeriodSecs":60,"slot":348125}],"id":1}"#,
+        )
+        .unwrap();
+        assert_eq!(samples.result[0].sample_period_secs, 60);
+
+        let filter: &str = LargestAccountsFilter::NonCirculating.into();
+        assert_eq!(filter, "nonCirculating");
+    }
+}
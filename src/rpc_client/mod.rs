@@ -7,5 +7,41 @@ pub use rpc_info::*;
 mod tx;
 pub use tx::*;
 
+mod parsed_instruction;
+pub use parsed_instruction::*;
+
 mod send_transaction;
 pub use send_transaction::*;
+
+mod vote_accounts;
+pub use vote_accounts::*;
+
+mod signature_statuses;
+pub use signature_statuses::*;
+
+mod program_deployer;
+pub use program_deployer::*;
+
+mod blockhash_cache;
+pub use blockhash_cache::*;
+
+mod rent_cache;
+pub use rent_cache::*;
+
+mod cluster_info;
+pub use cluster_info::*;
+
+mod block;
+pub use block::*;
+
+mod block_stream;
+pub use block_stream::*;
+
+mod economics;
+pub use economics::*;
+
+mod program_logs;
+pub use program_logs::*;
+
+mod program_errors;
+pub use program_errors::*;
@@ -0,0 +1,731 @@
+use crate::{
+    Base58BlockHash, Base58PublicKey, Base58TxSignature, RpcAccountKey, RpcAddressTableLookup,
+    RpcCompiledInstruction, TokenAmount,
+};
+use serde::{Deserialize, Serialize};
+
+/// A transaction returned with the `jsonParsed` encoding
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcParsedTransaction {
+    pub signatures: Vec<Base58TxSignature>,
+    pub message: RpcParsedMessage,
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcParsedMessage {
+    pub account_keys: Vec<RpcAccountKey>,
+    pub recent_blockhash: Base58BlockHash,
+    pub instructions: Vec<UiInstruction>,
+    #[serde(default)]
+    pub address_table_lookups: Option<Vec<RpcAddressTableLookup>>,
+}
+
+/// An instruction in any of the encodings returned by the RPC
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum UiInstruction {
+    Compiled(RpcCompiledInstruction),
+    Parsed(ParsedInstruction),
+    /// An instruction of a program the RPC cannot parse
+    PartiallyDecoded(PartiallyDecodedInstruction),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartiallyDecodedInstruction {
+    pub program_id: Base58PublicKey,
+    pub accounts: Vec<Base58PublicKey>,
+    /// Base58 encoded instruction data
+    pub data: String,
+    #[serde(default)]
+    pub stack_height: Option<u32>,
+}
+
+/// An instruction the RPC parsed into a human readable form
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(from = "RawParsedInstruction", into = "RawParsedInstruction")]
+pub struct ParsedInstruction {
+    pub program_id: Base58PublicKey,
+    pub stack_height: Option<u32>,
+    pub instruction: ParsedProgramInstruction,
+}
+
+/// The parsed instruction of each program. Instructions of programs or instruction
+/// types that are not modelled here are kept as `Unknown`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParsedProgramInstruction {
+    System(ParsedSystemInstruction),
+    SplToken(ParsedTokenInstruction),
+    AssociatedToken(ParsedAssociatedTokenInstruction),
+    Memo(String),
+    Stake(ParsedStakeInstruction),
+    Vote(ParsedVoteInstruction),
+    BpfLoader(ParsedBpfLoaderInstruction),
+    BpfUpgradeableLoader(ParsedBpfUpgradeableLoaderInstruction),
+    Unknown {
+        program: String,
+        parsed: serde_json::Value,
+    },
+}
+
+impl ParsedProgramInstruction {
+    /// The name the RPC uses for the program
+    pub fn program(&self) -> &str {
+        match self {
+            ParsedProgramInstruction::System(_) => "system",
+            ParsedProgramInstruction::SplToken(_) => "spl-token",
+            ParsedProgramInstruction::AssociatedToken(_) => "spl-associated-token-account",
+            ParsedProgramInstruction::Memo(_) => "spl-memo",
+            ParsedProgramInstruction::Stake(_) => "stake",
+            ParsedProgramInstruction::Vote(_) => "vote",
+            ParsedProgramInstruction::BpfLoader(_) => "bpf-loader",
+            ParsedProgramInstruction::BpfUpgradeableLoader(_) => "bpf-upgradeable-loader",
+            ParsedProgramInstruction::Unknown { program, .. } => program,
+        }
+    }
+}
+
+// The layout of a parsed instruction in the RPC response
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RawParsedInstruction {
+    program: String,
+    program_id: Base58PublicKey,
+    parsed: serde_json::Value,
+    #[serde(default)]
+    stack_height: Option<u32>,
+}
+
+impl From<RawParsedInstruction> for ParsedInstruction {
+    fn from(raw: RawParsedInstruction) -> Self {
+        let parsed = raw.parsed.clone();
+        let typed = match raw.program.as_str() {
+            "system" => serde_json::from_value(parsed)
+                .ok()
+                .map(ParsedProgramInstruction::System),
+            "spl-token" => serde_json::from_value(parsed)
+                .ok()
+                .map(ParsedProgramInstruction::SplToken),
+            "spl-associated-token-account" => serde_json::from_value(parsed)
+                .ok()
+                .map(ParsedProgramInstruction::AssociatedToken),
+            "spl-memo" => serde_json::from_value(parsed)
+                .ok()
+                .map(ParsedProgramInstruction::Memo),
+            "stake" => serde_json::from_value(parsed)
+                .ok()
+                .map(ParsedProgramInstruction::Stake),
+            "vote" => serde_json::from_value(parsed)
+                .ok()
+                .map(ParsedProgramInstruction::Vote),
+            "bpf-loader" => serde_json::from_value(parsed)
+                .ok()
+                .map(ParsedProgramInstruction::BpfLoader),
+            "bpf-upgradeable-loader" => serde_json::from_value(parsed)
+                .ok()
+                .map(ParsedProgramInstruction::BpfUpgradeableLoader),
+            _ => None,
+        };
+
+        ParsedInstruction {
+            program_id: raw.program_id,
+            stack_height: raw.stack_height,
+            instruction: typed.unwrap_or(ParsedProgramInstruction::Unknown {
+                program: raw.program,
+                parsed: raw.parsed,
+            }),
+        }
+    }
+}
+
+impl From<ParsedInstruction> for RawParsedInstruction {
+    fn from(instruction: ParsedInstruction) -> Self {
+        let program = instruction.instruction.program().to_owned();
+        let parsed = match instruction.instruction {
+            ParsedProgramInstruction::System(parsed) => serde_json::to_value(parsed),
+            ParsedProgramInstruction::SplToken(parsed) => serde_json::to_value(parsed),
+            ParsedProgramInstruction::AssociatedToken(parsed) => serde_json::to_value(parsed),
+            ParsedProgramInstruction::Memo(parsed) => serde_json::to_value(parsed),
+            ParsedProgramInstruction::Stake(parsed) => serde_json::to_value(parsed),
+            ParsedProgramInstruction::Vote(parsed) => serde_json::to_value(parsed),
+            ParsedProgramInstruction::BpfLoader(parsed) => serde_json::to_value(parsed),
+            ParsedProgramInstruction::BpfUpgradeableLoader(parsed) => serde_json::to_value(parsed),
+            ParsedProgramInstruction::Unknown { parsed, .. } => Ok(parsed),
+        };
+
+        RawParsedInstruction {
+            program,
+            program_id: instruction.program_id,
+            parsed: parsed.unwrap_or_default(),
+            stack_height: instruction.stack_height,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+pub enum ParsedSystemInstruction {
+    #[serde(rename_all = "camelCase")]
+    CreateAccount {
+        source: Base58PublicKey,
+        new_account: Base58PublicKey,
+        lamports: u64,
+        space: u64,
+        owner: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    Assign {
+        account: Base58PublicKey,
+        owner: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    Transfer {
+        source: Base58PublicKey,
+        destination: Base58PublicKey,
+        lamports: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    CreateAccountWithSeed {
+        source: Base58PublicKey,
+        new_account: Base58PublicKey,
+        base: Base58PublicKey,
+        seed: String,
+        lamports: u64,
+        space: u64,
+        owner: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    AdvanceNonce {
+        nonce_account: Base58PublicKey,
+        recent_blockhashes_sysvar: Base58PublicKey,
+        nonce_authority: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    WithdrawFromNonce {
+        nonce_account: Base58PublicKey,
+        destination: Base58PublicKey,
+        recent_blockhashes_sysvar: Base58PublicKey,
+        rent_sysvar: Base58PublicKey,
+        nonce_authority: Base58PublicKey,
+        lamports: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    InitializeNonce {
+        nonce_account: Base58PublicKey,
+        recent_blockhashes_sysvar: Base58PublicKey,
+        rent_sysvar: Base58PublicKey,
+        nonce_authority: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    AuthorizeNonce {
+        nonce_account: Base58PublicKey,
+        nonce_authority: Base58PublicKey,
+        new_authorized: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    UpgradeNonce { nonce_account: Base58PublicKey },
+    #[serde(rename_all = "camelCase")]
+    Allocate {
+        account: Base58PublicKey,
+        space: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    AllocateWithSeed {
+        account: Base58PublicKey,
+        base: Base58PublicKey,
+        seed: String,
+        space: u64,
+        owner: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    AssignWithSeed {
+        account: Base58PublicKey,
+        base: Base58PublicKey,
+        seed: String,
+        owner: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    TransferWithSeed {
+        source: Base58PublicKey,
+        source_base: Base58PublicKey,
+        destination: Base58PublicKey,
+        lamports: u64,
+        source_seed: String,
+        source_owner: Base58PublicKey,
+    },
+}
+
+/// Token program instructions. Accounts controlled by a multisig name the multisig
+/// in the `multisig_*` field and its signers in `signers` instead of a single authority.
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+pub enum ParsedTokenInstruction {
+    #[serde(rename_all = "camelCase")]
+    InitializeMint {
+        mint: Base58PublicKey,
+        decimals: u8,
+        mint_authority: Base58PublicKey,
+        freeze_authority: Option<Base58PublicKey>,
+        rent_sysvar: Option<Base58PublicKey>,
+    },
+    #[serde(rename_all = "camelCase")]
+    InitializeMint2 {
+        mint: Base58PublicKey,
+        decimals: u8,
+        mint_authority: Base58PublicKey,
+        freeze_authority: Option<Base58PublicKey>,
+    },
+    #[serde(rename_all = "camelCase")]
+    InitializeAccount {
+        account: Base58PublicKey,
+        mint: Base58PublicKey,
+        owner: Base58PublicKey,
+        rent_sysvar: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    InitializeAccount2 {
+        account: Base58PublicKey,
+        mint: Base58PublicKey,
+        owner: Base58PublicKey,
+        rent_sysvar: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    InitializeAccount3 {
+        account: Base58PublicKey,
+        mint: Base58PublicKey,
+        owner: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    Transfer {
+        source: Base58PublicKey,
+        destination: Base58PublicKey,
+        /// The amount in base units
+        amount: String,
+        authority: Option<Base58PublicKey>,
+        multisig_authority: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Approve {
+        source: Base58PublicKey,
+        delegate: Base58PublicKey,
+        amount: String,
+        owner: Option<Base58PublicKey>,
+        multisig_owner: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Revoke {
+        source: Base58PublicKey,
+        owner: Option<Base58PublicKey>,
+        multisig_owner: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    SetAuthority {
+        /// Present when the authority of a mint is changed
+        mint: Option<Base58PublicKey>,
+        /// Present when the authority of a token account is changed
+        account: Option<Base58PublicKey>,
+        authority_type: String,
+        new_authority: Option<Base58PublicKey>,
+        authority: Option<Base58PublicKey>,
+        multisig_authority: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    MintTo {
+        mint: Base58PublicKey,
+        account: Base58PublicKey,
+        amount: String,
+        mint_authority: Option<Base58PublicKey>,
+        multisig_mint_authority: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Burn {
+        account: Base58PublicKey,
+        mint: Base58PublicKey,
+        amount: String,
+        authority: Option<Base58PublicKey>,
+        multisig_authority: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    CloseAccount {
+        account: Base58PublicKey,
+        destination: Base58PublicKey,
+        owner: Option<Base58PublicKey>,
+        multisig_owner: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    FreezeAccount {
+        account: Base58PublicKey,
+        mint: Base58PublicKey,
+        freeze_authority: Option<Base58PublicKey>,
+        multisig_freeze_authority: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    ThawAccount {
+        account: Base58PublicKey,
+        mint: Base58PublicKey,
+        freeze_authority: Option<Base58PublicKey>,
+        multisig_freeze_authority: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    TransferChecked {
+        source: Base58PublicKey,
+        mint: Base58PublicKey,
+        destination: Base58PublicKey,
+        token_amount: TokenAmount,
+        authority: Option<Base58PublicKey>,
+        multisig_authority: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    MintToChecked {
+        mint: Base58PublicKey,
+        account: Base58PublicKey,
+        token_amount: TokenAmount,
+        mint_authority: Option<Base58PublicKey>,
+        multisig_mint_authority: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    BurnChecked {
+        account: Base58PublicKey,
+        mint: Base58PublicKey,
+        token_amount: TokenAmount,
+        authority: Option<Base58PublicKey>,
+        multisig_authority: Option<Base58PublicKey>,
+        signers: Option<Vec<Base58PublicKey>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    SyncNative { account: Base58PublicKey },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+pub enum ParsedAssociatedTokenInstruction {
+    #[serde(rename_all = "camelCase")]
+    Create {
+        source: Base58PublicKey,
+        account: Base58PublicKey,
+        wallet: Base58PublicKey,
+        mint: Base58PublicKey,
+        system_program: Base58PublicKey,
+        token_program: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    CreateIdempotent {
+        source: Base58PublicKey,
+        account: Base58PublicKey,
+        wallet: Base58PublicKey,
+        mint: Base58PublicKey,
+        system_program: Base58PublicKey,
+        token_program: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    RecoverNested {
+        nested_source: Base58PublicKey,
+        nested_mint: Base58PublicKey,
+        destination: Base58PublicKey,
+        nested_owner: Base58PublicKey,
+        owner_mint: Base58PublicKey,
+        wallet: Base58PublicKey,
+        token_program: Base58PublicKey,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedAuthorized {
+    pub staker: Base58PublicKey,
+    pub withdrawer: Base58PublicKey,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedLockup {
+    pub unix_timestamp: i64,
+    pub epoch: u64,
+    pub custodian: Base58PublicKey,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+pub enum ParsedStakeInstruction {
+    #[serde(rename_all = "camelCase")]
+    Initialize {
+        stake_account: Base58PublicKey,
+        rent_sysvar: Base58PublicKey,
+        authorized: ParsedAuthorized,
+        lockup: ParsedLockup,
+    },
+    #[serde(rename_all = "camelCase")]
+    Authorize {
+        stake_account: Base58PublicKey,
+        clock_sysvar: Base58PublicKey,
+        authority: Base58PublicKey,
+        new_authority: Base58PublicKey,
+        authority_type: String,
+        custodian: Option<Base58PublicKey>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Delegate {
+        stake_account: Base58PublicKey,
+        vote_account: Base58PublicKey,
+        clock_sysvar: Base58PublicKey,
+        stake_history_sysvar: Base58PublicKey,
+        stake_config_account: Base58PublicKey,
+        stake_authority: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    Split {
+        stake_account: Base58PublicKey,
+        new_split_account: Base58PublicKey,
+        stake_authority: Base58PublicKey,
+        lamports: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Withdraw {
+        stake_account: Base58PublicKey,
+        destination: Base58PublicKey,
+        clock_sysvar: Base58PublicKey,
+        stake_history_sysvar: Base58PublicKey,
+        withdraw_authority: Base58PublicKey,
+        lamports: u64,
+        custodian: Option<Base58PublicKey>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Deactivate {
+        stake_account: Base58PublicKey,
+        clock_sysvar: Base58PublicKey,
+        stake_authority: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    Merge {
+        destination: Base58PublicKey,
+        source: Base58PublicKey,
+        clock_sysvar: Base58PublicKey,
+        stake_history_sysvar: Base58PublicKey,
+        stake_authority: Base58PublicKey,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedVote {
+    pub slots: Vec<u64>,
+    pub hash: Base58BlockHash,
+    pub timestamp: Option<i64>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+pub enum ParsedVoteInstruction {
+    #[serde(rename_all = "camelCase")]
+    Initialize {
+        vote_account: Base58PublicKey,
+        rent_sysvar: Base58PublicKey,
+        clock_sysvar: Base58PublicKey,
+        node: Base58PublicKey,
+        authorized_voter: Base58PublicKey,
+        authorized_withdrawer: Base58PublicKey,
+        commission: u8,
+    },
+    #[serde(rename_all = "camelCase")]
+    Authorize {
+        vote_account: Base58PublicKey,
+        clock_sysvar: Base58PublicKey,
+        authority: Base58PublicKey,
+        new_authority: Base58PublicKey,
+        authority_type: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Vote {
+        vote_account: Base58PublicKey,
+        slot_hashes_sysvar: Base58PublicKey,
+        clock_sysvar: Base58PublicKey,
+        vote_authority: Base58PublicKey,
+        vote: ParsedVote,
+    },
+    #[serde(rename_all = "camelCase")]
+    Withdraw {
+        vote_account: Base58PublicKey,
+        destination: Base58PublicKey,
+        withdraw_authority: Base58PublicKey,
+        lamports: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    UpdateValidatorIdentity {
+        vote_account: Base58PublicKey,
+        new_validator_identity: Base58PublicKey,
+        withdraw_authority: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    UpdateCommission {
+        vote_account: Base58PublicKey,
+        withdraw_authority: Base58PublicKey,
+        commission: u8,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+pub enum ParsedBpfLoaderInstruction {
+    #[serde(rename_all = "camelCase")]
+    Write {
+        offset: u32,
+        /// Base64 encoded program bytes
+        bytes: String,
+        account: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    Finalize { account: Base58PublicKey },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+pub enum ParsedBpfUpgradeableLoaderInstruction {
+    #[serde(rename_all = "camelCase")]
+    InitializeBuffer {
+        account: Base58PublicKey,
+        authority: Option<Base58PublicKey>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Write {
+        offset: u32,
+        /// Base64 encoded program bytes
+        bytes: String,
+        account: Base58PublicKey,
+        authority: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    DeployWithMaxDataLen {
+        max_data_len: u64,
+        payer_account: Base58PublicKey,
+        program_data_account: Base58PublicKey,
+        program_account: Base58PublicKey,
+        buffer_account: Base58PublicKey,
+        authority: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    Upgrade {
+        program_data_account: Base58PublicKey,
+        program_account: Base58PublicKey,
+        buffer_account: Base58PublicKey,
+        spill_account: Base58PublicKey,
+        authority: Base58PublicKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    SetAuthority {
+        account: Base58PublicKey,
+        authority: Base58PublicKey,
+        new_authority: Option<Base58PublicKey>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Close {
+        account: Base58PublicKey,
+        recipient: Base58PublicKey,
+        authority: Base58PublicKey,
+        program_account: Option<Base58PublicKey>,
+    },
+    #[serde(rename_all = "camelCase")]
+    ExtendProgram {
+        additional_bytes: u32,
+        program_data_account: Base58PublicKey,
+        program_account: Base58PublicKey,
+        payer_account: Option<Base58PublicKey>,
+    },
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+
+    fn parse(json: &str) -> UiInstruction {
+        serde_json::from_str::<UiInstruction>(json).unwrap()
+    }
+
+    #[test]
+    fn parsed_instructions() {
+        let transfer = parse(
+            r#"{"parsed":{"info":{"destination":"9aE476sH92Vz7DMPyq5WLPkrKWivxeuTKEFKd2sZZcde","lamports":2000000,"source":"7Y6CfNwWQfnnpk4atZ7ARwTBthh6SJMx9sUPnPLDBj9D"},"type":"transfer"},"program":"system","programId":"11111111111111111111111111111111","stackHeight":null}"#,
+        );
+        match transfer {
+            UiInstruction::Parsed(ParsedInstruction {
+                instruction:
+                    ParsedProgramInstruction::System(ParsedSystemInstruction::Transfer {
+                        lamports, ..
+                    }),
+                ..
+            }) => assert_eq!(lamports, 2_000_000),
+            _ => panic!("Expected a system transfer"),
+        }
+
+        let token_transfer = parse(
+            r#"{"parsed":{"info":{"authority":"7Y6CfNwWQfnnpk4atZ7ARwTBthh6SJMx9sUPnPLDBj9D","destination":"3Xo9B5aGgcJSBS9CfmDr4yNwuUwiQk16P7jcp6M5W9AX","mint":"EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v","source":"8aE476sH92Vz7DMPyq5WLPkrKWivxeuTKEFKd2sZZcde","tokenAmount":{"amount":"2500000","decimals":6,"uiAmount":2.5,"uiAmountString":"2.5"}},"type":"transferChecked"},"program":"spl-token","programId":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","stackHeight":2}"#,
+        );
+        match &token_transfer {
+            UiInstruction::Parsed(ParsedInstruction {
+                stack_height,
+                instruction:
+                    ParsedProgramInstruction::SplToken(ParsedTokenInstruction::TransferChecked {
+                        token_amount,
+                        ..
+                    }),
+                ..
+            }) => {
+                assert_eq!(*stack_height, Some(2));
+                assert_eq!(token_amount.ui_amount_string, "2.5");
+            }
+            _ => panic!("Expected a token transfer"),
+        }
+
+        let memo = parse(
+            r#"{"parsed":"gm","program":"spl-memo","programId":"MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr","stackHeight":null}"#,
+        );
+        match &memo {
+            UiInstruction::Parsed(instruction) => assert_eq!(
+                instruction.instruction,
+                ParsedProgramInstruction::Memo("gm".to_owned())
+            ),
+            _ => panic!("Expected a memo"),
+        }
+
+        // Instruction types without a typed representation are kept as they were parsed
+        let compact_vote = parse(
+            r#"{"parsed":{"info":{"voteAccount":"Vote111111111111111111111111111111111111111"},"type":"towersync"},"program":"vote","programId":"Vote111111111111111111111111111111111111111","stackHeight":null}"#,
+        );
+        match &compact_vote {
+            UiInstruction::Parsed(instruction) => {
+                assert_eq!(instruction.instruction.program(), "vote");
+                assert!(matches!(
+                    &instruction.instruction,
+                    ParsedProgramInstruction::Unknown { parsed, .. } if parsed["type"] == "towersync"
+                ));
+            }
+            _ => panic!("Expected an unknown vote instruction"),
+        }
+
+        let partially_decoded = parse(
+            r#"{"accounts":["7Y6CfNwWQfnnpk4atZ7ARwTBthh6SJMx9sUPnPLDBj9D"],"data":"3Bxs4h24hBtQy9rw","programId":"JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4","stackHeight":null}"#,
+        );
+        assert!(matches!(
+            partially_decoded,
+            UiInstruction::PartiallyDecoded(_)
+        ));
+
+        // Parsed instructions serialize back to the layout returned by the RPC
+        let round_trip =
+            serde_json::from_str::<UiInstruction>(&serde_json::to_string(&token_transfer).unwrap())
+                .unwrap();
+        assert_eq!(round_trip, token_transfer);
+    }
+}
@@ -0,0 +1,443 @@
+use crate::{
+    Base58TxSignature, BpfLoaderUpgradeable, GetLatestBlockhash, GetMinimumBalanceForRentExemption,
+    GetSignatureStatuses, Instruction, Message, MessageBuilder, PdaPublicKey, PoseidonError,
+    PoseidonResult, PublicKey, RecentBlockHash, RpcClient, Transaction, TxSendOutcome,
+    BUFFER_METADATA_SIZE, PROGRAM_ACCOUNT_SIZE,
+};
+use ed25519_dalek::Keypair;
+use std::time::Duration;
+
+/// The RPC methods needed to deploy a program. `RpcClient` implements it, sending
+/// every call to its URL, and tests can provide a mock that records the transactions
+/// it is sent.
+pub trait TransactionSender {
+    fn latest_blockhash(&self) -> smol::Task<PoseidonResult<RecentBlockHash>>;
+
+    fn minimum_balance_for_rent_exemption(
+        &self,
+        data_len: usize,
+    ) -> smol::Task<PoseidonResult<u64>>;
+
+    fn send_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> smol::Task<PoseidonResult<TxSendOutcome>>;
+
+    fn confirm_transaction(&self, signature: Base58TxSignature) -> smol::Task<PoseidonResult<()>>;
+}
+
+impl TransactionSender for RpcClient {
+    fn latest_blockhash(&self) -> smol::Task<PoseidonResult<RecentBlockHash>> {
+        let rpc = self.clone();
+        let body = GetLatestBlockhash::body(self.commitment);
+
+        smol::spawn(async move {
+            rpc.request_with_result::<GetLatestBlockhash>(body)
+                .await?
+                .result
+                .value
+                .to_bytes()
+        })
+    }
+
+    fn minimum_balance_for_rent_exemption(
+        &self,
+        data_len: usize,
+    ) -> smol::Task<PoseidonResult<u64>> {
+        let rpc = self.clone();
+        let body = GetMinimumBalanceForRentExemption::body(data_len);
+
+        smol::spawn(async move { Ok(rpc.request::<u64>(body).await?.result) })
+    }
+
+    fn send_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> smol::Task<PoseidonResult<TxSendOutcome>> {
+        let mut rpc = self.clone();
+        let response = rpc.prepare_transaction(transaction).map(|rpc| rpc.send());
+
+        smol::spawn(async move { TxSendOutcome::parse_tx(response?.await?) })
+    }
+
+    fn confirm_transaction(&self, signature: Base58TxSignature) -> smol::Task<PoseidonResult<()>> {
+        let rpc = self.clone();
+
+        smol::spawn(async move {
+            GetSignatureStatuses::confirm_with(
+                &rpc,
+                &signature,
+                rpc.commitment,
+                Duration::from_millis(500),
+                120,
+            )
+            .await?;
+
+            Ok(())
+        })
+    }
+}
+
+/// The addresses of a deployed program and the signatures of the
+/// transactions sent to deploy it
+#[derive(Debug, Clone)]
+pub struct ProgramDeployment {
+    pub program_address: PublicKey,
+    pub programdata_address: PdaPublicKey,
+    pub buffer_address: PublicKey,
+    pub signatures: Vec<Base58TxSignature>,
+}
+
+/// ### Deploy a program with the BPF Loader Upgradeable
+/// The program ELF is written to the buffer with `Write` transactions that are
+/// sent concurrently, then the program is deployed from the buffer.
+/// The `payer` pays for all the accounts and becomes the upgrade authority.
+///
+/// ```no_run
+/// # use ed25519_dalek::Keypair;
+/// # use poseidon_client::*;
+/// # async fn example(payer: Keypair, program_keypair: Keypair, buffer_keypair: Keypair) -> PoseidonResult<()> {
+/// let program_elf = std::fs::read("target/deploy/my_program.so")?;
+/// let deployment = ProgramDeployer::new(RpcClient::new(), &payer, &program_keypair, &buffer_keypair)
+///     .add_program_data(program_elf)
+///     .add_concurrency(16)
+///     .deploy()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ProgramDeployer<'k, S: TransactionSender> {
+    sender: S,
+    payer: &'k Keypair,
+    program: &'k Keypair,
+    buffer: &'k Keypair,
+    program_data: Vec<u8>,
+    max_data_len: Option<usize>,
+    concurrency: usize,
+}
+
+impl<'k, S: TransactionSender> ProgramDeployer<'k, S> {
+    pub fn new(sender: S, payer: &'k Keypair, program: &'k Keypair, buffer: &'k Keypair) -> Self {
+        ProgramDeployer {
+            sender,
+            payer,
+            program,
+            buffer,
+            program_data: Vec::default(),
+            max_data_len: Option::default(),
+            concurrency: 8,
+        }
+    }
+
+    pub fn add_program_data(&mut self, program_data: Vec<u8>) -> &mut Self {
+        self.program_data = program_data;
+
+        self
+    }
+
+    /// The maximum size the program can be upgraded to.
+    /// Defaults to twice the size of the program data.
+    pub fn add_max_data_len(&mut self, max_data_len: usize) -> &mut Self {
+        self.max_data_len = Some(max_data_len);
+
+        self
+    }
+
+    /// The number of `Write` transactions in flight at the same time
+    pub fn add_concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+
+        self
+    }
+
+    pub async fn deploy(&self) -> PoseidonResult<ProgramDeployment> {
+        let payer = self.payer.public.to_bytes();
+        let buffer_address = self.buffer.public.to_bytes();
+        let program_address = self.program.public.to_bytes();
+        let mut signatures = Vec::<Base58TxSignature>::default();
+
+        let buffer_lamports = self
+            .sender
+            .minimum_balance_for_rent_exemption(BUFFER_METADATA_SIZE + self.program_data.len())
+            .await?;
+        let create_buffer = BpfLoaderUpgradeable::create_buffer(
+            payer,
+            buffer_address,
+            payer,
+            buffer_lamports,
+            self.program_data.len(),
+        )?;
+        let blockhash = self.sender.latest_blockhash().await?;
+        let transaction = self.transaction(create_buffer, blockhash, &[self.payer, self.buffer])?;
+        signatures.push(self.send_and_confirm(&transaction).await?);
+
+        let chunk_size =
+            BpfLoaderUpgradeable::calculate_max_chunk_size(payer, buffer_address, payer)?;
+        let writes = BpfLoaderUpgradeable::write_chunks(
+            buffer_address,
+            payer,
+            &self.program_data,
+            chunk_size,
+        )?;
+
+        for batch in writes.chunks(self.concurrency) {
+            // Refresh the blockhash per batch since large programs take
+            // longer to write than a blockhash remains valid
+            let blockhash = self.sender.latest_blockhash().await?;
+
+            let sends = batch
+                .iter()
+                .map(|write| {
+                    let transaction =
+                        self.transaction(vec![write.clone()], blockhash, &[self.payer])?;
+
+                    Ok(self.sender.send_transaction(&transaction))
+                })
+                .collect::<PoseidonResult<Vec<smol::Task<PoseidonResult<TxSendOutcome>>>>>()?;
+
+            let mut batch_signatures = Vec::with_capacity(sends.len());
+            for send in sends {
+                batch_signatures.push(ProgramDeployer::<S>::signature(send.await?)?);
+            }
+
+            let confirmations = batch_signatures
+                .iter()
+                .map(|signature| self.sender.confirm_transaction(signature.to_owned()))
+                .collect::<Vec<smol::Task<PoseidonResult<()>>>>();
+            for confirmation in confirmations {
+                confirmation.await?;
+            }
+
+            signatures.extend(batch_signatures);
+        }
+
+        let program_lamports = self
+            .sender
+            .minimum_balance_for_rent_exemption(PROGRAM_ACCOUNT_SIZE)
+            .await?;
+        let deploy = BpfLoaderUpgradeable::deploy_with_max_program_len(
+            payer,
+            program_address,
+            buffer_address,
+            payer,
+            program_lamports,
+            self.max_data_len.unwrap_or(self.program_data.len() * 2),
+        )?;
+        let blockhash = self.sender.latest_blockhash().await?;
+        let transaction = self.transaction(deploy, blockhash, &[self.payer, self.program])?;
+        signatures.push(self.send_and_confirm(&transaction).await?);
+
+        Ok(ProgramDeployment {
+            program_address,
+            programdata_address: BpfLoaderUpgradeable::programdata_address(program_address)?,
+            buffer_address,
+            signatures,
+        })
+    }
+
+    fn transaction(
+        &self,
+        instructions: Vec<Instruction>,
+        blockhash: RecentBlockHash,
+        signers: &[&Keypair],
+    ) -> PoseidonResult<Transaction> {
+        let mut message_builder = MessageBuilder::new();
+        instructions.into_iter().for_each(|instruction| {
+            message_builder.add_instruction(instruction);
+        });
+        message_builder
+            .add_payer(self.payer.public.to_bytes())
+            .build();
+
+        let mut message = Message::new();
+        message.build(message_builder)?;
+        message.add_recent_blockhash(blockhash);
+
+        let mut transaction = Transaction::new(message);
+        transaction.sign(signers)?;
+
+        Ok(transaction)
+    }
+
+    async fn send_and_confirm(
+        &self,
+        transaction: &Transaction,
+    ) -> PoseidonResult<Base58TxSignature> {
+        let outcome = self.sender.send_transaction(transaction).await?;
+        let signature = ProgramDeployer::<S>::signature(outcome)?;
+        self.sender
+            .confirm_transaction(signature.to_owned())
+            .await?;
+
+        Ok(signature)
+    }
+
+    fn signature(outcome: TxSendOutcome) -> PoseidonResult<Base58TxSignature> {
+        match outcome {
+            TxSendOutcome::Success(response) => Ok(response.result),
+            TxSendOutcome::Failure(error) => {
+                Err(PoseidonError::TransactionRejected(Box::new(error)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use crate::{
+        test_utils::{keypair, mock_rpc_server},
+        *,
+    };
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct MockSender {
+        transactions: Arc<Mutex<Vec<Transaction>>>,
+    }
+
+    impl TransactionSender for MockSender {
+        fn latest_blockhash(&self) -> smol::Task<PoseidonResult<RecentBlockHash>> {
+            smol::spawn(async { Ok([7u8; 32]) })
+        }
+
+        fn minimum_balance_for_rent_exemption(
+            &self,
+            data_len: usize,
+        ) -> smol::Task<PoseidonResult<u64>> {
+            smol::spawn(async move { Ok(data_len as u64 * 6_960) })
+        }
+
+        fn send_transaction(
+            &self,
+            transaction: &Transaction,
+        ) -> smol::Task<PoseidonResult<TxSendOutcome>> {
+            let signature = bs58::encode(&transaction.signatures[0]).into_string();
+            self.transactions.lock().unwrap().push(transaction.clone());
+
+            smol::spawn(async move {
+                Ok(TxSendOutcome::Success(SendTxResponse {
+                    jsonrpc: "2.0".to_owned(),
+                    id: 1,
+                    result: signature,
+                }))
+            })
+        }
+
+        fn confirm_transaction(
+            &self,
+            _signature: Base58TxSignature,
+        ) -> smol::Task<PoseidonResult<()>> {
+            smol::spawn(async { Ok(()) })
+        }
+    }
+
+    #[test]
+    fn deploy_against_mock_rpc() {
+        let payer = keypair(1);
+        let program = keypair(2);
+        let buffer = keypair(3);
+        let program_elf = (0..5_000u32).map(|byte| byte as u8).collect::<Vec<u8>>();
+
+        let sender = MockSender::default();
+        let deployment = smol::block_on(async {
+            ProgramDeployer::new(sender.clone(), &payer, &program, &buffer)
+                .add_program_data(program_elf.clone())
+                .add_concurrency(2)
+                .deploy()
+                .await
+                .unwrap()
+        });
+
+        let transactions = sender.transactions.lock().unwrap();
+        assert_eq!(transactions.len(), deployment.signatures.len());
+        assert!(transactions
+            .iter()
+            .all(|transaction| { transaction.to_bytes().unwrap().len() <= PACKET_DATA_SIZE }));
+
+        let mut written = vec![0u8; program_elf.len()];
+        transactions[1..transactions.len() - 1]
+            .iter()
+            .for_each(|transaction| {
+                let instruction = &transaction.message.instructions[0];
+                match bincode::deserialize(&instruction.data).unwrap() {
+                    UpgradeableLoaderInstruction::Write { offset, bytes } => {
+                        let offset = offset as usize;
+                        written[offset..offset + bytes.len()].copy_from_slice(&bytes);
+                    }
+                    _ => panic!("Expected a `Write` instruction"),
+                }
+            });
+        assert_eq!(written, program_elf);
+
+        let deploy = transactions.last().unwrap();
+        assert_eq!(deploy.signatures.len(), 2);
+        assert_eq!(
+            deployment.programdata_address,
+            BpfLoaderUpgradeable::programdata_address(program.public.to_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn rpc_client_sends_to_its_url() {
+        let (url, server) = mock_rpc_server(4, |request| {
+            let context = json!({ "apiVersion": "1.16.0", "slot": 1 });
+            Ok(match request["method"].as_str().unwrap() {
+                "getLatestBlockhash" => json!({
+                    "context": context,
+                    "value": {
+                        "blockhash": bs58::encode([7u8; 32]).into_string(),
+                        "lastValidBlockHeight": 100,
+                    },
+                }),
+                "getMinimumBalanceForRentExemption" => json!(1_000),
+                "sendTransaction" => json!("signature"),
+                "getSignatureStatuses" => json!({
+                    "context": context,
+                    "value": [{
+                        "slot": 1,
+                        "confirmations": null,
+                        "err": null,
+                        "confirmationStatus": "finalized",
+                    }],
+                }),
+                method => panic!("Unexpected method {}", method),
+            })
+        });
+        let mut rpc = RpcClient::new();
+        rpc.add_url(&url);
+
+        smol::block_on(async {
+            assert_eq!(rpc.latest_blockhash().await.unwrap(), [7u8; 32]);
+            assert_eq!(
+                rpc.minimum_balance_for_rent_exemption(10).await.unwrap(),
+                1_000
+            );
+
+            let transaction = Transaction::new(Message::new());
+            assert!(matches!(
+                rpc.send_transaction(&transaction).await.unwrap(),
+                TxSendOutcome::Success(response) if response.result == "signature"
+            ));
+
+            rpc.confirm_transaction("signature".to_owned())
+                .await
+                .unwrap();
+        });
+
+        let requests = server.join().unwrap();
+        assert!(requests.iter().all(|(path, _)| path == "/rpc"));
+        assert_eq!(
+            requests
+                .iter()
+                .map(|(_, method)| method.as_str())
+                .collect::<Vec<&str>>(),
+            vec![
+                "getLatestBlockhash",
+                "getMinimumBalanceForRentExemption",
+                "sendTransaction",
+                "getSignatureStatuses",
+            ]
+        );
+    }
+}
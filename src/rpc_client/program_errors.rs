@@ -0,0 +1,492 @@
+use crate::{
+    AnchorErrors, ErrorData, InstructionError, Message, ProgramLogs, PublicKey,
+    RpcConfirmedTransaction, RpcMeta, TransactionError, TxSendOutcome, Utilities,
+};
+use core::{any::Any, fmt};
+use std::collections::HashMap;
+
+/// The error enum of a program, decoded from the code of `InstructionError::Custom`.
+/// Implement it for the error enum of your program or derive it with the `derive` feature.
+/// ```no_run
+/// # use poseidon_client::*;
+/// #[derive(Debug)]
+/// enum LendingError {
+///     InsufficientCollateral,
+///     StaleOracle,
+/// }
+///
+/// impl ProgramErrorDecoder for LendingError {
+///     fn from_code(code: u32) -> Option<Self> {
+///         match code {
+///             0 => Some(LendingError::InsufficientCollateral),
+///             1 => Some(LendingError::StaleOracle),
+///             _ => None,
+///         }
+///     }
+///
+///     fn name(&self) -> &'static str {
+///         match self {
+///             LendingError::InsufficientCollateral => "InsufficientCollateral",
+///             LendingError::StaleOracle => "StaleOracle",
+///         }
+///     }
+///
+///     fn message(&self) -> String {
+///         match self {
+///             LendingError::InsufficientCollateral => "Not enough collateral".to_owned(),
+///             LendingError::StaleOracle => "The oracle price is stale".to_owned(),
+///         }
+///     }
+/// }
+/// ```
+pub trait ProgramErrorDecoder: Sized + Send + Sync + 'static {
+    /// `None` if the program does not define the code
+    fn from_code(code: u32) -> Option<Self>;
+
+    fn name(&self) -> &'static str;
+
+    fn message(&self) -> String;
+}
+
+type DeserializedError = (String, String, Box<dyn Any + Send + Sync>);
+
+type ErrorDeserializer = Box<dyn Fn(u32) -> Option<DeserializedError> + Send + Sync>;
+
+fn deserialize_error<T: ProgramErrorDecoder>(code: u32) -> Option<DeserializedError> {
+    let error = T::from_code(code)?;
+
+    Some((error.name().to_owned(), error.message(), Box::new(error)))
+}
+
+/// A custom program error decoded by the `ProgramErrorRegistry`.
+/// The error is a `ProgramErrorDecoder` enum or, for errors of `AnchorErrors`,
+/// an `AnchorErrorDefinition`.
+pub struct DecodedProgramError {
+    /// The program that returned the error
+    pub program_id: PublicKey,
+    pub code: u32,
+    pub name: String,
+    pub message: String,
+    error: Box<dyn Any + Send + Sync>,
+}
+
+impl DecodedProgramError {
+    pub fn is<T: Any>(&self) -> bool {
+        self.error.is::<T>()
+    }
+
+    /// The error if it is a `T`
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.error.downcast_ref::<T>()
+    }
+
+    pub fn downcast<T: Any>(self) -> Result<T, Self> {
+        match self.error.downcast::<T>() {
+            Ok(error) => Ok(*error),
+            Err(error) => Err(DecodedProgramError {
+                program_id: self.program_id,
+                code: self.code,
+                name: self.name,
+                message: self.message,
+                error,
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for DecodedProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodedProgramError")
+            .field("program_id", &bs58::encode(&self.program_id).into_string())
+            .field("code", &self.code)
+            .field("name", &self.name)
+            .field("message", &self.message)
+            .finish()
+    }
+}
+
+impl fmt::Display for DecodedProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.name, self.code, self.message)
+    }
+}
+
+/// A custom program error returned by an instruction of a transaction
+#[derive(Debug)]
+pub struct ProgramTransactionError {
+    /// The index of the instruction that failed
+    pub instruction_index: u8,
+    pub error: DecodedProgramError,
+}
+
+/// ### Decode `InstructionError::Custom` codes into the errors of programs
+/// The errors of a program are a `ProgramErrorDecoder` enum or the `AnchorErrors`
+/// of its IDL. The program that returned the error is the deepest failed invocation
+/// in the logs of the transaction, from the preflight simulation of `sendTransaction`
+/// or the metadata of a confirmed transaction. Registering errors for a program that
+/// already has them replaces them.
+/// ```no_run
+/// # use poseidon_client::*;
+/// # #[derive(Debug)]
+/// # enum LendingError {}
+/// # impl ProgramErrorDecoder for LendingError {
+/// #     fn from_code(code: u32) -> Option<Self> { None }
+/// #     fn name(&self) -> &'static str { "" }
+/// #     fn message(&self) -> String { String::new() }
+/// # }
+/// # fn example(outcome: TxSendOutcome, idl: AnchorIdl) -> PoseidonResult<()> {
+/// # const LENDING_PROGRAM_ID: PublicKey = [0; 32];
+/// let mut registry = ProgramErrorRegistry::new();
+/// registry
+///     .add_decoder::<LendingError>(LENDING_PROGRAM_ID)
+///     .add_anchor_errors(idl.program_id()?, idl.anchor_errors());
+///
+/// if let Some(failed) = outcome.program_error(&registry) {
+///     println!("Instruction {} failed: {}", failed.instruction_index, failed.error);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ProgramErrorRegistry {
+    decoders: HashMap<PublicKey, ErrorDeserializer>,
+}
+
+impl ProgramErrorRegistry {
+    pub fn new() -> Self {
+        ProgramErrorRegistry::default()
+    }
+
+    pub fn add_decoder<T: ProgramErrorDecoder>(&mut self, program_id: PublicKey) -> &mut Self {
+        self.decoders
+            .insert(program_id, Box::new(deserialize_error::<T>));
+
+        self
+    }
+
+    /// Decode the errors of an Anchor program, like the errors of its IDL
+    pub fn add_anchor_errors(&mut self, program_id: PublicKey, errors: AnchorErrors) -> &mut Self {
+        self.decoders.insert(
+            program_id,
+            Box::new(move |code| {
+                let error = errors.get(code)?.clone();
+
+                Some((
+                    error.name.clone(),
+                    error.message.clone(),
+                    Box::new(error) as Box<dyn Any + Send + Sync>,
+                ))
+            }),
+        );
+
+        self
+    }
+
+    pub fn remove_decoder(&mut self, program_id: PublicKey) -> &mut Self {
+        self.decoders.remove(&program_id);
+
+        self
+    }
+
+    /// `None` if no error enum is registered for the program or it does not define the code
+    pub fn decode(&self, program_id: PublicKey, code: u32) -> Option<DecodedProgramError> {
+        let (name, message, error) = (self.decoders.get(&program_id)?)(code)?;
+
+        Some(DecodedProgramError {
+            program_id,
+            code,
+            name,
+            message,
+            error,
+        })
+    }
+
+    /// Decode the error of a transaction when the program that failed is already known
+    pub fn decode_transaction_error(
+        &self,
+        error: &TransactionError,
+        program_id: PublicKey,
+    ) -> Option<ProgramTransactionError> {
+        match error {
+            TransactionError::InstructionError(
+                instruction_index,
+                InstructionError::Custom(code),
+            ) => self
+                .decode(program_id, *code)
+                .map(|error| ProgramTransactionError {
+                    instruction_index: *instruction_index,
+                    error,
+                }),
+            _ => None,
+        }
+    }
+
+    /// Decode the error of a transaction, finding the program that failed in its logs.
+    /// When the logs are truncated before the failure the program of the failed
+    /// instruction in `message` is used instead.
+    pub fn decode_with_logs(
+        &self,
+        error: &TransactionError,
+        logs: &ProgramLogs,
+        message: Option<&Message>,
+    ) -> Option<ProgramTransactionError> {
+        let program_id = match logs.failed_invocation() {
+            Some(invocation) => Utilities::base58_to_u32_array(&invocation.program_id).ok()?,
+            None => ProgramErrorRegistry::failed_instruction_program(error, message?)?,
+        };
+
+        self.decode_transaction_error(error, program_id)
+    }
+
+    // The program of the top level instruction that failed
+    fn failed_instruction_program(
+        error: &TransactionError,
+        message: &Message,
+    ) -> Option<PublicKey> {
+        match error {
+            TransactionError::InstructionError(instruction_index, _) => {
+                let instruction = message.instructions.get(*instruction_index as usize)?;
+
+                message
+                    .account_keys
+                    .get(instruction.program_id_index as usize)
+                    .copied()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ErrorData {
+    /// The custom program error that failed the preflight simulation
+    pub fn program_error(
+        &self,
+        registry: &ProgramErrorRegistry,
+    ) -> Option<ProgramTransactionError> {
+        registry.decode_with_logs(&self.err, &self.program_logs(), None)
+    }
+}
+
+impl RpcMeta {
+    /// `None` if the transaction succeeded or the node did not record the log messages
+    pub fn program_error(
+        &self,
+        registry: &ProgramErrorRegistry,
+    ) -> Option<ProgramTransactionError> {
+        registry.decode_with_logs(self.err.as_ref()?, &self.program_logs()?, None)
+    }
+}
+
+impl RpcConfirmedTransaction {
+    /// Like `RpcMeta::program_error`, falling back to the program of the failed instruction
+    /// when the logs are truncated or missing. The fallback needs a binary encoded legacy transaction.
+    pub fn program_error(
+        &self,
+        registry: &ProgramErrorRegistry,
+    ) -> Option<ProgramTransactionError> {
+        let meta = self.meta.as_ref()?;
+        let message = self.decode().ok().map(|transaction| transaction.message);
+
+        registry.decode_with_logs(
+            meta.err.as_ref()?,
+            &meta.program_logs().unwrap_or_default(),
+            message.as_ref(),
+        )
+    }
+}
+
+impl TxSendOutcome {
+    /// `None` for `TxSendOutcome::Success`
+    pub fn program_error(
+        &self,
+        registry: &ProgramErrorRegistry,
+    ) -> Option<ProgramTransactionError> {
+        match self {
+            TxSendOutcome::Success(_) => None,
+            TxSendOutcome::Failure(failure) => failure.error.data.program_error(registry),
+        }
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::{AnchorErrorDefinition, CompiledInstruction, SYSTEM_PROGRAM_ID};
+
+    const LENDING_PROGRAM_ID: &str = "SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8";
+
+    #[derive(Debug, PartialEq)]
+    enum LendingError {
+        InsufficientCollateral,
+        StaleOracle,
+    }
+
+    impl ProgramErrorDecoder for LendingError {
+        fn from_code(code: u32) -> Option<Self> {
+            match code {
+                6000 => Some(LendingError::InsufficientCollateral),
+                6001 => Some(LendingError::StaleOracle),
+                _ => None,
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            match self {
+                LendingError::InsufficientCollateral => "InsufficientCollateral",
+                LendingError::StaleOracle => "StaleOracle",
+            }
+        }
+
+        fn message(&self) -> String {
+            match self {
+                LendingError::InsufficientCollateral => "Not enough collateral".to_owned(),
+                LendingError::StaleOracle => "The oracle price is stale".to_owned(),
+            }
+        }
+    }
+
+    fn logs(code: &str) -> Vec<String> {
+        vec![
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]".to_owned(),
+            "Program ComputeBudget111111111111111111111111111111 success".to_owned(),
+            "Program 11111111111111111111111111111111 invoke [1]".to_owned(),
+            format!("Program {} invoke [2]", LENDING_PROGRAM_ID),
+            format!(
+                "Program {} failed: custom program error: {}",
+                LENDING_PROGRAM_ID, code
+            ),
+            format!(
+                "Program 11111111111111111111111111111111 failed: custom program error: {}",
+                code
+            ),
+        ]
+    }
+
+    #[test]
+    fn decode_program_errors() {
+        let lending_program_id = Utilities::base58_to_u32_array(LENDING_PROGRAM_ID).unwrap();
+
+        let mut registry = ProgramErrorRegistry::new();
+        registry.add_decoder::<LendingError>(lending_program_id);
+
+        let decoded = registry.decode(lending_program_id, 6001).unwrap();
+        assert_eq!(decoded.name, "StaleOracle");
+        assert_eq!(
+            decoded.to_string(),
+            "StaleOracle (6001): The oracle price is stale"
+        );
+        assert!(registry.decode(lending_program_id, 1).is_none());
+        assert!(registry.decode([0; 32], 6001).is_none());
+
+        let error_data = ErrorData {
+            accounts: None,
+            err: TransactionError::InstructionError(1, InstructionError::Custom(6000)),
+            logs: logs("0x1770"),
+            units_consumed: 2_000,
+        };
+        let failed = error_data.program_error(&registry).unwrap();
+        assert_eq!(failed.instruction_index, 1);
+        assert_eq!(failed.error.program_id, lending_program_id);
+        assert!(failed.error.is::<LendingError>());
+        assert_eq!(
+            failed.error.downcast::<LendingError>().unwrap(),
+            LendingError::InsufficientCollateral
+        );
+
+        let not_custom = ErrorData {
+            err: TransactionError::InstructionError(1, InstructionError::InvalidAccountData),
+            ..error_data.clone()
+        };
+        assert!(not_custom.program_error(&registry).is_none());
+
+        registry.remove_decoder(lending_program_id);
+        assert!(error_data.program_error(&registry).is_none());
+    }
+
+    #[test]
+    fn decode_anchor_errors() {
+        let lending_program_id = Utilities::base58_to_u32_array(LENDING_PROGRAM_ID).unwrap();
+
+        let mut errors = AnchorErrors::new();
+        errors
+            .add_error("SlippageExceeded", "Slippage tolerance exceeded")
+            .add_error("PoolClosed", "The pool is closed");
+
+        let mut registry = ProgramErrorRegistry::new();
+        registry.add_anchor_errors(lending_program_id, errors);
+
+        let error_data = ErrorData {
+            accounts: None,
+            err: TransactionError::InstructionError(1, InstructionError::Custom(6001)),
+            logs: logs("0x1771"),
+            units_consumed: 2_000,
+        };
+        let failed = error_data.program_error(&registry).unwrap();
+        assert_eq!(failed.error.name, "PoolClosed");
+        assert_eq!(
+            failed
+                .error
+                .downcast_ref::<AnchorErrorDefinition>()
+                .unwrap()
+                .code,
+            6001
+        );
+        assert!(!failed.error.is::<LendingError>());
+        assert!(format!("{:?}", failed.error).contains(LENDING_PROGRAM_ID));
+        assert!(registry.decode(lending_program_id, 6002).is_none());
+    }
+
+    #[test]
+    fn decode_with_truncated_logs() {
+        let lending_program_id = Utilities::base58_to_u32_array(LENDING_PROGRAM_ID).unwrap();
+
+        let mut registry = ProgramErrorRegistry::new();
+        registry.add_decoder::<LendingError>(lending_program_id);
+
+        let mut message = Message::new();
+        message.account_keys = vec![[1; 32], SYSTEM_PROGRAM_ID, lending_program_id];
+        message.instructions = [1, 2]
+            .into_iter()
+            .map(|program_id_index| CompiledInstruction {
+                program_id_index,
+                accounts: vec![0],
+                data: Vec::default(),
+            })
+            .collect();
+
+        let error = TransactionError::InstructionError(1, InstructionError::Custom(6001));
+        let truncated = ProgramLogs::parse(&[
+            "Program 11111111111111111111111111111111 invoke [1]".to_owned(),
+            "Program 11111111111111111111111111111111 success".to_owned(),
+            format!("Program {} invoke [1]", LENDING_PROGRAM_ID),
+            "Log truncated".to_owned(),
+        ]);
+        assert!(truncated.failed_invocation().is_none());
+        assert!(registry
+            .decode_with_logs(&error, &truncated, None)
+            .is_none());
+
+        let failed = registry
+            .decode_with_logs(&error, &truncated, Some(&message))
+            .unwrap();
+        assert_eq!(failed.instruction_index, 1);
+        assert_eq!(failed.error.program_id, lending_program_id);
+        assert_eq!(
+            failed.error.downcast::<LendingError>().unwrap(),
+            LendingError::StaleOracle
+        );
+
+        // The failed invocation in the logs wins over the top level instruction
+        let error = TransactionError::InstructionError(0, InstructionError::Custom(6001));
+        let failed = registry
+            .decode_with_logs(&error, &ProgramLogs::parse(&logs("0x1771")), Some(&message))
+            .unwrap();
+        assert_eq!(failed.error.program_id, lending_program_id);
+
+        // An instruction the message does not have
+        let error = TransactionError::InstructionError(5, InstructionError::Custom(6001));
+        assert!(registry
+            .decode_with_logs(&error, &ProgramLogs::default(), Some(&message))
+            .is_none());
+    }
+}
@@ -0,0 +1,368 @@
+use crate::{Base58PublicKey, ErrorData, RpcMeta};
+
+/// The compute units consumed by an invocation and the limit it ran with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComputeUnits {
+    pub consumed: u64,
+    pub limit: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvocationResult {
+    Success,
+    /// The error logged by the runtime, like `custom program error: 0x1`
+    Failed(String),
+}
+
+/// A program invocation and the invocations it made through CPI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramInvocation {
+    pub program_id: Base58PublicKey,
+    /// The invocation depth starting from `1` for the instructions of the transaction
+    pub depth: usize,
+    /// The messages of `Program log:` lines
+    pub logs: Vec<String>,
+    /// The base64 decoded fields of each `Program data:` line
+    pub data: Vec<Vec<Vec<u8>>>,
+    /// The data set with `set_return_data`
+    pub return_data: Option<Vec<u8>>,
+    pub compute_units: Option<ComputeUnits>,
+    /// `None` if the logs end before the invocation completes
+    pub result: Option<InvocationResult>,
+    pub invocations: Vec<ProgramInvocation>,
+    /// Lines logged during the invocation that are not recognized
+    pub other: Vec<String>,
+}
+
+impl ProgramInvocation {
+    fn new(program_id: &str, depth: usize) -> Self {
+        ProgramInvocation {
+            program_id: program_id.to_owned(),
+            depth,
+            logs: Vec::default(),
+            data: Vec::default(),
+            return_data: Option::default(),
+            compute_units: Option::default(),
+            result: Option::default(),
+            invocations: Vec::default(),
+            other: Vec::default(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.result == Some(InvocationResult::Success)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self.result, Some(InvocationResult::Failed(_)))
+    }
+
+    /// The deepest failed invocation, which is the one that caused the failure
+    pub fn failed_invocation(&self) -> Option<&ProgramInvocation> {
+        self.invocations
+            .iter()
+            .find_map(|invocation| invocation.failed_invocation())
+            .or_else(|| self.is_failed().then_some(self))
+    }
+
+    /// This invocation followed by its nested invocations, depth first
+    pub fn iter(&self) -> Vec<&ProgramInvocation> {
+        let mut invocations = vec![self];
+        self.invocations
+            .iter()
+            .for_each(|invocation| invocations.extend(invocation.iter()));
+
+        invocations
+    }
+}
+
+/// ### The invocation tree of a transaction parsed from its program logs
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn example(error_data: ErrorData) {
+/// let logs = ProgramLogs::parse(&error_data.logs);
+/// if let Some(failed) = logs.failed_invocation() {
+///     println!("{} failed at depth {}: {:?}", failed.program_id, failed.depth, failed.result);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProgramLogs {
+    /// The invocations of the instructions of the transaction
+    pub invocations: Vec<ProgramInvocation>,
+    /// The runtime stopped recording logs after reaching the log limit
+    pub truncated: bool,
+    /// Lines logged outside of any invocation
+    pub other: Vec<String>,
+}
+
+impl ProgramLogs {
+    pub fn parse<S: AsRef<str>>(logs: &[S]) -> Self {
+        let mut program_logs = ProgramLogs::default();
+        let mut stack: Vec<ProgramInvocation> = Vec::new();
+
+        for line in logs {
+            let line = line.as_ref();
+
+            if line == "Log truncated" {
+                program_logs.truncated = true;
+                break;
+            }
+
+            // Messages logged by programs can look like the lines of the runtime
+            if !is_program_output(line) {
+                if let Some(program_id) = invoked_program(line) {
+                    stack.push(ProgramInvocation::new(program_id, stack.len() + 1));
+                    continue;
+                }
+
+                if let Some((program_id, result)) = invocation_result(line) {
+                    let current = stack
+                        .last()
+                        .map(|invocation| invocation.program_id.as_str());
+
+                    if current == Some(program_id) {
+                        if let Some(mut invocation) = stack.pop() {
+                            invocation.result = Some(result);
+                            program_logs.attach(&mut stack, invocation);
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            match stack.last_mut() {
+                Some(invocation) => ProgramLogs::record(invocation, line),
+                None => program_logs.other.push(line.to_owned()),
+            }
+        }
+
+        // Invocations that had not completed when the logs ended
+        while let Some(invocation) = stack.pop() {
+            program_logs.attach(&mut stack, invocation);
+        }
+
+        program_logs
+    }
+
+    /// The deepest failed invocation, which is the one that caused the transaction to fail
+    pub fn failed_invocation(&self) -> Option<&ProgramInvocation> {
+        self.invocations
+            .iter()
+            .find_map(|invocation| invocation.failed_invocation())
+    }
+
+    /// Every invocation, depth first
+    pub fn iter(&self) -> Vec<&ProgramInvocation> {
+        self.invocations
+            .iter()
+            .flat_map(|invocation| invocation.iter())
+            .collect()
+    }
+
+    /// The compute units consumed by the instructions of the transaction.
+    /// Nested invocations are already included in the units of their parent.
+    pub fn compute_units_consumed(&self) -> u64 {
+        self.invocations
+            .iter()
+            .filter_map(|invocation| invocation.compute_units)
+            .map(|compute_units| compute_units.consumed)
+            .sum()
+    }
+
+    fn attach(&mut self, stack: &mut [ProgramInvocation], invocation: ProgramInvocation) {
+        match stack.last_mut() {
+            Some(parent) => parent.invocations.push(invocation),
+            None => self.invocations.push(invocation),
+        }
+    }
+
+    fn record(invocation: &mut ProgramInvocation, line: &str) {
+        if let Some(message) = line.strip_prefix("Program log: ") {
+            invocation.logs.push(message.to_owned());
+        } else if let Some(data) = line.strip_prefix("Program data: ") {
+            match data.split(' ').map(base64::decode).collect() {
+                Ok(fields) => invocation.data.push(fields),
+                Err(_) => invocation.other.push(line.to_owned()),
+            }
+        } else if let Some(return_data) = line.strip_prefix("Program return: ") {
+            match return_data
+                .split_once(' ')
+                .map(|(_, data)| base64::decode(data))
+            {
+                Some(Ok(data)) => invocation.return_data = Some(data),
+                _ => invocation.other.push(line.to_owned()),
+            }
+        } else if let Some(compute_units) = consumed_compute_units(line) {
+            invocation.compute_units = Some(compute_units);
+        } else {
+            invocation.other.push(line.to_owned());
+        }
+    }
+}
+
+// `Program log: `, `Program data: ` and `Program return: ` lines
+fn is_program_output(line: &str) -> bool {
+    ["Program log: ", "Program data: ", "Program return: "]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+}
+
+// `Program <id> invoke [<depth>]`
+fn invoked_program(line: &str) -> Option<&str> {
+    let (program_id, rest) = line.strip_prefix("Program ")?.split_once(' ')?;
+    rest.strip_prefix("invoke [")?.strip_suffix(']')?;
+
+    Some(program_id)
+}
+
+// `Program <id> success` or `Program <id> failed: <error>`
+fn invocation_result(line: &str) -> Option<(&str, InvocationResult)> {
+    let (program_id, rest) = line.strip_prefix("Program ")?.split_once(' ')?;
+
+    let result = if rest == "success" {
+        InvocationResult::Success
+    } else {
+        InvocationResult::Failed(rest.strip_prefix("failed: ")?.to_owned())
+    };
+
+    Some((program_id, result))
+}
+
+// `Program <id> consumed <consumed> of <limit> compute units`
+fn consumed_compute_units(line: &str) -> Option<ComputeUnits> {
+    let (_, rest) = line.strip_prefix("Program ")?.split_once(' ')?;
+    let (consumed, limit) = rest
+        .strip_prefix("consumed ")?
+        .strip_suffix(" compute units")?
+        .split_once(" of ")?;
+
+    Some(ComputeUnits {
+        consumed: consumed.parse().ok()?,
+        limit: limit.parse().ok()?,
+    })
+}
+
+impl ErrorData {
+    pub fn program_logs(&self) -> ProgramLogs {
+        ProgramLogs::parse(&self.logs)
+    }
+}
+
+impl RpcMeta {
+    /// `None` if the node did not record the log messages
+    pub fn program_logs(&self) -> Option<ProgramLogs> {
+        self.log_messages.as_deref().map(ProgramLogs::parse)
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+
+    const LOGS: &[&str] = &[
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+        "Program log: Instruction: Route",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+        "Program log: Instruction: Transfer",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 4645 of 180000 compute units",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+        "Program data: AQIDBA== BQY=",
+        "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [2]",
+        "Program log: Error: slippage tolerance exceeded",
+        "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc consumed 12000 of 175355 compute units",
+        "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc failed: custom program error: 0x1771",
+        "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 consumed 24645 of 199850 compute units",
+        "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 failed: custom program error: 0x1771",
+    ];
+
+    #[test]
+    fn invocation_tree() {
+        let logs = ProgramLogs::parse(LOGS);
+        assert!(!logs.truncated);
+        assert!(logs.other.is_empty());
+        assert_eq!(logs.invocations.len(), 2);
+        assert!(logs.invocations[0].is_success());
+        assert!(logs.invocations[0].compute_units.is_none());
+
+        let route = &logs.invocations[1];
+        assert_eq!(route.logs, vec!["Instruction: Route"]);
+        assert_eq!(route.data, vec![vec![vec![1, 2, 3, 4], vec![5, 6]]]);
+        assert_eq!(
+            route.compute_units,
+            Some(ComputeUnits {
+                consumed: 24645,
+                limit: 199850
+            })
+        );
+        assert_eq!(route.invocations.len(), 2);
+        assert_eq!(route.invocations[0].depth, 2);
+        assert!(route.invocations[0].is_success());
+
+        let failed = logs.failed_invocation().unwrap();
+        assert_eq!(
+            failed.program_id,
+            "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"
+        );
+        assert_eq!(
+            failed.result,
+            Some(InvocationResult::Failed(
+                "custom program error: 0x1771".to_owned()
+            ))
+        );
+        assert_eq!(failed.logs, vec!["Error: slippage tolerance exceeded"]);
+
+        assert_eq!(logs.iter().len(), 4);
+        assert_eq!(logs.compute_units_consumed(), 24645);
+    }
+
+    #[test]
+    fn truncated_logs() {
+        let logs = ProgramLogs::parse(&[
+            "Program 11111111111111111111111111111111 invoke [1]",
+            "Program return: 11111111111111111111111111111111 AQAAAA==",
+            "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr invoke [2]",
+            "Log truncated",
+            "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr success",
+        ]);
+
+        assert!(logs.truncated);
+        assert_eq!(logs.invocations.len(), 1);
+        assert_eq!(logs.invocations[0].return_data, Some(vec![1, 0, 0, 0]));
+        assert!(logs.invocations[0].result.is_none());
+        assert!(logs.invocations[0].invocations[0].result.is_none());
+        assert!(logs.failed_invocation().is_none());
+    }
+
+    #[test]
+    fn program_output_like_runtime_lines() {
+        let logs = ProgramLogs::parse(&[
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+            "Program log: success",
+            "Program log: invoke [1]",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 success",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 success",
+        ]);
+
+        assert!(logs.other.is_empty());
+        assert_eq!(logs.invocations.len(), 1);
+
+        let route = &logs.invocations[0];
+        assert!(route.is_success());
+        assert!(route.logs.is_empty());
+        assert_eq!(route.invocations.len(), 1);
+
+        let transfer = &route.invocations[0];
+        assert!(transfer.is_success());
+        assert!(transfer.invocations.is_empty());
+        assert_eq!(transfer.logs, vec!["success", "invoke [1]"]);
+        assert_eq!(
+            transfer.other,
+            vec!["Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 success"]
+        );
+    }
+}
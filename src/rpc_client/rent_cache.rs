@@ -0,0 +1,65 @@
+use crate::{GetSysvar, PoseidonResult, Rent};
+use std::sync::{Arc, OnceLock};
+
+/// ### Fetch the `Rent` sysvar once and reuse it
+/// The `Rent` is fetched the first time it is requested. Clones share the fetched
+/// `Rent`, so keep one cache per cluster and clone it where rent is computed.
+///
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example() -> PoseidonResult<()> {
+/// let rent_cache = RentCache::new();
+///
+/// let rent = rent_cache.get().await?;
+/// let lamports = rent.minimum_balance(STAKE_STATE_SIZE);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RentCache {
+    rent: Arc<OnceLock<Rent>>,
+}
+
+impl RentCache {
+    pub fn new() -> Self {
+        RentCache::default()
+    }
+
+    /// The cached `Rent`, fetching it if it was not fetched yet
+    pub async fn get(&self) -> PoseidonResult<Rent> {
+        if let Some(rent) = self.cached() {
+            return Ok(rent);
+        }
+
+        let rent = GetSysvar::<Rent>::process().await?;
+
+        Ok(*self.rent.get_or_init(|| rent))
+    }
+
+    /// The cached `Rent` without contacting the RPC
+    pub fn cached(&self) -> Option<Rent> {
+        self.rent.get().copied()
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+
+    #[test]
+    fn clones_share_the_rent() {
+        let cache = RentCache::new();
+        let shared = cache.clone();
+        assert!(cache.cached().is_none());
+
+        let rent = Rent {
+            lamports_per_byte_year: 1,
+            ..Rent::default()
+        };
+        cache.rent.set(rent).unwrap();
+
+        assert_eq!(shared.cached(), Some(rent));
+        assert_eq!(smol::block_on(shared.get()).unwrap(), rent);
+        assert!(RentCache::new().cached().is_none());
+    }
+}
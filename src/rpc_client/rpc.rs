@@ -1,26 +1,45 @@
-use crate::{Cluster, Commitment, PoseidonResult, RpcTxError, SendTxResponse, Transaction};
+use crate::{
+    Cluster, Commitment, PoseidonError, PoseidonResult, RpcResponseJsonError, RpcTxError,
+    SendTxResponse, Transaction,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use json::JsonValue;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RpcClient {
     cluster: Cluster,
+    url: Option<String>,
     headers: Vec<(String, String)>,
     body: JsonValue,
-    commitment: Commitment,
+    pub(crate) commitment: Commitment,
+}
+
+impl Default for RpcClient {
+    fn default() -> Self {
+        RpcClient::new()
+    }
 }
 
 impl RpcClient {
     pub fn new() -> Self {
         RpcClient {
             cluster: Cluster::default(),
+            url: Option::default(),
             headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
             body: JsonValue::Null,
             commitment: Commitment::Finalized,
         }
     }
 
+    /// Send the requests of this client to `url` instead of the URL of the `Cluster`.
+    /// The `process` methods of the RPC methods create their own client and are not affected.
+    pub fn add_url(&mut self, url: &str) -> &mut Self {
+        self.url = Some(url.to_owned());
+
+        self
+    }
+
     pub fn add_header(&mut self, key: &str, value: &str) -> &mut Self {
         self.headers.push((key.to_owned(), value.to_owned()));
 
@@ -63,8 +82,33 @@ impl RpcClient {
         Ok(self)
     }
 
+    /// Send `body` to the URL of this client with its headers
+    pub(crate) async fn request<T: serde::de::DeserializeOwned>(
+        &self,
+        body: JsonValue,
+    ) -> PoseidonResult<RpcResponse<T>> {
+        let mut rpc = self.clone();
+        rpc.common_methods(body);
+        let response = rpc.send().await?;
+        parse_response::<RpcResponse<T>>(response.as_str()?)
+    }
+
+    /// Send `body` to the URL of this client with its headers
+    pub(crate) async fn request_with_result<T: serde::de::DeserializeOwned>(
+        &self,
+        body: JsonValue,
+    ) -> PoseidonResult<RpcResponseWithResult<T>> {
+        let mut rpc = self.clone();
+        rpc.common_methods(body);
+        let response = rpc.send().await?;
+        parse_response::<RpcResponseWithResult<T>>(response.as_str()?)
+    }
+
     pub fn send(&self) -> smol::Task<PoseidonResult<minreq::Response>> {
-        let cluster_url = self.cluster.url();
+        let cluster_url = self
+            .url
+            .clone()
+            .unwrap_or_else(|| self.cluster.url().to_owned());
         let body = self.body.clone().to_string();
         let headers = self.headers.clone();
 
@@ -88,12 +132,12 @@ pub enum TxSendOutcome {
 
 impl TxSendOutcome {
     pub fn parse_tx(response: minreq::Response) -> PoseidonResult<TxSendOutcome> {
-        let first_response = serde_json::from_str::<SendTxResponse>(&response.as_str()?);
+        let first_response = serde_json::from_str::<SendTxResponse>(response.as_str()?);
 
         match first_response {
             Ok(value) => Ok(TxSendOutcome::Success(value)),
             Err(first_error) => {
-                let err_response = serde_json::from_str::<RpcTxError>(&response.as_str()?);
+                let err_response = serde_json::from_str::<RpcTxError>(response.as_str()?);
                 match err_response {
                     Ok(value) => Ok(TxSendOutcome::Failure(value)),
                     Err(_) => Err(first_error.into()),
@@ -147,22 +191,22 @@ pub struct Context {
 pub(crate) async fn request<T: serde::de::DeserializeOwned>(
     body: json::JsonValue,
 ) -> PoseidonResult<RpcResponse<T>> {
-    let mut rpc = RpcClient::new();
-    rpc.common_methods(body);
-    let response = rpc.send().await?;
-    let deser_response: RpcResponse<T> = serde_json::from_str(response.as_str()?)?;
-
-    Ok(deser_response)
+    RpcClient::new().request(body).await
 }
 
 pub(crate) async fn request_with_result<T: serde::de::DeserializeOwned>(
     body: json::JsonValue,
 ) -> PoseidonResult<RpcResponseWithResult<T>> {
-    let mut rpc = RpcClient::new();
-    rpc.common_methods(body);
-    let response = rpc.send().await?;
-    dbg!(&response.as_str()?);
-    let deser_response: RpcResponseWithResult<T> = serde_json::from_str(response.as_str()?)?;
+    RpcClient::new().request_with_result(body).await
+}
 
-    Ok(deser_response)
+/// Deserialize a JSON-RPC response, surfacing error responses as `PoseidonError::JsonRpc`
+pub(crate) fn parse_response<T: serde::de::DeserializeOwned>(response: &str) -> PoseidonResult<T> {
+    match serde_json::from_str::<T>(response) {
+        Ok(value) => Ok(value),
+        Err(first_error) => match serde_json::from_str::<RpcResponseJsonError>(response) {
+            Ok(error_response) => Err(PoseidonError::JsonRpc(error_response.error)),
+            Err(_) => Err(first_error.into()),
+        },
+    }
 }
@@ -1,8 +1,9 @@
 use crate::{
-    request, request_with_result, BorrowedBase58PublicKey, Commitment, PoseidonError,
-    PoseidonResult, PublicKey, RpcResponse, RpcResponseWithResult,
+    request, request_with_result, BorrowedBase58PublicKey, Commitment, Message, PoseidonError,
+    PoseidonResult, PublicKey, RpcResponse, RpcResponseWithResult, RpcResult, Sysvar,
 };
 use borsh::{BorshDeserialize, BorshSerialize};
+use core::marker::PhantomData;
 use serde::{Deserialize, Serialize};
 
 pub const LAMPORT: u64 = 1_000_000_000;
@@ -18,17 +19,20 @@ impl GetLatestBlockhash {
     pub async fn process(
         commitment: Commitment,
     ) -> PoseidonResult<RpcResponseWithResult<GetLatestBlockhash>> {
+        request_with_result::<GetLatestBlockhash>(GetLatestBlockhash::body(commitment)).await
+    }
+
+    pub(crate) fn body(commitment: Commitment) -> json::JsonValue {
         let commitment: &str = commitment.into();
-        let body: json::JsonValue = json::object! {
+
+        json::object! {
             jsonrpc: "2.0",
             id: 1u8,
             method: "getLatestBlockhash",
             params: json::array![json::object!{
                 commitment: commitment,
             }]
-        };
-
-        Ok(request_with_result::<GetLatestBlockhash>(body).await?)
+        }
     }
 
     pub fn get_hash(response: RpcResponseWithResult<GetLatestBlockhash>) -> GetLatestBlockhash {
@@ -55,24 +59,118 @@ impl GetLatestBlockhash {
     }
 }
 
+/// Check whether a blockhash can still be used in a transaction
+#[derive(Debug, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+pub struct IsBlockhashValid;
+
+impl IsBlockhashValid {
+    pub async fn process(
+        blockhash: &str,
+        commitment: Commitment,
+    ) -> PoseidonResult<RpcResponseWithResult<bool>> {
+        request_with_result::<bool>(IsBlockhashValid::body(blockhash, commitment)).await
+    }
+
+    pub(crate) fn body(blockhash: &str, commitment: Commitment) -> json::JsonValue {
+        let commitment: &str = commitment.into();
+
+        json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "isBlockhashValid",
+            params: json::array![
+                blockhash,
+                json::object!{
+                    commitment: commitment,
+                }
+            ]
+        }
+    }
+}
+
+/// Fetch the fee the cluster will charge to process a `Message`
+#[derive(Debug, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+pub struct GetFeeForMessage;
+
+impl GetFeeForMessage {
+    /// The fee in lamports, `None` if the blockhash of the `message` has expired
+    pub async fn process(
+        message: &Message,
+        commitment: Commitment,
+    ) -> PoseidonResult<RpcResponseWithResult<Option<u64>>> {
+        let commitment: &str = commitment.into();
+        let message = base64::encode(message.to_bytes()?);
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getFeeForMessage",
+            params: json::array![
+                message,
+                json::object!{
+                    commitment: commitment,
+                }
+            ]
+        };
+
+        request_with_result::<Option<u64>>(body).await
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFees {
     pub blockhash: String,
     pub fee_calculator: FeeCalculator,
     pub last_valid_block_height: u64,
+    /// Holds `last_valid_block_height`, like the `getFees` method did in its last releases,
+    /// since `getLatestBlockhash` does not report a last valid slot
+    #[deprecated(note = "use `last_valid_block_height` instead")]
     pub last_valid_slot: u64,
 }
 
 impl GetFees {
+    /// The deprecated `getFees` method is no longer served by newer RPC nodes so the
+    /// response is assembled from `getLatestBlockhash` and `getFeeForMessage`
     pub async fn process() -> PoseidonResult<RpcResponseWithResult<GetFees>> {
-        let body: json::JsonValue = json::object! {
-            jsonrpc: "2.0",
-            id: 1u8,
-            method: "getFees",
-        };
+        let latest = GetLatestBlockhash::process(Commitment::Finalized).await?;
+
+        // The fee of a message with a single signature and no instructions
+        // is the fee charged per signature
+        let mut message = Message::new();
+        message.header.num_required_signatures = 1;
+        message.account_keys = vec![[1u8; 32]];
+        message.add_recent_blockhash(latest.result.value.to_bytes()?);
 
-        Ok(request_with_result::<GetFees>(body).await?)
+        let fee = GetFeeForMessage::process(&message, Commitment::Finalized).await?;
+
+        GetFees::from_responses(latest, fee)
+    }
+
+    /// Assemble the response from the `getLatestBlockhash` response and the
+    /// `getFeeForMessage` response of a message with a single signature
+    pub fn from_responses(
+        latest: RpcResponseWithResult<GetLatestBlockhash>,
+        fee: RpcResponseWithResult<Option<u64>>,
+    ) -> PoseidonResult<RpcResponseWithResult<GetFees>> {
+        let lamports_per_signature = fee.result.value.ok_or(PoseidonError::BlockhashNotFound)?;
+        let last_valid_block_height = latest.result.value.last_valid_block_height;
+
+        #[allow(deprecated)]
+        Ok(RpcResponseWithResult {
+            jsonrpc: latest.jsonrpc,
+            id: latest.id,
+            result: RpcResult {
+                value: GetFees {
+                    blockhash: latest.result.value.blockhash,
+                    fee_calculator: FeeCalculator {
+                        lamports_per_signature,
+                    },
+                    last_valid_block_height,
+                    last_valid_slot: last_valid_block_height,
+                },
+                context: latest.result.context,
+            },
+        })
     }
 }
 
@@ -116,11 +214,13 @@ impl RequestAirdrop {
             params: json::array![public_key, lamports]
         };
 
-        Ok(request::<String>(body).await?)
+        request::<String>(body).await
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize, BorshSerialize,
+)]
 #[serde(rename_all = "camelCase")]
 pub struct FeeCalculator {
     pub lamports_per_signature: u64,
@@ -131,40 +231,35 @@ pub struct FeeCalculator {
 pub struct GetMinimumBalanceForRentExemption;
 
 impl GetMinimumBalanceForRentExemption {
+    /// Uses `core::mem::size_of::<T>()` which undercounts types holding a `String` or `Vec`,
+    /// use `Rent::minimum_balance_for_value()` to compute the balance from the Borsh serialized length
     pub async fn process<T>() -> PoseidonResult<RpcResponse<u64>> {
-        let size = core::mem::size_of::<T>() as u64;
+        let size = core::mem::size_of::<T>();
 
-        let body: json::JsonValue = json::object! {
-            jsonrpc: "2.0",
-            id: 1u8,
-            method: "getMinimumBalanceForRentExemption",
-            params: json::array![
-                size
-            ]
-        };
-
-        Ok(request::<u64>(body).await?)
+        request::<u64>(GetMinimumBalanceForRentExemption::body(size)).await
     }
     pub async fn process_precalculated(size: usize) -> PoseidonResult<RpcResponse<u64>> {
+        request::<u64>(GetMinimumBalanceForRentExemption::body(size)).await
+    }
+
+    pub(crate) fn body(size: usize) -> json::JsonValue {
         let size = size as u64;
 
-        let body: json::JsonValue = json::object! {
+        json::object! {
             jsonrpc: "2.0",
             id: 1u8,
             method: "getMinimumBalanceForRentExemption",
             params: json::array![
                 size
             ]
-        };
-
-        Ok(request::<u64>(body).await?)
+        }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetAccountInfo {
-    pub data: (String, String), // (PublicKey String, encoding)
+    pub data: (String, String), // (Encoded account data, encoding)
     pub executable: bool,
     pub lamports: u64,
     pub owner: String, // Base58 formatted PublicKey
@@ -182,11 +277,109 @@ impl GetAccountInfo {
             params: [
                 public_key,
                 {
-                    "encoding": "base58"
+                    "encoding": "base64"
                 }
             ]
         };
 
-        Ok(request_with_result::<GetAccountInfo>(body).await?)
+        request_with_result::<GetAccountInfo>(body).await
+    }
+
+    /// Decode the account data into bytes using the encoding returned by the RPC
+    pub fn decode_data(&self) -> PoseidonResult<Vec<u8>> {
+        match self.data.1.as_str() {
+            "base58" => Ok(bs58::decode(&self.data.0).into_vec()?),
+            "base64" => Ok(base64::decode(&self.data.0)?),
+            _ => Err(PoseidonError::UnsupportedAccountEncoding(
+                self.data.1.to_owned(),
+            )),
+        }
+    }
+}
+
+/// Fetch a sysvar account and decode it with the decoders of the `Sysvar` types
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example() -> PoseidonResult<()> {
+/// let clock = GetSysvar::<Clock>::process().await?;
+/// let rent = GetSysvar::<Rent>::process().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct GetSysvar<T: Sysvar>(PhantomData<T>);
+
+impl<T: Sysvar> GetSysvar<T> {
+    pub async fn process() -> PoseidonResult<T> {
+        let address = bs58::encode(&T::ADDRESS).into_string();
+        let account = GetAccountInfo::process(&address).await?.result.value;
+
+        T::from_bytes(&account.decode_data()?)
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::parse_response;
+
+    #[test]
+    fn fee_for_message_responses() {
+        let fee = parse_response::<RpcResponseWithResult<Option<u64>>>(include_str!(
+            "fixtures/get_fee_for_message.json"
+        ))
+        .unwrap();
+        assert_eq!(fee.result.context.slot, 284_316_285);
+        assert_eq!(fee.result.value, Some(5000));
+
+        let expired = parse_response::<RpcResponseWithResult<Option<u64>>>(include_str!(
+            "fixtures/get_fee_for_message_expired.json"
+        ))
+        .unwrap();
+        assert_eq!(expired.result.value, None);
+
+        let is_valid = parse_response::<RpcResponseWithResult<bool>>(include_str!(
+            "fixtures/is_blockhash_valid.json"
+        ))
+        .unwrap();
+        assert!(is_valid.result.value);
+    }
+
+    #[test]
+    fn fees_from_responses() {
+        let latest = || {
+            parse_response::<RpcResponseWithResult<GetLatestBlockhash>>(include_str!(
+                "fixtures/get_latest_blockhash.json"
+            ))
+            .unwrap()
+        };
+        let fee = parse_response::<RpcResponseWithResult<Option<u64>>>(include_str!(
+            "fixtures/get_fee_for_message.json"
+        ))
+        .unwrap();
+
+        let fees = GetFees::from_responses(latest(), fee).unwrap();
+        assert_eq!(fees.result.context.slot, 284_316_283);
+        assert_eq!(
+            fees.result.value.blockhash,
+            "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N"
+        );
+        assert_eq!(
+            fees.result.value.fee_calculator.lamports_per_signature,
+            5000
+        );
+        assert_eq!(fees.result.value.last_valid_block_height, 262_847_302);
+        #[allow(deprecated)]
+        let last_valid_slot = fees.result.value.last_valid_slot;
+        assert_eq!(last_valid_slot, 262_847_302);
+
+        let expired = parse_response::<RpcResponseWithResult<Option<u64>>>(include_str!(
+            "fixtures/get_fee_for_message_expired.json"
+        ))
+        .unwrap();
+        assert!(matches!(
+            GetFees::from_responses(latest(), expired),
+            Err(PoseidonError::BlockhashNotFound)
+        ));
     }
 }
@@ -0,0 +1,241 @@
+use crate::{
+    request_with_result, Base58TxSignature, Commitment, PoseidonError, PoseidonResult, RpcClient,
+    RpcResponseWithResult, TransactionError,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSignatureStatuses {
+    pub slot: u64,
+    /// Number of blocks since signature confirmation, `None` if rooted
+    pub confirmations: Option<u64>,
+    pub err: Option<TransactionError>,
+    /// The cluster confirmation status of the transaction,
+    /// either `processed`, `confirmed` or `finalized`
+    pub confirmation_status: Option<String>,
+}
+
+impl GetSignatureStatuses {
+    /// Fetch the statuses of up to 256 `signatures`. A status is `None`
+    /// if the signature has not been seen by the cluster.
+    pub async fn process(
+        signatures: &[Base58TxSignature],
+        search_transaction_history: bool,
+    ) -> PoseidonResult<RpcResponseWithResult<Vec<Option<GetSignatureStatuses>>>> {
+        let body = GetSignatureStatuses::body(signatures, search_transaction_history);
+
+        request_with_result::<Vec<Option<GetSignatureStatuses>>>(body).await
+    }
+
+    fn body(signatures: &[Base58TxSignature], search_transaction_history: bool) -> json::JsonValue {
+        json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getSignatureStatuses",
+            params: json::array![
+                signatures.to_vec(),
+                json::object!{
+                    searchTransactionHistory: search_transaction_history,
+                }
+            ]
+        }
+    }
+
+    /// Poll the status of `signature` every `interval` until it reaches the `commitment`,
+    /// giving up after `attempts` polls.
+    pub async fn confirm(
+        signature: &str,
+        commitment: Commitment,
+        interval: Duration,
+        attempts: usize,
+    ) -> PoseidonResult<GetSignatureStatuses> {
+        GetSignatureStatuses::confirm_with(
+            &RpcClient::new(),
+            signature,
+            commitment,
+            interval,
+            attempts,
+        )
+        .await
+    }
+
+    /// `confirm` with the URL and headers of `rpc`
+    pub(crate) async fn confirm_with(
+        rpc: &RpcClient,
+        signature: &str,
+        commitment: Commitment,
+        interval: Duration,
+        attempts: usize,
+    ) -> PoseidonResult<GetSignatureStatuses> {
+        let body = GetSignatureStatuses::body(&[signature.to_owned()], false);
+
+        for _ in 0..attempts {
+            let status = rpc
+                .request_with_result::<Vec<Option<GetSignatureStatuses>>>(body.clone())
+                .await?
+                .result
+                .value
+                .pop()
+                .flatten();
+
+            if let Some(status) = status {
+                if let Some(error) = status.err {
+                    return Err(PoseidonError::TransactionFailed(error));
+                }
+
+                if status.has_reached(commitment) {
+                    return Ok(status);
+                }
+            }
+
+            smol::Timer::after(interval).await;
+        }
+
+        Err(PoseidonError::TransactionConfirmationTimeout)
+    }
+
+    /// Whether the transaction has been confirmed at the `commitment` level or higher
+    pub fn has_reached(&self, commitment: Commitment) -> bool {
+        let status = match &self.confirmation_status {
+            Some(status) => Commitment::from(status.as_str()),
+            // Only rooted transactions lack a confirmation count
+            None if self.confirmations.is_none() => Commitment::Finalized,
+            None => Commitment::Confirmed,
+        };
+
+        match commitment {
+            Commitment::Finalized => status == Commitment::Finalized,
+            Commitment::Confirmed => {
+                status == Commitment::Confirmed || status == Commitment::Finalized
+            }
+            _ => status != Commitment::Unspecified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use crate::{test_utils::mock_rpc_server, *};
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn status(
+        confirmations: Option<u64>,
+        confirmation_status: Option<&str>,
+    ) -> GetSignatureStatuses {
+        GetSignatureStatuses {
+            slot: 1,
+            confirmations,
+            err: None,
+            confirmation_status: confirmation_status.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn has_reached_commitment() {
+        let processed = status(Some(0), Some("processed"));
+        assert!(processed.has_reached(Commitment::Processed));
+        assert!(!processed.has_reached(Commitment::Confirmed));
+        assert!(!processed.has_reached(Commitment::Finalized));
+
+        let confirmed = status(Some(3), Some("confirmed"));
+        assert!(confirmed.has_reached(Commitment::Processed));
+        assert!(confirmed.has_reached(Commitment::Confirmed));
+        assert!(!confirmed.has_reached(Commitment::Finalized));
+
+        let finalized = status(None, Some("finalized"));
+        assert!(finalized.has_reached(Commitment::Confirmed));
+        assert!(finalized.has_reached(Commitment::Finalized));
+
+        // Nodes that do not report a confirmation status
+        assert!(status(None, None).has_reached(Commitment::Finalized));
+        assert!(status(Some(3), None).has_reached(Commitment::Confirmed));
+        assert!(!status(Some(3), None).has_reached(Commitment::Finalized));
+    }
+
+    #[test]
+    fn confirm_polls_until_commitment() {
+        let mut polls = 0;
+        let (url, server) = mock_rpc_server(3, move |_| {
+            polls += 1;
+            let value = match polls {
+                1 => json!([null]),
+                2 => {
+                    json!([{ "slot": 1, "confirmations": 0, "err": null, "confirmationStatus": "processed" }])
+                }
+                _ => {
+                    json!([{ "slot": 1, "confirmations": 2, "err": null, "confirmationStatus": "confirmed" }])
+                }
+            };
+            Ok(json!({ "context": { "apiVersion": "1.16.0", "slot": 7 }, "value": value }))
+        });
+        let mut rpc = RpcClient::new();
+        rpc.add_url(&url);
+
+        let confirmed = smol::block_on(GetSignatureStatuses::confirm_with(
+            &rpc,
+            "signature",
+            Commitment::Confirmed,
+            Duration::from_millis(1),
+            5,
+        ))
+        .unwrap();
+
+        assert_eq!(confirmed, status(Some(2), Some("confirmed")));
+        assert_eq!(server.join().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn confirm_gives_up() {
+        let (url, server) = mock_rpc_server(2, |_| {
+            Ok(json!({ "context": { "apiVersion": "1.16.0", "slot": 7 }, "value": [null] }))
+        });
+        let mut rpc = RpcClient::new();
+        rpc.add_url(&url);
+
+        let outcome = smol::block_on(GetSignatureStatuses::confirm_with(
+            &rpc,
+            "signature",
+            Commitment::Finalized,
+            Duration::from_millis(1),
+            2,
+        ));
+
+        assert!(matches!(
+            outcome,
+            Err(PoseidonError::TransactionConfirmationTimeout)
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn confirm_surfaces_failed_transaction() {
+        let (url, server) = mock_rpc_server(1, |_| {
+            Ok(json!({
+                "context": { "apiVersion": "1.16.0", "slot": 7 },
+                "value": [{ "slot": 1, "confirmations": 0, "err": "AccountInUse", "confirmationStatus": "processed" }],
+            }))
+        });
+        let mut rpc = RpcClient::new();
+        rpc.add_url(&url);
+
+        let outcome = smol::block_on(GetSignatureStatuses::confirm_with(
+            &rpc,
+            "signature",
+            Commitment::Confirmed,
+            Duration::from_millis(1),
+            5,
+        ));
+
+        assert!(matches!(
+            outcome,
+            Err(PoseidonError::TransactionFailed(
+                TransactionError::AccountInUse
+            ))
+        ));
+        server.join().unwrap();
+    }
+}
@@ -1,85 +1,297 @@
 use crate::{
-    Base58BlockHash, Base58PublicKey, MessageHeader, PoseidonError, PoseidonResult, RpcClient,
-    Transaction, TransactionError, UnixTimestamp,
+    request, Base58BlockHash, Base58PublicKey, Base58TxSignature, Commitment, MessageHeader,
+    PoseidonError, PoseidonResult, RpcParsedTransaction, RpcResponse, Transaction,
+    TransactionError, UiInstruction, UnixTimestamp,
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, BorshSerialize, BorshDeserialize)]
-#[serde(rename_all = "camelCase")]
+/// The encoding of the transactions returned by the RPC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransactionEncoding {
+    Base58,
+    #[default]
+    Base64,
+    Json,
+    /// JSON with the instructions of known programs parsed into `ParsedInstruction`s
+    JsonParsed,
+}
+
+impl From<TransactionEncoding> for &str {
+    fn from(encoding: TransactionEncoding) -> Self {
+        match encoding {
+            TransactionEncoding::Base58 => "base58",
+            TransactionEncoding::Base64 => "base64",
+            TransactionEncoding::Json => "json",
+            TransactionEncoding::JsonParsed => "jsonParsed",
+        }
+    }
+}
+
+/// ### Fetch a confirmed transaction
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example() -> PoseidonResult<()> {
+/// let transaction = GetTransaction::new("44stjcK4f7RC7KNCorh9gzhQagpYoT9Tq775UFtYbn5gepRocHEeXrtG2JmzgTYKCx83pfBhWHiwLa6sC7f8Ruft")
+///     .change_commitment(Commitment::Confirmed)
+///     .add_max_supported_transaction_version(0)
+///     .process()
+///     .await?
+///     .result
+///     .ok_or(PoseidonError::TransactionNotFoundInCluster)?
+///     .decode()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GetTransaction {
-    pub jsonrpc: String,
-    pub id: u8,
-    pub result: Option<RpcResult>,
+    signature: Base58TxSignature,
+    commitment: Commitment,
+    encoding: TransactionEncoding,
+    max_supported_transaction_version: Option<u8>,
 }
 
 impl GetTransaction {
-    pub async fn process(transaction: &str) -> PoseidonResult<GetTransaction> {
-        use json::JsonValue;
+    pub fn new(signature: &str) -> Self {
+        GetTransaction {
+            signature: signature.to_owned(),
+            commitment: Commitment::Finalized,
+            encoding: TransactionEncoding::default(),
+            max_supported_transaction_version: Option::default(),
+        }
+    }
+
+    /// `Commitment::Processed` is not supported for transactions
+    pub fn change_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.commitment = commitment;
+
+        self
+    }
+
+    /// Defaults to `TransactionEncoding::Base64` so the transaction can be decoded
+    pub fn add_encoding(&mut self, encoding: TransactionEncoding) -> &mut Self {
+        self.encoding = encoding;
+
+        self
+    }
+
+    /// Return versioned transactions up to this version. Without it the request
+    /// fails if the transaction is a versioned transaction.
+    pub fn add_max_supported_transaction_version(&mut self, version: u8) -> &mut Self {
+        self.max_supported_transaction_version = Some(version);
+
+        self
+    }
+
+    /// The transaction is `None` if it was not found or has not been confirmed yet
+    pub async fn process(&self) -> PoseidonResult<RpcResponse<Option<RpcConfirmedTransaction>>> {
+        let commitment: &str = self.commitment.into();
+        let encoding: &str = self.encoding.into();
+
+        let mut config = json::object! {
+            commitment: commitment,
+            encoding: encoding,
+        };
+        if let Some(version) = self.max_supported_transaction_version {
+            config["maxSupportedTransactionVersion"] = version.into();
+        }
 
         let body: json::JsonValue = json::object! {
             jsonrpc: "2.0",
             id: 1u8,
             method: "getTransaction",
-            params: json::array![JsonValue::String(transaction.to_owned()), JsonValue::String("base58".to_owned())]
+            params: json::array![self.signature.as_str(), config]
         };
 
-        Ok(GetTransaction::request(body).await?)
+        request::<Option<RpcConfirmedTransaction>>(body).await
     }
+}
 
-    pub fn transaction(&self) -> PoseidonResult<Transaction> {
-        match &self.result {
-            Some(rpc_result) => {
-                let encoded = &rpc_result.transaction.0;
-                let decoded = bs58::decode(encoded).into_vec()?;
-                let data = bincode::deserialize::<Transaction>(&decoded)?;
+/// A confirmed transaction and its status meta
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcConfirmedTransaction {
+    /// The slot this transaction was processed in
+    pub slot: u64,
+    /// Estimated production time of the block, `None` if not available
+    pub block_time: Option<UnixTimestamp>,
+    pub meta: Option<RpcMeta>,
+    pub transaction: EncodedTransaction,
+    /// Only present when `maxSupportedTransactionVersion` is set
+    pub version: Option<TransactionVersion>,
+}
 
-                Ok(data)
+impl RpcConfirmedTransaction {
+    pub fn decode(&self) -> PoseidonResult<Transaction> {
+        self.transaction.decode_versioned(self.version)
+    }
+}
+
+/// A transaction as returned by the RPC for the requested encoding
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum EncodedTransaction {
+    /// The encoded transaction and the encoding used
+    Binary(String, String),
+    Json(RpcTransaction),
+    JsonParsed(RpcParsedTransaction),
+    Accounts(RpcTransactionAccounts),
+}
+
+impl EncodedTransaction {
+    pub fn decode(&self) -> PoseidonResult<Transaction> {
+        let bytes = match self {
+            EncodedTransaction::Binary(data, encoding) => match encoding.as_str() {
+                "base58" => bs58::decode(data).into_vec()?,
+                "base64" => base64::decode(data)?,
+                _ => {
+                    return Err(PoseidonError::UnsupportedTransactionEncoding(
+                        encoding.to_owned(),
+                    ))
+                }
+            },
+            EncodedTransaction::Json(_) => {
+                return Err(PoseidonError::UnsupportedTransactionEncoding(
+                    "json".to_owned(),
+                ))
             }
-            None => Err(PoseidonError::TransactionNotFoundInCluster),
-        }
+            EncodedTransaction::JsonParsed(_) => {
+                return Err(PoseidonError::UnsupportedTransactionEncoding(
+                    "jsonParsed".to_owned(),
+                ))
+            }
+            EncodedTransaction::Accounts(_) => {
+                return Err(PoseidonError::UnsupportedTransactionEncoding(
+                    "accounts".to_owned(),
+                ))
+            }
+        };
+
+        Ok(bincode::deserialize::<Transaction>(&bytes)?)
     }
 
-    async fn request(body: json::JsonValue) -> PoseidonResult<GetTransaction> {
-        let mut rpc = RpcClient::new();
-        rpc.add_body(body);
-        let response = rpc.send().await?;
-        let deser_response: GetTransaction = serde_json::from_str(response.as_str()?)?;
+    /// Decode the transaction, only legacy transactions are supported
+    pub fn decode_versioned(
+        &self,
+        version: Option<TransactionVersion>,
+    ) -> PoseidonResult<Transaction> {
+        if let Some(TransactionVersion::Number(version)) = version {
+            return Err(PoseidonError::UnsupportedTransactionVersion(version));
+        }
 
-        Ok(deser_response)
+        self.decode()
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, BorshSerialize, BorshDeserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TransactionVersion {
+    Legacy(Legacy),
+    Number(u8),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct RpcResult {
-    pub block_time: UnixTimestamp,
-    pub meta: RpcMeta,
-    pub transaction: (String, String),
+pub enum Legacy {
+    Legacy,
 }
 
-#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, BorshSerialize, BorshDeserialize)]
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransaction {
+    pub signatures: Vec<Base58TxSignature>,
+    pub message: RpcMessage,
+}
+
+/// The transaction returned with `TransactionDetails::Accounts`
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionAccounts {
+    pub signatures: Vec<Base58TxSignature>,
+    pub account_keys: Vec<RpcAccountKey>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountKey {
+    pub pubkey: Base58PublicKey,
+    pub writable: bool,
+    pub signer: bool,
+    /// Either `transaction` or `lookupTable`
+    pub source: Option<String>,
+}
+
+/// The status meta of a transaction. Most fields are `None` when
+/// the node did not record them or the transaction details were reduced.
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcMeta {
     pub err: Option<TransactionError>,
-    pub fee: u32,
-    pub inner_instructions: Vec<RpcInnerInstructions>,
-    pub log_messages: Vec<String>,
+    pub fee: u64,
+    #[serde(default)]
+    pub inner_instructions: Option<Vec<RpcInnerInstructions>>,
+    #[serde(default)]
+    pub log_messages: Option<Vec<String>>,
     pub pre_balances: Vec<u64>,
     pub post_balances: Vec<u64>,
-    pub pre_token_balances: Vec<TokenBalances>,
-    pub post_token_balances: Vec<TokenBalances>,
-    pub rewards: Vec<Reward>,
-    pub status: Result<(), TransactionError>,
+    #[serde(default)]
+    pub pre_token_balances: Option<Vec<TokenBalances>>,
+    #[serde(default)]
+    pub post_token_balances: Option<Vec<TokenBalances>>,
+    #[serde(default)]
+    pub rewards: Option<Vec<Reward>>,
+    /// Deprecated in favor of `err`
+    pub status: TransactionStatus,
+    /// The addresses loaded from address lookup tables by a versioned transaction
+    #[serde(default)]
+    pub loaded_addresses: Option<RpcLoadedAddresses>,
+    /// The data most recently set by a program with `set_return_data`
+    #[serde(default)]
+    pub return_data: Option<RpcReturnData>,
+    #[serde(default)]
+    pub compute_units_consumed: Option<u64>,
 }
 
-#[derive(
-    Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize, BorshSerialize, BorshDeserialize,
-)]
+/// The outcome of a transaction, serialized as `{"Ok":null}` or `{"Err":<TransactionError>}`
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub enum TransactionStatus {
+    Ok(()),
+    Err(TransactionError),
+}
+
+impl TransactionStatus {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, TransactionStatus::Ok(_))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLoadedAddresses {
+    pub writable: Vec<Base58PublicKey>,
+    pub readonly: Vec<Base58PublicKey>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcReturnData {
+    pub program_id: Base58PublicKey,
+    /// The return data and its encoding, always `base64`
+    pub data: (String, String),
+}
+
+impl RpcReturnData {
+    pub fn decode(&self) -> PoseidonResult<Vec<u8>> {
+        Ok(base64::decode(&self.data.0)?)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcInnerInstructions {
     pub index: u8,
-    pub instructions: Vec<RpcCompiledInstruction>,
+    /// Compiled instructions, or parsed ones for the `jsonParsed` encoding
+    pub instructions: Vec<UiInstruction>,
 }
 
 #[derive(
@@ -89,7 +301,8 @@ pub struct RpcInnerInstructions {
 pub struct TokenBalances {
     pub account_index: u8,
     pub mint: Base58PublicKey,
-    pub owner: Base58PublicKey,
+    pub owner: Option<Base58PublicKey>,
+    pub program_id: Option<Base58PublicKey>,
     pub ui_token_amount: TokenAmount,
 }
 
@@ -100,7 +313,8 @@ pub struct TokenBalances {
 pub struct TokenAmount {
     pub amount: String,
     pub decimals: u8,
-    pub ui_amount: f64,
+    /// Deprecated in favor of `ui_amount_string`
+    pub ui_amount: Option<f64>,
     pub ui_amount_string: String,
 }
 
@@ -112,14 +326,14 @@ pub struct Reward {
     pub pubkey: String,
     pub lamports: i64,
     pub post_balance: u64,
-    pub reward_type: RewardType,
-    pub commission: u8,
+    pub reward_type: Option<RewardType>,
+    /// The vote account commission, only present for voting and staking rewards
+    pub commission: Option<u8>,
 }
 
 #[derive(
     Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize, BorshSerialize, BorshDeserialize,
 )]
-#[serde(rename_all = "camelCase")]
 pub enum RewardType {
     Fee,
     Rent,
@@ -131,11 +345,13 @@ pub enum RewardType {
     Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize, BorshSerialize, BorshDeserialize,
 )]
 #[serde(rename_all = "camelCase")]
-
 pub struct RpcCompiledInstruction {
     pub program_id_index: u8,
     pub accounts: Vec<u8>,
     pub data: String,
+    /// The invocation depth of an inner instruction
+    #[serde(default)]
+    pub stack_height: Option<u32>,
 }
 
 #[derive(
@@ -147,21 +363,171 @@ pub struct RpcMessage {
     pub account_keys: Vec<Base58PublicKey>,
     pub recent_blockhash: Base58BlockHash,
     pub instructions: Vec<RpcCompiledInstruction>,
+    /// The address lookup tables used by a versioned transaction
+    #[serde(default)]
+    pub address_table_lookups: Option<Vec<RpcAddressTableLookup>>,
 }
 
 #[derive(
-    Debug,
-    PartialEq,
-    Eq,
-    Ord,
-    PartialOrd,
-    Clone,
-    Deserialize,
-    Serialize,
-    BorshSerialize,
-    BorshDeserialize,
+    Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize, BorshSerialize, BorshDeserialize,
 )]
 #[serde(rename_all = "camelCase")]
-pub struct Context {
-    pub slot: u64,
+pub struct RpcAddressTableLookup {
+    pub account_key: Base58PublicKey,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::{
+        parse_response, InstructionError, ParsedInstruction, ParsedProgramInstruction,
+        ParsedSystemInstruction, ParsedTokenInstruction, SYSTEM_PROGRAM_ID,
+    };
+
+    #[test]
+    fn legacy_base64_transaction() {
+        let response = parse_response::<RpcResponse<Option<RpcConfirmedTransaction>>>(
+            include_str!("fixtures/get_transaction_legacy_base64.json"),
+        )
+        .unwrap();
+        let confirmed = response.result.unwrap();
+
+        assert_eq!(confirmed.slot, 214_315_421);
+        assert_eq!(confirmed.block_time, Some(1_692_093_527));
+        assert_eq!(
+            confirmed.version,
+            Some(TransactionVersion::Legacy(Legacy::Legacy))
+        );
+
+        let meta = confirmed.meta.as_ref().unwrap();
+        assert!(meta.err.is_none());
+        assert!(meta.status.is_ok());
+        assert_eq!(meta.fee, 5000);
+        assert_eq!(meta.compute_units_consumed, Some(150));
+        assert_eq!(meta.loaded_addresses, Some(RpcLoadedAddresses::default()));
+        assert!(meta.return_data.is_none());
+        assert_eq!(meta.log_messages.as_ref().unwrap().len(), 2);
+
+        let transaction = confirmed.decode().unwrap();
+        assert_eq!(transaction.signatures.len(), 1);
+        assert_eq!(transaction.message.account_keys.len(), 3);
+        assert_eq!(transaction.message.account_keys[2], SYSTEM_PROGRAM_ID);
+        assert_eq!(transaction.message.instructions[0].data[0], 2);
+    }
+
+    #[test]
+    fn failed_versioned_json_transaction() {
+        let response = parse_response::<RpcResponse<Option<RpcConfirmedTransaction>>>(
+            include_str!("fixtures/get_transaction_v0_json.json"),
+        )
+        .unwrap();
+        let confirmed = response.result.unwrap();
+
+        assert_eq!(confirmed.block_time, Some(1_692_093_562));
+        assert_eq!(confirmed.version, Some(TransactionVersion::Number(0)));
+        assert!(matches!(
+            confirmed.decode(),
+            Err(PoseidonError::UnsupportedTransactionVersion(0))
+        ));
+
+        let meta = confirmed.meta.as_ref().unwrap();
+        let error = TransactionError::InstructionError(2, InstructionError::Custom(6001));
+        assert_eq!(meta.err, Some(error.clone()));
+        assert_eq!(meta.status, TransactionStatus::Err(error));
+
+        let loaded_addresses = meta.loaded_addresses.as_ref().unwrap();
+        assert_eq!(loaded_addresses.writable.len(), 1);
+        assert_eq!(loaded_addresses.readonly.len(), 2);
+
+        let return_data = meta.return_data.as_ref().unwrap();
+        assert_eq!(return_data.decode().unwrap(), vec![1, 0, 0, 0]);
+
+        let rewards = meta.rewards.as_ref().unwrap();
+        assert_eq!(rewards[0].reward_type, Some(RewardType::Fee));
+        assert!(rewards[0].commission.is_none());
+
+        let inner_instructions = meta.inner_instructions.as_ref().unwrap();
+        match &inner_instructions[0].instructions[0] {
+            UiInstruction::Compiled(instruction) => assert_eq!(instruction.stack_height, Some(2)),
+            _ => panic!("Expected a compiled inner instruction"),
+        }
+
+        let token_balances = meta.pre_token_balances.as_ref().unwrap();
+        assert_eq!(token_balances[0].ui_token_amount.amount, "1250000000");
+        assert_eq!(token_balances[0].ui_token_amount.ui_amount, Some(1250.0));
+
+        match &confirmed.transaction {
+            EncodedTransaction::Json(transaction) => {
+                let lookups = transaction.message.address_table_lookups.as_ref().unwrap();
+                assert_eq!(lookups[0].readonly_indexes, vec![4, 7]);
+            }
+            _ => panic!("Expected a JSON encoded transaction"),
+        }
+    }
+
+    #[test]
+    fn json_parsed_transaction() {
+        let response = parse_response::<RpcResponse<Option<RpcConfirmedTransaction>>>(
+            include_str!("fixtures/get_transaction_v0_json_parsed.json"),
+        )
+        .unwrap();
+        let confirmed = response.result.unwrap();
+        assert!(matches!(
+            confirmed.transaction.decode(),
+            Err(PoseidonError::UnsupportedTransactionEncoding(_))
+        ));
+
+        let message = match &confirmed.transaction {
+            EncodedTransaction::JsonParsed(transaction) => &transaction.message,
+            _ => panic!("Expected a jsonParsed transaction"),
+        };
+        assert!(message.account_keys[0].signer);
+
+        match &message.instructions[0] {
+            UiInstruction::Parsed(ParsedInstruction {
+                instruction:
+                    ParsedProgramInstruction::System(ParsedSystemInstruction::Transfer {
+                        lamports, ..
+                    }),
+                ..
+            }) => assert_eq!(*lamports, 2_000_000),
+            _ => panic!("Expected a system transfer"),
+        }
+        match &message.instructions[1] {
+            UiInstruction::Parsed(instruction) => assert_eq!(
+                instruction.instruction,
+                ParsedProgramInstruction::Memo("gm".to_owned())
+            ),
+            _ => panic!("Expected a memo"),
+        }
+        assert!(matches!(
+            &message.instructions[2],
+            UiInstruction::PartiallyDecoded(instruction) if instruction.accounts.len() == 3
+        ));
+
+        let meta = confirmed.meta.unwrap();
+        let inner_instructions = meta.inner_instructions.unwrap();
+        assert!(matches!(
+            &inner_instructions[0].instructions[0],
+            UiInstruction::Parsed(ParsedInstruction {
+                instruction: ParsedProgramInstruction::SplToken(
+                    ParsedTokenInstruction::TransferChecked { .. }
+                ),
+                stack_height: Some(2),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn transaction_not_found() {
+        let response = parse_response::<RpcResponse<Option<RpcConfirmedTransaction>>>(
+            r#"{"jsonrpc":"2.0","result":null,"id":1}"#,
+        )
+        .unwrap();
+
+        assert!(response.result.is_none());
+    }
 }
@@ -0,0 +1,123 @@
+use crate::{request, Base58PublicKey, Commitment, PoseidonResult, RpcResponse};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// ### Fetch the vote accounts of the current bank
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example() -> PoseidonResult<()> {
+/// let vote_accounts = GetVoteAccounts::new()
+///     .add_vote_pubkey("Vote111111111111111111111111111111111111111")
+///     .keep_unstaked_delinquents(true)
+///     .process()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVoteAccounts {
+    commitment: Commitment,
+    vote_pubkey: Option<Base58PublicKey>,
+    keep_unstaked_delinquents: Option<bool>,
+    delinquent_slot_distance: Option<u64>,
+}
+
+impl Default for GetVoteAccounts {
+    fn default() -> Self {
+        GetVoteAccounts::new()
+    }
+}
+
+impl GetVoteAccounts {
+    pub fn new() -> Self {
+        GetVoteAccounts {
+            commitment: Commitment::Finalized,
+            vote_pubkey: Option::default(),
+            keep_unstaked_delinquents: Option::default(),
+            delinquent_slot_distance: Option::default(),
+        }
+    }
+
+    pub fn change_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.commitment = commitment;
+
+        self
+    }
+
+    /// Only return results for this validator vote address
+    pub fn add_vote_pubkey(&mut self, vote_pubkey: &str) -> &mut Self {
+        self.vote_pubkey = Some(vote_pubkey.to_owned());
+
+        self
+    }
+
+    /// Do not filter out delinquent validators with no stake
+    pub fn keep_unstaked_delinquents(&mut self, keep: bool) -> &mut Self {
+        self.keep_unstaked_delinquents = Some(keep);
+
+        self
+    }
+
+    /// Specify the number of slots behind the tip that a validator
+    /// must fall to be considered delinquent
+    pub fn add_delinquent_slot_distance(&mut self, distance: u64) -> &mut Self {
+        self.delinquent_slot_distance = Some(distance);
+
+        self
+    }
+
+    pub async fn process(&self) -> PoseidonResult<RpcResponse<RpcVoteAccountStatus>> {
+        let commitment: &str = self.commitment.into();
+
+        let mut config = json::object! {
+            commitment: commitment,
+        };
+        if let Some(vote_pubkey) = &self.vote_pubkey {
+            config["votePubkey"] = vote_pubkey.as_str().into();
+        }
+        if let Some(keep_unstaked_delinquents) = self.keep_unstaked_delinquents {
+            config["keepUnstakedDelinquents"] = keep_unstaked_delinquents.into();
+        }
+        if let Some(delinquent_slot_distance) = self.delinquent_slot_distance {
+            config["delinquentSlotDistance"] = delinquent_slot_distance.into();
+        }
+
+        let body: json::JsonValue = json::object! {
+            jsonrpc: "2.0",
+            id: 1u8,
+            method: "getVoteAccounts",
+            params: json::array![config]
+        };
+
+        request::<RpcVoteAccountStatus>(body).await
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVoteAccountStatus {
+    pub current: Vec<RpcVoteAccountInfo>,
+    pub delinquent: Vec<RpcVoteAccountInfo>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVoteAccountInfo {
+    /// Vote account address
+    pub vote_pubkey: Base58PublicKey,
+    /// The validator identity
+    pub node_pubkey: Base58PublicKey,
+    /// The stake, in lamports, delegated to this vote account and active in this epoch
+    pub activated_stake: u64,
+    /// Whether the vote account is staked for this epoch
+    pub epoch_vote_account: bool,
+    /// Percentage (0-100) of rewards payout owed to the vote account
+    pub commission: u8,
+    /// Most recent slot voted on by this vote account
+    pub last_vote: u64,
+    /// Latest history of earned credits for up to five epochs, as `[epoch, credits, previousCredits]`
+    pub epoch_credits: Vec<(u64, u64, u64)>,
+    /// Current root slot for this vote account
+    pub root_slot: u64,
+}
@@ -0,0 +1,78 @@
+use ed25519_dalek::{Keypair, PublicKey, SecretKey};
+use serde_json::json;
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread::JoinHandle,
+};
+
+/// A keypair whose secret key is 32 times `seed`
+pub(crate) fn keypair(seed: u8) -> Keypair {
+    let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+    let public = PublicKey::from(&secret);
+
+    Keypair { secret, public }
+}
+
+/// Answer `requests` JSON-RPC calls on a local port with the result, or the error object,
+/// `respond` gives for each request, returning the URL to call and the path and method
+/// of every request
+pub(crate) fn mock_rpc_server(
+    requests: usize,
+    mut respond: impl FnMut(&serde_json::Value) -> Result<serde_json::Value, serde_json::Value>
+        + Send
+        + 'static,
+) -> (String, JoinHandle<Vec<(String, String)>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/rpc", listener.local_addr().unwrap());
+
+    let server = std::thread::spawn(move || {
+        (0..requests)
+            .map(|_| {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split(' ').nth(1).unwrap().to_owned();
+
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+                let method = body["method"].as_str().unwrap().to_owned();
+
+                let response = match respond(&body) {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": 1, "result": result }),
+                    Err(error) => json!({ "jsonrpc": "2.0", "id": 1, "error": error }),
+                }
+                .to_string();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+
+                (path, method)
+            })
+            .collect()
+    });
+
+    (url, server)
+}
@@ -0,0 +1,198 @@
+use crate::{
+    AccountMeta, Instruction, PdaBuilder, PdaPublicKey, PoseidonResult, PublicKey,
+    ADDRESS_LOOKUP_TABLE_PROGRAM_ID, SYSTEM_PROGRAM_ID,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum AddressLookupTableInstruction {
+    /// Create an address lookup table
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Uninitialized address lookup table account
+    ///   1. `[SIGNER]` Account used to derive and control the new address lookup table.
+    ///   2. `[SIGNER, WRITE]` Account that will fund the new address lookup table.
+    ///   3. `[]` System program for CPI.
+    CreateLookupTable {
+        /// A recent slot must be used in the derivation path
+        /// for each initialized table. When closing table accounts,
+        /// the initialization slot must no longer be "recent" to prevent
+        /// address tables from being recreated with reordered or
+        /// otherwise malicious addresses.
+        recent_slot: u64,
+        /// Address tables are always initialized at program-derived
+        /// addresses using the funding address, recent blockhash, and
+        /// the user-passed `bump_seed`.
+        bump_seed: u8,
+    },
+
+    /// Permanently freeze an address lookup table, making it immutable.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to freeze
+    ///   1. `[SIGNER]` Current authority
+    FreezeLookupTable,
+
+    /// Extend an address lookup table with new addresses. Funding account and
+    /// system program account references are only required if the lookup table
+    /// account requires additional lamports to cover the rent-exempt balance
+    /// after being extended.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to extend
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the table reallocation
+    ///   3. `[OPTIONAL]` System program for CPI.
+    ExtendLookupTable { new_addresses: Vec<PublicKey> },
+
+    /// Deactivate an address lookup table, making it unusable and
+    /// eligible for closure after a short period of time.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to deactivate
+    ///   1. `[SIGNER]` Current authority
+    DeactivateLookupTable,
+
+    /// Close an address lookup table account
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to close
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Recipient of closed account lamports
+    CloseLookupTable,
+}
+
+/// ### Build Address Lookup Table Program instructions
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn example(authority: PublicKey, payer: PublicKey, first_address: PublicKey, second_address: PublicKey) -> PoseidonResult<()> {
+/// let recent_slot = 150_000_000;
+/// let (create_table, table_address) =
+///     AddressLookupTableProgram::create_lookup_table(authority, payer, recent_slot)?;
+/// let extend_table = AddressLookupTableProgram::extend_lookup_table(
+///     table_address,
+///     authority,
+///     Some(payer),
+///     vec![first_address, second_address],
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AddressLookupTableProgram;
+
+impl AddressLookupTableProgram {
+    /// Derive the address of a lookup table from its `authority` and the `recent_slot`
+    /// used to create it, returning the address and its bump seed
+    pub fn derive_lookup_table_address(
+        authority: PublicKey,
+        recent_slot: u64,
+    ) -> PoseidonResult<(PdaPublicKey, u8)> {
+        PdaBuilder::find_program_address(
+            &[&authority, &recent_slot.to_le_bytes()],
+            &ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+        )
+    }
+
+    /// Create the lookup table owned by `authority` and funded by `payer`.
+    /// Both `authority` and `payer` must sign the transaction.
+    pub fn create_lookup_table(
+        authority: PublicKey,
+        payer: PublicKey,
+        recent_slot: u64,
+    ) -> PoseidonResult<(Instruction, PdaPublicKey)> {
+        let (lookup_table_address, bump_seed) =
+            AddressLookupTableProgram::derive_lookup_table_address(authority, recent_slot)?;
+
+        let instruction = AddressLookupTableProgram::instruction(
+            AddressLookupTableInstruction::CreateLookupTable {
+                recent_slot,
+                bump_seed,
+            },
+            vec![
+                AccountMeta::new(lookup_table_address, false),
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ],
+        )?;
+
+        Ok((instruction, lookup_table_address))
+    }
+
+    pub fn freeze_lookup_table(
+        lookup_table_address: PublicKey,
+        authority: PublicKey,
+    ) -> PoseidonResult<Instruction> {
+        AddressLookupTableProgram::instruction(
+            AddressLookupTableInstruction::FreezeLookupTable,
+            vec![
+                AccountMeta::new(lookup_table_address, false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+        )
+    }
+
+    /// Append `new_addresses` to the lookup table. Provide a `payer` if the table
+    /// needs more lamports to stay rent exempt after growing.
+    pub fn extend_lookup_table(
+        lookup_table_address: PublicKey,
+        authority: PublicKey,
+        payer: Option<PublicKey>,
+        new_addresses: Vec<PublicKey>,
+    ) -> PoseidonResult<Instruction> {
+        let mut accounts = vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority, true),
+        ];
+
+        if let Some(payer) = payer {
+            accounts.push(AccountMeta::new(payer, true));
+            accounts.push(AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false));
+        }
+
+        AddressLookupTableProgram::instruction(
+            AddressLookupTableInstruction::ExtendLookupTable { new_addresses },
+            accounts,
+        )
+    }
+
+    pub fn deactivate_lookup_table(
+        lookup_table_address: PublicKey,
+        authority: PublicKey,
+    ) -> PoseidonResult<Instruction> {
+        AddressLookupTableProgram::instruction(
+            AddressLookupTableInstruction::DeactivateLookupTable,
+            vec![
+                AccountMeta::new(lookup_table_address, false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+        )
+    }
+
+    pub fn close_lookup_table(
+        lookup_table_address: PublicKey,
+        authority: PublicKey,
+        recipient: PublicKey,
+    ) -> PoseidonResult<Instruction> {
+        AddressLookupTableProgram::instruction(
+            AddressLookupTableInstruction::CloseLookupTable,
+            vec![
+                AccountMeta::new(lookup_table_address, false),
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new(recipient, false),
+            ],
+        )
+    }
+
+    fn instruction(
+        lookup_table_instruction: AddressLookupTableInstruction,
+        accounts: Vec<AccountMeta>,
+    ) -> PoseidonResult<Instruction> {
+        Ok(Instruction {
+            program_id: ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+            accounts,
+            data: bincode::serialize(&lookup_table_instruction)?,
+        })
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{PoseidonError, PoseidonResult, PublicKey};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// The size in bytes of the metadata at the start of a lookup table account
+pub const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// The maximum number of addresses that a lookup table can hold
+pub const LOOKUP_TABLE_MAX_ADDRESSES: usize = 256;
+
+/// An address lookup table decoded from its account data.
+///
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example(lookup_table_address: &str) -> PoseidonResult<()> {
+/// let account = GetAccountInfo::process(lookup_table_address).await?.result.value;
+/// let lookup_table = AddressLookupTable::from_bytes(&account.decode_data()?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressLookupTable {
+    pub meta: LookupTableMeta,
+    pub addresses: Vec<PublicKey>,
+}
+
+impl AddressLookupTable {
+    pub fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        if data.len() < LOOKUP_TABLE_META_SIZE {
+            return Err(PoseidonError::InvalidAccountData);
+        }
+
+        let meta = match bincode::deserialize::<ProgramState>(&data[..LOOKUP_TABLE_META_SIZE])? {
+            ProgramState::LookupTable(meta) => meta,
+            ProgramState::Uninitialized => return Err(PoseidonError::InvalidAccountData),
+        };
+
+        let raw_addresses = data[LOOKUP_TABLE_META_SIZE..].chunks_exact(32);
+        if !raw_addresses.remainder().is_empty() {
+            return Err(PoseidonError::InvalidAccountData);
+        }
+
+        let addresses = raw_addresses
+            .map(|address| address.try_into())
+            .collect::<Result<Vec<PublicKey>, _>>()
+            .map_err(|_| PoseidonError::ErrorConvertingToU832)?;
+
+        Ok(AddressLookupTable { meta, addresses })
+    }
+
+    /// A table is active until it has been deactivated
+    pub fn is_active(&self) -> bool {
+        self.meta.deactivation_slot == u64::MAX
+    }
+
+    /// A table without an authority can no longer be extended or closed
+    pub fn is_frozen(&self) -> bool {
+        self.meta.authority.is_none()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum ProgramState {
+    Uninitialized,
+    LookupTable(LookupTableMeta),
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LookupTableMeta {
+    /// Lookup tables cannot be closed until the deactivation slot is
+    /// no longer "recent" (not accessible in the `SlotHashes` sysvar).
+    pub deactivation_slot: u64,
+    /// The slot that the table was last extended. Address tables may
+    /// only be used to lookup addresses that were extended before
+    /// the current bank's slot.
+    pub last_extended_slot: u64,
+    /// The start index where the table was last extended from during
+    /// the `last_extended_slot`.
+    pub last_extended_slot_start_index: u8,
+    /// Authority address which must sign for each modification.
+    pub authority: Option<PublicKey>,
+    // Padding to keep addresses 8-byte aligned
+    _padding: u16,
+}
+
+impl fmt::Debug for LookupTableMeta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LookupTableMeta")
+            .field("deactivation_slot", &self.deactivation_slot)
+            .field("last_extended_slot", &self.last_extended_slot)
+            .field(
+                "last_extended_slot_start_index",
+                &self.last_extended_slot_start_index,
+            )
+            .field(
+                "authority",
+                &self
+                    .authority
+                    .map(|authority| bs58::encode(authority).into_string()),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::{AddressLookupTableProgram, ADDRESS_LOOKUP_TABLE_PROGRAM_ID};
+
+    #[test]
+    fn decode_lookup_table() {
+        let meta = LookupTableMeta {
+            deactivation_slot: u64::MAX,
+            last_extended_slot: 150,
+            last_extended_slot_start_index: 0,
+            authority: Some([5u8; 32]),
+            _padding: 0,
+        };
+        let mut data = bincode::serialize(&ProgramState::LookupTable(meta.clone())).unwrap();
+        assert_eq!(data.len(), LOOKUP_TABLE_META_SIZE);
+        data.extend_from_slice(&[1u8; 32]);
+        data.extend_from_slice(&[2u8; 32]);
+
+        let lookup_table = AddressLookupTable::from_bytes(&data).unwrap();
+        assert_eq!(lookup_table.meta, meta);
+        assert_eq!(lookup_table.addresses, vec![[1u8; 32], [2u8; 32]]);
+        assert!(lookup_table.is_active());
+        assert!(!lookup_table.is_frozen());
+
+        assert!(AddressLookupTable::from_bytes(&data[..LOOKUP_TABLE_META_SIZE + 3]).is_err());
+    }
+
+    #[test]
+    fn create_lookup_table() {
+        let authority = [5u8; 32];
+        let (instruction, table_address) =
+            AddressLookupTableProgram::create_lookup_table(authority, authority, 150).unwrap();
+        let (derived_address, bump_seed) =
+            AddressLookupTableProgram::derive_lookup_table_address(authority, 150).unwrap();
+
+        assert_eq!(instruction.program_id, ADDRESS_LOOKUP_TABLE_PROGRAM_ID);
+        assert_eq!(table_address, derived_address);
+        assert_eq!(instruction.accounts[0].pubkey, table_address);
+        assert!(ed25519_dalek::PublicKey::from_bytes(&table_address).is_err());
+
+        let mut expected_data = vec![0, 0, 0, 0];
+        expected_data.extend_from_slice(&150u64.to_le_bytes());
+        expected_data.push(bump_seed);
+        assert_eq!(instruction.data, expected_data);
+    }
+}
@@ -0,0 +1,495 @@
+use crate::{
+    AccountMeta, Instruction, Message, MessageBuilder, PdaBuilder, PdaPublicKey, PoseidonError,
+    PoseidonResult, PublicKey, Signature, SystemInstruction, Transaction,
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID, PACKET_DATA_SIZE, SYSTEM_PROGRAM_ID, SYSVAR_CLOCK,
+    SYSVAR_RENT,
+};
+use serde::{Deserialize, Serialize};
+
+/// Size of the metadata at the start of a buffer account
+pub const BUFFER_METADATA_SIZE: usize = 37;
+/// Size of the metadata at the start of a program data account
+pub const PROGRAMDATA_METADATA_SIZE: usize = 45;
+/// Size of a program account
+pub const PROGRAM_ACCOUNT_SIZE: usize = 36;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum UpgradeableLoaderInstruction {
+    /// Initialize a Buffer account.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` source account to initialize.
+    ///   1. `[]` Buffer authority, optional, if omitted then the buffer will be
+    ///      immutable.
+    InitializeBuffer,
+
+    /// Write program data into a Buffer account.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Buffer account to write program data to.
+    ///   1. `[SIGNER]` Buffer authority
+    Write {
+        /// Offset at which to write the given bytes.
+        offset: u32,
+        /// Serialized program data
+        bytes: Vec<u8>,
+    },
+
+    /// Deploy an executable program from a Buffer account.
+    ///
+    /// # Account references
+    ///   0. `[WRITE, SIGNER]` The payer account that will pay to create the
+    ///      ProgramData account.
+    ///   1. `[WRITE]` The uninitialized ProgramData account.
+    ///   2. `[WRITE]` The uninitialized Program account.
+    ///   3. `[WRITE]` The Buffer account where the program data has been
+    ///      written. The buffer account's authority must match the program's
+    ///      authority
+    ///   4. `[]` Rent sysvar.
+    ///   5. `[]` Clock sysvar.
+    ///   6. `[]` System program.
+    ///   7. `[SIGNER]` The program's authority
+    DeployWithMaxDataLen {
+        /// Maximum length that the program can be upgraded to.
+        max_data_len: u64,
+    },
+
+    /// Upgrade a program.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` The ProgramData account.
+    ///   1. `[WRITE]` The Program account.
+    ///   2. `[WRITE]` The Buffer account where the program data has been
+    ///      written. The buffer account's authority must match the program's
+    ///      authority
+    ///   3. `[WRITE]` The spill account.
+    ///   4. `[]` Rent sysvar.
+    ///   5. `[]` Clock sysvar.
+    ///   6. `[SIGNER]` The program's authority.
+    Upgrade,
+
+    /// Set a new authority that is allowed to write the buffer or upgrade the
+    /// program. To permanently make the buffer immutable or disable program
+    /// updates omit the new authority.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` The Buffer or ProgramData account to change the
+    ///      authority of.
+    ///   1. `[SIGNER]` The current authority.
+    ///   2. `[]` The new authority, optional, if omitted then the program will
+    ///      not be upgradeable.
+    SetAuthority,
+
+    /// Closes an account owned by the upgradeable loader of all lamports and
+    /// withdraws all the lamports
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` The account to close, if closing a program must be the
+    ///      ProgramData account.
+    ///   1. `[WRITE]` The account to deposit the closed account's lamports.
+    ///   2. `[SIGNER]` The account's authority, Optional, required for
+    ///      initialized accounts.
+    ///   3. `[WRITE]` The associated Program account if the account to close
+    ///      is a ProgramData account.
+    Close,
+
+    /// Extend a program's ProgramData account by the specified number of bytes.
+    /// Only upgradeable program's can be extended.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` The ProgramData account.
+    ///   1. `[WRITE]` The ProgramData account's associated Program account.
+    ///   2. `[]` System program, optional, used to transfer lamports from the payer
+    ///      to the ProgramData account.
+    ///   3. `[WRITE, SIGNER]` The payer account, optional, that will pay
+    ///      necessary rent exemption costs for the increased storage size.
+    ExtendProgram {
+        /// Number of bytes to extend the program data.
+        additional_bytes: u32,
+    },
+}
+
+/// ### Build BPF Loader Upgradeable instructions
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn example(payer: PublicKey, buffer: PublicKey, buffer_lamports: u64, program_elf: Vec<u8>) -> PoseidonResult<()> {
+/// let buffer_instructions = BpfLoaderUpgradeable::create_buffer(
+///     payer,
+///     buffer,
+///     payer,
+///     buffer_lamports,
+///     program_elf.len(),
+/// )?;
+/// let chunk_size = BpfLoaderUpgradeable::calculate_max_chunk_size(payer, buffer, payer)?;
+/// let write_instructions =
+///     BpfLoaderUpgradeable::write_chunks(buffer, payer, &program_elf, chunk_size)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BpfLoaderUpgradeable;
+
+impl BpfLoaderUpgradeable {
+    /// The address of the ProgramData account of the `program_address`
+    pub fn programdata_address(program_address: PublicKey) -> PoseidonResult<PdaPublicKey> {
+        let (programdata_address, _) = PdaBuilder::find_program_address(
+            &[&program_address],
+            &BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
+        )?;
+
+        Ok(programdata_address)
+    }
+
+    /// Create and initialize a buffer large enough to hold `program_len` bytes.
+    /// Both `payer` and `buffer_address` must sign the transaction.
+    pub fn create_buffer(
+        payer: PublicKey,
+        buffer_address: PublicKey,
+        authority: PublicKey,
+        lamports: u64,
+        program_len: usize,
+    ) -> PoseidonResult<Vec<Instruction>> {
+        let create_account = SystemInstruction::CreateAccount {
+            lamports,
+            space: (BUFFER_METADATA_SIZE + program_len) as u64,
+            owner: BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
+        };
+
+        Ok(vec![
+            Instruction {
+                program_id: SYSTEM_PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(buffer_address, true),
+                ],
+                data: bincode::serialize(&create_account)?,
+            },
+            BpfLoaderUpgradeable::instruction(
+                UpgradeableLoaderInstruction::InitializeBuffer,
+                vec![
+                    AccountMeta::new(buffer_address, false),
+                    AccountMeta::new_readonly(authority, false),
+                ],
+            )?,
+        ])
+    }
+
+    pub fn write(
+        buffer_address: PublicKey,
+        authority: PublicKey,
+        offset: u32,
+        bytes: Vec<u8>,
+    ) -> PoseidonResult<Instruction> {
+        BpfLoaderUpgradeable::instruction(
+            UpgradeableLoaderInstruction::Write { offset, bytes },
+            vec![
+                AccountMeta::new(buffer_address, false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+        )
+    }
+
+    /// Split `program_data` into `Write` instructions of at most `chunk_size` bytes each.
+    /// Returns `PoseidonError::InvalidChunkSize` when `chunk_size` is zero and
+    /// `PoseidonError::IntegerOverflow` when an offset does not fit in a `u32`.
+    pub fn write_chunks(
+        buffer_address: PublicKey,
+        authority: PublicKey,
+        program_data: &[u8],
+        chunk_size: usize,
+    ) -> PoseidonResult<Vec<Instruction>> {
+        if chunk_size == 0 {
+            return Err(PoseidonError::InvalidChunkSize);
+        }
+
+        program_data
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(index, chunk)| {
+                BpfLoaderUpgradeable::write(
+                    buffer_address,
+                    authority,
+                    u32::try_from(index * chunk_size)?,
+                    chunk.to_vec(),
+                )
+            })
+            .collect()
+    }
+
+    /// The largest number of program bytes a single `Write` instruction can carry
+    /// while the transaction paid for by `payer` still fits in `PACKET_DATA_SIZE`
+    pub fn calculate_max_chunk_size(
+        payer: PublicKey,
+        buffer_address: PublicKey,
+        authority: PublicKey,
+    ) -> PoseidonResult<usize> {
+        let baseline_instruction =
+            BpfLoaderUpgradeable::write(buffer_address, authority, 0, Vec::default())?;
+
+        let mut message_builder = MessageBuilder::new();
+        message_builder
+            .add_instruction(baseline_instruction)
+            .add_payer(payer)
+            .build();
+        let mut message = Message::new();
+        message.build(message_builder)?;
+
+        let num_required_signatures = message.header.num_required_signatures as usize;
+        let mut transaction = Transaction::new(message);
+        transaction.signatures = vec![Signature::default(); num_required_signatures];
+
+        let transaction_size = bincode::serialized_size(&transaction)? as usize;
+
+        // The length prefix of the instruction data grows by one byte once
+        // the data is larger than 127 bytes
+        Ok(PACKET_DATA_SIZE
+            .saturating_sub(transaction_size)
+            .saturating_sub(1))
+    }
+
+    /// Create the program account and deploy the program written to `buffer_address`.
+    /// Both `payer` and `program_address` must sign the transaction.
+    pub fn deploy_with_max_program_len(
+        payer: PublicKey,
+        program_address: PublicKey,
+        buffer_address: PublicKey,
+        upgrade_authority: PublicKey,
+        program_lamports: u64,
+        max_data_len: usize,
+    ) -> PoseidonResult<Vec<Instruction>> {
+        let programdata_address = BpfLoaderUpgradeable::programdata_address(program_address)?;
+
+        let create_account = SystemInstruction::CreateAccount {
+            lamports: program_lamports,
+            space: PROGRAM_ACCOUNT_SIZE as u64,
+            owner: BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
+        };
+
+        Ok(vec![
+            Instruction {
+                program_id: SYSTEM_PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(program_address, true),
+                ],
+                data: bincode::serialize(&create_account)?,
+            },
+            BpfLoaderUpgradeable::instruction(
+                UpgradeableLoaderInstruction::DeployWithMaxDataLen {
+                    max_data_len: max_data_len as u64,
+                },
+                vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(programdata_address, false),
+                    AccountMeta::new(program_address, false),
+                    AccountMeta::new(buffer_address, false),
+                    AccountMeta::new_readonly(SYSVAR_RENT, false),
+                    AccountMeta::new_readonly(SYSVAR_CLOCK, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(upgrade_authority, true),
+                ],
+            )?,
+        ])
+    }
+
+    pub fn upgrade(
+        program_address: PublicKey,
+        buffer_address: PublicKey,
+        authority: PublicKey,
+        spill_address: PublicKey,
+    ) -> PoseidonResult<Instruction> {
+        let programdata_address = BpfLoaderUpgradeable::programdata_address(program_address)?;
+
+        BpfLoaderUpgradeable::instruction(
+            UpgradeableLoaderInstruction::Upgrade,
+            vec![
+                AccountMeta::new(programdata_address, false),
+                AccountMeta::new(program_address, false),
+                AccountMeta::new(buffer_address, false),
+                AccountMeta::new(spill_address, false),
+                AccountMeta::new_readonly(SYSVAR_RENT, false),
+                AccountMeta::new_readonly(SYSVAR_CLOCK, false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+        )
+    }
+
+    pub fn set_buffer_authority(
+        buffer_address: PublicKey,
+        current_authority: PublicKey,
+        new_authority: PublicKey,
+    ) -> PoseidonResult<Instruction> {
+        BpfLoaderUpgradeable::instruction(
+            UpgradeableLoaderInstruction::SetAuthority,
+            vec![
+                AccountMeta::new(buffer_address, false),
+                AccountMeta::new_readonly(current_authority, true),
+                AccountMeta::new_readonly(new_authority, false),
+            ],
+        )
+    }
+
+    /// Change the upgrade authority of a program.
+    /// Passing `None` as the `new_authority` makes the program immutable.
+    pub fn set_upgrade_authority(
+        program_address: PublicKey,
+        current_authority: PublicKey,
+        new_authority: Option<PublicKey>,
+    ) -> PoseidonResult<Instruction> {
+        let programdata_address = BpfLoaderUpgradeable::programdata_address(program_address)?;
+
+        let mut accounts = vec![
+            AccountMeta::new(programdata_address, false),
+            AccountMeta::new_readonly(current_authority, true),
+        ];
+        if let Some(new_authority) = new_authority {
+            accounts.push(AccountMeta::new_readonly(new_authority, false));
+        }
+
+        BpfLoaderUpgradeable::instruction(UpgradeableLoaderInstruction::SetAuthority, accounts)
+    }
+
+    /// Close a buffer or a ProgramData account. When closing a ProgramData account
+    /// the `program_address` it belongs to must be provided.
+    pub fn close(
+        close_address: PublicKey,
+        recipient_address: PublicKey,
+        authority: PublicKey,
+        program_address: Option<PublicKey>,
+    ) -> PoseidonResult<Instruction> {
+        let mut accounts = vec![
+            AccountMeta::new(close_address, false),
+            AccountMeta::new(recipient_address, false),
+            AccountMeta::new_readonly(authority, true),
+        ];
+        if let Some(program_address) = program_address {
+            accounts.push(AccountMeta::new(program_address, false));
+        }
+
+        BpfLoaderUpgradeable::instruction(UpgradeableLoaderInstruction::Close, accounts)
+    }
+
+    /// Grow the ProgramData account of `program_address` by `additional_bytes`.
+    /// The `payer` covers the extra rent when provided.
+    pub fn extend_program(
+        program_address: PublicKey,
+        payer: Option<PublicKey>,
+        additional_bytes: u32,
+    ) -> PoseidonResult<Instruction> {
+        let programdata_address = BpfLoaderUpgradeable::programdata_address(program_address)?;
+
+        let mut accounts = vec![
+            AccountMeta::new(programdata_address, false),
+            AccountMeta::new(program_address, false),
+        ];
+        if let Some(payer) = payer {
+            accounts.push(AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false));
+            accounts.push(AccountMeta::new(payer, true));
+        }
+
+        BpfLoaderUpgradeable::instruction(
+            UpgradeableLoaderInstruction::ExtendProgram { additional_bytes },
+            accounts,
+        )
+    }
+
+    fn instruction(
+        loader_instruction: UpgradeableLoaderInstruction,
+        accounts: Vec<AccountMeta>,
+    ) -> PoseidonResult<Instruction> {
+        Ok(Instruction {
+            program_id: BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
+            accounts,
+            data: bincode::serialize(&loader_instruction)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use crate::*;
+
+    const PAYER: PublicKey = [1; 32];
+    const BUFFER: PublicKey = [2; 32];
+    const AUTHORITY: PublicKey = [3; 32];
+    const PROGRAM: PublicKey = [4; 32];
+
+    #[test]
+    fn upgradeable_loader_instruction_layout() {
+        let create_buffer =
+            BpfLoaderUpgradeable::create_buffer(PAYER, BUFFER, AUTHORITY, 1_000, 10).unwrap();
+        let initialize_buffer = &create_buffer[1];
+        assert_eq!(
+            initialize_buffer.program_id,
+            BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+        );
+        assert_eq!(initialize_buffer.data, vec![0, 0, 0, 0]);
+        assert_eq!(initialize_buffer.accounts[0].pubkey, BUFFER);
+        assert_eq!(initialize_buffer.accounts[1].pubkey, AUTHORITY);
+        assert!(!initialize_buffer.accounts[1].is_signer);
+
+        let write = BpfLoaderUpgradeable::write(BUFFER, AUTHORITY, 258, vec![9, 8]).unwrap();
+        // discriminant + offset + length of the bytes + bytes
+        assert_eq!(
+            write.data,
+            vec![1, 0, 0, 0, 2, 1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 9, 8]
+        );
+        assert!(write.accounts[0].is_writable);
+        assert!(write.accounts[1].is_signer);
+
+        let deploy = BpfLoaderUpgradeable::deploy_with_max_program_len(
+            PAYER, PROGRAM, BUFFER, AUTHORITY, 1_000, 300,
+        )
+        .unwrap();
+        let deploy = &deploy[1];
+        assert_eq!(deploy.data, vec![2, 0, 0, 0, 44, 1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            deploy.accounts[1].pubkey,
+            BpfLoaderUpgradeable::programdata_address(PROGRAM).unwrap()
+        );
+        assert_eq!(deploy.accounts[4].pubkey, SYSVAR_RENT);
+        assert_eq!(deploy.accounts[5].pubkey, SYSVAR_CLOCK);
+        assert_eq!(deploy.accounts[6].pubkey, SYSTEM_PROGRAM_ID);
+        assert!(deploy.accounts[7].is_signer);
+
+        let upgrade = BpfLoaderUpgradeable::upgrade(PROGRAM, BUFFER, AUTHORITY, PAYER).unwrap();
+        assert_eq!(upgrade.data, vec![3, 0, 0, 0]);
+        assert_eq!(upgrade.accounts.len(), 7);
+        assert_eq!(upgrade.accounts[3].pubkey, PAYER);
+        assert!(upgrade.accounts[6].is_signer);
+    }
+
+    #[test]
+    fn write_chunks_offsets() {
+        let program_data = (0..10u8).collect::<Vec<u8>>();
+
+        let chunks =
+            BpfLoaderUpgradeable::write_chunks(BUFFER, AUTHORITY, &program_data, 4).unwrap();
+        let writes = chunks
+            .iter()
+            .map(|instruction| bincode::deserialize(&instruction.data).unwrap())
+            .collect::<Vec<UpgradeableLoaderInstruction>>();
+        assert_eq!(
+            writes,
+            vec![
+                UpgradeableLoaderInstruction::Write {
+                    offset: 0,
+                    bytes: vec![0, 1, 2, 3]
+                },
+                UpgradeableLoaderInstruction::Write {
+                    offset: 4,
+                    bytes: vec![4, 5, 6, 7]
+                },
+                UpgradeableLoaderInstruction::Write {
+                    offset: 8,
+                    bytes: vec![8, 9]
+                },
+            ]
+        );
+
+        assert!(matches!(
+            BpfLoaderUpgradeable::write_chunks(BUFFER, AUTHORITY, &program_data, 0),
+            Err(PoseidonError::InvalidChunkSize)
+        ));
+    }
+}
@@ -1,2 +1,5 @@
 pub const MAX_SEED_LEN: usize = 32;
+pub const MAX_SEEDS: usize = 16;
 pub const PDA_MARKER: &[u8; 21] = b"ProgramDerivedAddress";
+/// Maximum size of a serialized transaction that fits into a network packet
+pub const PACKET_DATA_SIZE: usize = 1232;
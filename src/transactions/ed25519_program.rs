@@ -1,4 +1,4 @@
-use crate::{Instruction, ED25519_PROGRAM_ID};
+use crate::{Instruction, PoseidonError, PoseidonResult, ED25519_PROGRAM_ID};
 use bytemuck::{bytes_of, Pod, Zeroable};
 
 pub const PUBKEY_SERIALIZED_SIZE: usize = 32;
@@ -7,18 +7,40 @@ pub const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
 // bytemuck requires structures to be aligned
 pub const SIGNATURE_OFFSETS_START: usize = 2;
 pub const DATA_START: usize = SIGNATURE_OFFSETS_SERIALIZED_SIZE + SIGNATURE_OFFSETS_START;
+/// The instruction index that refers to the ed25519 instruction itself
+pub const CURRENT_INSTRUCTION_INDEX: u16 = u16::MAX;
 
+/// ### Build a native ed25519 signature verification instruction
+/// The public key, signature and message given to `new()`, `add_signature()`
+/// and `build()` are verified first, followed by any added with `add_verification()`.
+/// Offsets pointing at data in other instructions of the transaction
+/// can be added with `add_external_offsets()`.
+///
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn example(first_public_key: [u8; 32], first_signature: [u8; 64], second_public_key: [u8; 32], second_signature: [u8; 64]) -> PoseidonResult<()> {
+/// let instruction = Ed25519ProgramCPI::new(first_public_key)
+///     .add_signature(first_signature)
+///     .add_verification(second_public_key, second_signature, b"second message")
+///     .build(b"first message")?;
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct Ed25519ProgramCPI {
     public_key: [u8; 32],
     signature: [u8; 64],
+    verifications: Vec<([u8; 32], [u8; 64], Vec<u8>)>,
+    external_offsets: Vec<Ed25519SignatureOffsets>,
 }
 
 impl Ed25519ProgramCPI {
     pub fn new(public_key: [u8; 32]) -> Self {
         Ed25519ProgramCPI {
-            public_key: public_key,
+            public_key,
             signature: [0u8; 64],
+            verifications: Vec::default(),
+            external_offsets: Vec::default(),
         }
     }
 
@@ -28,64 +50,279 @@ impl Ed25519ProgramCPI {
         self
     }
 
-    pub fn build(&self, message: &[u8]) -> Instruction {
+    /// Verify another `signature` of `message` by `public_key` in the same instruction
+    pub fn add_verification(
+        &mut self,
+        public_key: [u8; 32],
+        signature: [u8; 64],
+        message: &[u8],
+    ) -> &mut Self {
+        self.verifications
+            .push((public_key, signature, message.to_owned()));
+
+        self
+    }
+
+    /// Verify a signature whose public key, signature or message live in other
+    /// instructions of the transaction, as indicated by the instruction indexes of the `offsets`
+    pub fn add_external_offsets(&mut self, offsets: Ed25519SignatureOffsets) -> &mut Self {
+        self.external_offsets.push(offsets);
+
+        self
+    }
+
+    pub fn build(&self, message: &[u8]) -> PoseidonResult<Instruction> {
+        let mut verifications = vec![(self.public_key, self.signature, message.to_owned())];
+        verifications.extend(self.verifications.iter().cloned());
+
+        let num_signatures = u8::try_from(verifications.len() + self.external_offsets.len())
+            .map_err(|_| PoseidonError::InstructionDataTooLarge)?;
+        let data_start = SIGNATURE_OFFSETS_START
+            .saturating_add(usize::from(num_signatures) * SIGNATURE_OFFSETS_SERIALIZED_SIZE);
+
         let mut instruction_data = Vec::with_capacity(
-            DATA_START
-                .saturating_add(SIGNATURE_SERIALIZED_SIZE)
-                .saturating_add(PUBKEY_SERIALIZED_SIZE)
-                .saturating_add(message.len()),
+            data_start.saturating_add(
+                verifications
+                    .iter()
+                    .map(|(_, _, message)| {
+                        PUBKEY_SERIALIZED_SIZE + SIGNATURE_SERIALIZED_SIZE + message.len()
+                    })
+                    .sum(),
+            ),
         );
 
-        let num_signatures: u8 = 1;
-        let public_key_offset = DATA_START;
-        let signature_offset = public_key_offset.saturating_add(PUBKEY_SERIALIZED_SIZE);
-        let message_data_offset = signature_offset.saturating_add(SIGNATURE_SERIALIZED_SIZE);
-
         // add padding byte so that offset structure is aligned
         instruction_data.extend_from_slice(bytes_of(&[num_signatures, 0]));
 
-        let offsets = Ed25519SignatureOffsets {
-            signature_offset: signature_offset as u16,
-            signature_instruction_index: u16::MAX,
-            public_key_offset: public_key_offset as u16,
-            public_key_instruction_index: u16::MAX,
-            message_data_offset: message_data_offset as u16,
-            message_data_size: message.len() as u16,
-            message_instruction_index: u16::MAX,
-        };
-
-        instruction_data.extend_from_slice(bytes_of(&offsets));
+        let mut signature_data = Vec::<u8>::default();
+        for (public_key, signature, message) in &verifications {
+            let public_key_offset = data_start.saturating_add(signature_data.len());
+            let signature_offset = public_key_offset.saturating_add(PUBKEY_SERIALIZED_SIZE);
+            let message_data_offset = signature_offset.saturating_add(SIGNATURE_SERIALIZED_SIZE);
 
-        debug_assert_eq!(instruction_data.len(), public_key_offset);
+            let offsets = Ed25519SignatureOffsets {
+                signature_offset: Ed25519ProgramCPI::to_u16(signature_offset)?,
+                signature_instruction_index: CURRENT_INSTRUCTION_INDEX,
+                public_key_offset: Ed25519ProgramCPI::to_u16(public_key_offset)?,
+                public_key_instruction_index: CURRENT_INSTRUCTION_INDEX,
+                message_data_offset: Ed25519ProgramCPI::to_u16(message_data_offset)?,
+                message_data_size: Ed25519ProgramCPI::to_u16(message.len())?,
+                message_instruction_index: CURRENT_INSTRUCTION_INDEX,
+            };
 
-        instruction_data.extend_from_slice(&self.public_key);
+            instruction_data.extend_from_slice(bytes_of(&offsets));
 
-        debug_assert_eq!(instruction_data.len(), signature_offset);
+            signature_data.extend_from_slice(public_key);
+            signature_data.extend_from_slice(signature);
+            signature_data.extend_from_slice(message);
+        }
 
-        instruction_data.extend_from_slice(&self.signature);
+        self.external_offsets.iter().for_each(|offsets| {
+            instruction_data.extend_from_slice(bytes_of(offsets));
+        });
 
-        debug_assert_eq!(instruction_data.len(), message_data_offset);
+        debug_assert_eq!(instruction_data.len(), data_start);
 
-        instruction_data.extend_from_slice(message);
+        instruction_data.extend_from_slice(&signature_data);
 
-        Instruction {
+        Ok(Instruction {
             program_id: ED25519_PROGRAM_ID,
             accounts: vec![],
             data: instruction_data,
+        })
+    }
+
+    /// Read the signature offsets back from the data of an ed25519 instruction
+    pub fn parse(instruction_data: &[u8]) -> PoseidonResult<Vec<Ed25519SignatureOffsets>> {
+        let num_signatures = match instruction_data.first() {
+            Some(num_signatures) => *num_signatures as usize,
+            None => return Err(PoseidonError::InvalidInstructionData),
+        };
+
+        (0..num_signatures)
+            .map(|index| {
+                let start = SIGNATURE_OFFSETS_START
+                    .saturating_add(index.saturating_mul(SIGNATURE_OFFSETS_SERIALIZED_SIZE));
+                let end = start.saturating_add(SIGNATURE_OFFSETS_SERIALIZED_SIZE);
+
+                match instruction_data.get(start..end) {
+                    Some(offsets) => bytemuck::try_pod_read_unaligned(offsets)
+                        .map_err(|_| PoseidonError::InvalidInstructionData),
+                    None => Err(PoseidonError::InvalidInstructionData),
+                }
+            })
+            .collect()
+    }
+
+    /// Verify every signature of an ed25519 instruction locally.
+    /// `instructions` holds the data of each instruction in the transaction and is
+    /// only consulted for offsets that point outside of `instruction_data`.
+    pub fn verify(instruction_data: &[u8], instructions: &[&[u8]]) -> PoseidonResult<()> {
+        Ed25519ProgramCPI::parse(instruction_data)?
+            .iter()
+            .try_for_each(|offsets| {
+                let public_key = Ed25519ProgramCPI::get_data_slice(
+                    instruction_data,
+                    instructions,
+                    offsets.public_key_instruction_index,
+                    offsets.public_key_offset,
+                    PUBKEY_SERIALIZED_SIZE,
+                )?;
+                let signature = Ed25519ProgramCPI::get_data_slice(
+                    instruction_data,
+                    instructions,
+                    offsets.signature_instruction_index,
+                    offsets.signature_offset,
+                    SIGNATURE_SERIALIZED_SIZE,
+                )?;
+                let message = Ed25519ProgramCPI::get_data_slice(
+                    instruction_data,
+                    instructions,
+                    offsets.message_instruction_index,
+                    offsets.message_data_offset,
+                    offsets.message_data_size as usize,
+                )?;
+
+                let public_key = ed25519_dalek::PublicKey::from_bytes(public_key)
+                    .map_err(|_| PoseidonError::InvalidInstructionData)?;
+                let signature = ed25519_dalek::Signature::from_bytes(signature)
+                    .map_err(|_| PoseidonError::InvalidInstructionData)?;
+
+                public_key
+                    .verify_strict(message, &signature)
+                    .map_err(|_| PoseidonError::SignatureVerificationFailed)
+            })
+    }
+
+    fn get_data_slice<'d>(
+        instruction_data: &'d [u8],
+        instructions: &[&'d [u8]],
+        instruction_index: u16,
+        offset_start: u16,
+        size: usize,
+    ) -> PoseidonResult<&'d [u8]> {
+        let data = if instruction_index == CURRENT_INSTRUCTION_INDEX {
+            instruction_data
+        } else {
+            match instructions.get(instruction_index as usize) {
+                Some(data) => *data,
+                None => return Err(PoseidonError::InvalidInstructionData),
+            }
+        };
+
+        let start = offset_start as usize;
+        match data.get(start..start.saturating_add(size)) {
+            Some(slice) => Ok(slice),
+            None => Err(PoseidonError::InvalidInstructionData),
         }
     }
+
+    fn to_u16(value: usize) -> PoseidonResult<u16> {
+        u16::try_from(value).map_err(|_| PoseidonError::InstructionDataTooLarge)
+    }
 }
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
-#[derive(Default, Debug, Copy, Clone, Zeroable, Pod, BorshDeserialize, BorshSerialize)]
+#[derive(
+    Default, Debug, Copy, Clone, PartialEq, Eq, Zeroable, Pod, BorshDeserialize, BorshSerialize,
+)]
 #[repr(C)]
 pub struct Ed25519SignatureOffsets {
-    signature_offset: u16,             // offset to ed25519 signature of 64 bytes
-    signature_instruction_index: u16,  // instruction index to find signature
-    public_key_offset: u16,            // offset to public key of 32 bytes
-    public_key_instruction_index: u16, // instruction index to find public key
-    message_data_offset: u16,          // offset to start of message data
-    message_data_size: u16,            // size of message data
-    message_instruction_index: u16,    // index of instruction data to get message data
+    pub signature_offset: u16, // offset to ed25519 signature of 64 bytes
+    pub signature_instruction_index: u16, // instruction index to find signature
+    pub public_key_offset: u16, // offset to public key of 32 bytes
+    pub public_key_instruction_index: u16, // instruction index to find public key
+    pub message_data_offset: u16, // offset to start of message data
+    pub message_data_size: u16, // size of message data
+    pub message_instruction_index: u16, // index of instruction data to get message data
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::test_utils::keypair;
+    use ed25519_dalek::Signer;
+
+    #[test]
+    fn verify_multiple_signatures() {
+        let first = keypair(1);
+        let second = keypair(2);
+        let external = keypair(3);
+
+        // Data of another instruction in the transaction holding a public key,
+        // a signature and the signed message back to back
+        let external_message = b"external message";
+        let mut external_data = external.public.to_bytes().to_vec();
+        external_data.extend_from_slice(&external.sign(external_message).to_bytes());
+        external_data.extend_from_slice(external_message);
+
+        let instruction = Ed25519ProgramCPI::new(first.public.to_bytes())
+            .add_signature(first.sign(b"first message").to_bytes())
+            .add_verification(
+                second.public.to_bytes(),
+                second.sign(b"second message").to_bytes(),
+                b"second message",
+            )
+            .add_external_offsets(Ed25519SignatureOffsets {
+                signature_offset: 32,
+                signature_instruction_index: 0,
+                public_key_offset: 0,
+                public_key_instruction_index: 0,
+                message_data_offset: 96,
+                message_data_size: u16::try_from(external_message.len()).unwrap(),
+                message_instruction_index: 0,
+            })
+            .build(b"first message")
+            .unwrap();
+
+        let offsets = Ed25519ProgramCPI::parse(&instruction.data).unwrap();
+        assert_eq!(offsets.len(), 3);
+        assert_eq!(
+            offsets[0].public_key_offset as usize,
+            SIGNATURE_OFFSETS_START + 3 * 14
+        );
+        assert_eq!(offsets[1].message_data_size, 14);
+        assert_eq!(offsets[2].signature_instruction_index, 0);
+
+        assert!(Ed25519ProgramCPI::verify(&instruction.data, &[&external_data]).is_ok());
+
+        let mut tampered = instruction.data.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            Ed25519ProgramCPI::verify(&tampered, &[&external_data]),
+            Err(PoseidonError::SignatureVerificationFailed)
+        ));
+        assert!(Ed25519ProgramCPI::verify(&instruction.data, &[]).is_err());
+    }
+
+    #[test]
+    fn offsets_overflow() {
+        let signer = keypair(1);
+
+        let long_message = vec![0u8; usize::from(u16::MAX) + 1];
+        assert!(matches!(
+            Ed25519ProgramCPI::new(signer.public.to_bytes())
+                .add_signature(signer.sign(&long_message).to_bytes())
+                .build(&long_message),
+            Err(PoseidonError::InstructionDataTooLarge)
+        ));
+
+        // The message of the first verification pushes the offsets of the second past `u16::MAX`
+        let mut builder = Ed25519ProgramCPI::new(signer.public.to_bytes());
+        builder.add_verification(signer.public.to_bytes(), [0; 64], b"second message");
+        assert!(matches!(
+            builder.build(&vec![0u8; usize::from(u16::MAX) - 200]),
+            Err(PoseidonError::InstructionDataTooLarge)
+        ));
+
+        let mut builder = Ed25519ProgramCPI::new(signer.public.to_bytes());
+        (0..255).for_each(|_| {
+            builder.add_external_offsets(Ed25519SignatureOffsets::default());
+        });
+        assert!(matches!(
+            builder.build(b"message"),
+            Err(PoseidonError::InstructionDataTooLarge)
+        ));
+    }
 }
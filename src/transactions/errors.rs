@@ -44,6 +44,13 @@ pub enum TransactionError {
     InvalidRentPayingAccount,
     WouldExceedMaxVoteCostLimit,
     WouldExceedAccountDataTotalLimit,
+    DuplicateInstruction(u8),
+    InsufficientFundsForRent { account_index: u8 },
+    MaxLoadedAccountsDataSizeExceeded,
+    InvalidLoadedAccountsDataSizeLimit,
+    ResanitizationNeeded,
+    ProgramExecutionTemporarilyRestricted { account_index: u8 },
+    UnbalancedTransaction,
 }
 
 #[derive(
@@ -111,4 +118,7 @@ pub enum InstructionError {
     IllegalOwner,
     MaxAccountsDataSizeExceeded,
     ActiveVoteAccountClose,
+    MaxAccountsDataAllocationsExceeded,
+    MaxInstructionTraceLengthExceeded,
+    BuiltinProgramsMustConsumeComputeUnits,
 }
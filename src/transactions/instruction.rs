@@ -39,7 +39,7 @@ impl Instruction {
         &mut self,
         program_id: BorrowedBase58PublicKey,
     ) -> PoseidonResult<&mut Self> {
-        let program_id = Utilities::base58_to_u32_array(&program_id)?;
+        let program_id = Utilities::base58_to_u32_array(program_id)?;
 
         self.program_id = program_id;
 
@@ -66,6 +66,7 @@ impl Instruction {
         self
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn borrow(&self) -> &Self {
         self
     }
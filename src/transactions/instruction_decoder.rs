@@ -0,0 +1,854 @@
+use crate::{
+    CompiledInstruction, Ed25519ProgramCPI, Message, PoseidonError, PoseidonResult, PublicKey,
+    SystemInstruction, Transaction, COMPUTE_BUDGET_PROGRAM_ID, ED25519_PROGRAM_ID, MEMO_PROGRAM_ID,
+    SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Decodes the instructions of a single program into named instructions.
+/// Implement it to decode the instructions of your own program.
+/// ```no_run
+/// # use borsh::BorshDeserialize;
+/// # use poseidon_client::*;
+/// # use serde::Serialize;
+/// # const COUNTER_PROGRAM_ID: PublicKey = [7; 32];
+/// #[derive(BorshDeserialize, Serialize)]
+/// enum CounterInstruction {
+///     Increment { by: u64 },
+/// }
+///
+/// struct CounterDecoder;
+///
+/// impl InstructionDecoder for CounterDecoder {
+///     fn program_id(&self) -> PublicKey {
+///         COUNTER_PROGRAM_ID
+///     }
+///
+///     fn program_name(&self) -> &str {
+///         "counter"
+///     }
+///
+///     fn decode(&self, data: &[u8], accounts: &[PublicKey]) -> PoseidonResult<DecodedInstruction> {
+///         let instruction = CounterInstruction::try_from_slice(data)
+///             .map_err(|_| PoseidonError::InvalidInstructionData)?;
+///
+///         Ok(DecodedInstruction::new("increment", &["counter", "authority"], accounts)
+///             .with_args(serde_json::to_value(instruction)?))
+///     }
+/// }
+///
+/// # fn example(transaction: Transaction) -> PoseidonResult<()> {
+/// let mut registry = InstructionDecoderRegistry::new();
+/// registry.add_decoder(CounterDecoder);
+/// let instructions = registry.decode_transaction(&transaction)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait InstructionDecoder: Send + Sync {
+    /// The program the decoder is registered for
+    fn program_id(&self) -> PublicKey;
+
+    fn program_name(&self) -> &str;
+
+    /// Decode the instruction `data`. `accounts` are the public keys passed to
+    /// the instruction, in order.
+    fn decode(&self, data: &[u8], accounts: &[PublicKey]) -> PoseidonResult<DecodedInstruction>;
+}
+
+/// An instruction decoded by an `InstructionDecoder`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecodedInstruction {
+    pub name: String,
+    pub accounts: Vec<DecodedAccount>,
+    /// The arguments of the instruction, `Null` if it has none
+    pub args: serde_json::Value,
+}
+
+impl DecodedInstruction {
+    /// Name the `accounts` in order. Accounts without a name, like the
+    /// signers of a multisig, are named `remaining`.
+    pub fn new(name: &str, account_names: &[&str], accounts: &[PublicKey]) -> Self {
+        let accounts = accounts
+            .iter()
+            .enumerate()
+            .map(|(index, public_key)| DecodedAccount {
+                name: account_names
+                    .get(index)
+                    .copied()
+                    .unwrap_or("remaining")
+                    .to_owned(),
+                public_key: *public_key,
+            })
+            .collect();
+
+        DecodedInstruction {
+            name: name.to_owned(),
+            accounts,
+            args: serde_json::Value::Null,
+        }
+    }
+
+    pub fn with_args(mut self, args: serde_json::Value) -> Self {
+        self.args = args;
+
+        self
+    }
+
+    /// The public key of the account named `name`
+    pub fn account(&self, name: &str) -> Option<PublicKey> {
+        self.accounts
+            .iter()
+            .find(|account| account.name == name)
+            .map(|account| account.public_key)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecodedAccount {
+    pub name: String,
+    pub public_key: PublicKey,
+}
+
+/// An instruction of a transaction with its accounts resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedInstruction {
+    Decoded {
+        program_id: PublicKey,
+        program: String,
+        instruction: DecodedInstruction,
+    },
+    /// No decoder is registered for the program
+    Unknown {
+        program_id: PublicKey,
+        accounts: Vec<PublicKey>,
+        data: Vec<u8>,
+    },
+    /// The decoder of the program could not decode the instruction
+    Failed {
+        program_id: PublicKey,
+        program: String,
+        accounts: Vec<PublicKey>,
+        data: Vec<u8>,
+        /// The error returned by the decoder
+        error: String,
+    },
+}
+
+impl ResolvedInstruction {
+    pub fn program_id(&self) -> PublicKey {
+        match self {
+            ResolvedInstruction::Decoded { program_id, .. } => *program_id,
+            ResolvedInstruction::Unknown { program_id, .. } => *program_id,
+            ResolvedInstruction::Failed { program_id, .. } => *program_id,
+        }
+    }
+}
+
+/// ### Decode the instructions of compiled transactions
+/// `new()` registers the decoders of the System, Compute Budget, Ed25519,
+/// Memo and SPL Token programs. Registering a decoder for a program that
+/// already has one replaces it.
+pub struct InstructionDecoderRegistry {
+    decoders: HashMap<PublicKey, Box<dyn InstructionDecoder>>,
+}
+
+impl Default for InstructionDecoderRegistry {
+    fn default() -> Self {
+        InstructionDecoderRegistry::new()
+    }
+}
+
+impl InstructionDecoderRegistry {
+    pub fn new() -> Self {
+        let mut registry = InstructionDecoderRegistry::empty();
+        registry
+            .add_decoder(SystemDecoder)
+            .add_decoder(ComputeBudgetDecoder)
+            .add_decoder(Ed25519Decoder)
+            .add_decoder(MemoDecoder)
+            .add_decoder(SplTokenDecoder);
+
+        registry
+    }
+
+    /// A registry without any decoders
+    pub fn empty() -> Self {
+        InstructionDecoderRegistry {
+            decoders: HashMap::default(),
+        }
+    }
+
+    pub fn add_decoder(&mut self, decoder: impl InstructionDecoder + 'static) -> &mut Self {
+        self.decoders
+            .insert(decoder.program_id(), Box::new(decoder));
+
+        self
+    }
+
+    pub fn remove_decoder(&mut self, program_id: &PublicKey) -> &mut Self {
+        self.decoders.remove(program_id);
+
+        self
+    }
+
+    pub fn decoder(&self, program_id: &PublicKey) -> Option<&dyn InstructionDecoder> {
+        self.decoders
+            .get(program_id)
+            .map(|decoder| decoder.as_ref())
+    }
+
+    pub fn decode_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> PoseidonResult<Vec<ResolvedInstruction>> {
+        self.decode_message(&transaction.message)
+    }
+
+    pub fn decode_message(&self, message: &Message) -> PoseidonResult<Vec<ResolvedInstruction>> {
+        message
+            .instructions
+            .iter()
+            .map(|instruction| self.decode_instruction(&message.account_keys, instruction))
+            .collect()
+    }
+
+    /// Resolve the program and account indexes of `instruction` against
+    /// `account_keys` and decode it. Only indexes missing from `account_keys`
+    /// are errors, an instruction the decoder rejects is `ResolvedInstruction::Failed`.
+    pub fn decode_instruction(
+        &self,
+        account_keys: &[PublicKey],
+        instruction: &CompiledInstruction,
+    ) -> PoseidonResult<ResolvedInstruction> {
+        let program_id = *account_keys
+            .get(instruction.program_id_index as usize)
+            .ok_or(PoseidonError::ProgramIdNotFound)?;
+
+        let accounts = instruction
+            .accounts
+            .iter()
+            .map(|index| {
+                account_keys
+                    .get(*index as usize)
+                    .copied()
+                    .ok_or(PoseidonError::AccountIndexNotFoundInMessageAccounts)
+            })
+            .collect::<PoseidonResult<Vec<PublicKey>>>()?;
+
+        match self.decoders.get(&program_id) {
+            Some(decoder) => match decoder.decode(&instruction.data, &accounts) {
+                Ok(decoded) => Ok(ResolvedInstruction::Decoded {
+                    program_id,
+                    program: decoder.program_name().to_owned(),
+                    instruction: decoded,
+                }),
+                Err(error) => Ok(ResolvedInstruction::Failed {
+                    program_id,
+                    program: decoder.program_name().to_owned(),
+                    accounts,
+                    data: instruction.data.clone(),
+                    error: error.to_string(),
+                }),
+            },
+            None => Ok(ResolvedInstruction::Unknown {
+                program_id,
+                accounts,
+                data: instruction.data.clone(),
+            }),
+        }
+    }
+}
+
+fn base58(public_key: &PublicKey) -> String {
+    bs58::encode(public_key).into_string()
+}
+
+// Reads the fixed size fields of instruction data in order
+struct InstructionData<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> InstructionData<'a> {
+    fn take(&mut self, len: usize) -> PoseidonResult<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(PoseidonError::InvalidInstructionData);
+        }
+
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> PoseidonResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> PoseidonResult<u32> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.take(4)?);
+
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn u64(&mut self) -> PoseidonResult<u64> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.take(8)?);
+
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn public_key(&mut self) -> PoseidonResult<PublicKey> {
+        let mut public_key = [0u8; 32];
+        public_key.copy_from_slice(self.take(32)?);
+
+        Ok(public_key)
+    }
+
+    // A `COption<Pubkey>` as packed by the SPL Token program
+    fn optional_public_key(&mut self) -> PoseidonResult<Option<String>> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(base58(&self.public_key()?))),
+            _ => Err(PoseidonError::InvalidInstructionData),
+        }
+    }
+}
+
+pub struct SystemDecoder;
+
+impl InstructionDecoder for SystemDecoder {
+    fn program_id(&self) -> PublicKey {
+        SYSTEM_PROGRAM_ID
+    }
+
+    fn program_name(&self) -> &str {
+        "system"
+    }
+
+    fn decode(&self, data: &[u8], accounts: &[PublicKey]) -> PoseidonResult<DecodedInstruction> {
+        let instruction = bincode::deserialize::<SystemInstruction>(data)
+            .map_err(|_| PoseidonError::InvalidInstructionData)?;
+
+        let decoded = match instruction {
+            SystemInstruction::CreateAccount {
+                lamports,
+                space,
+                owner,
+            } => DecodedInstruction::new("createAccount", &["source", "newAccount"], accounts)
+                .with_args(json!({
+                    "lamports": lamports,
+                    "space": space,
+                    "owner": base58(&owner),
+                })),
+            SystemInstruction::Assign { owner } => {
+                DecodedInstruction::new("assign", &["account"], accounts)
+                    .with_args(json!({ "owner": base58(&owner) }))
+            }
+            SystemInstruction::Transfer { lamports } => {
+                DecodedInstruction::new("transfer", &["source", "destination"], accounts)
+                    .with_args(json!({ "lamports": lamports }))
+            }
+            SystemInstruction::CreateAccountWithSeed {
+                base,
+                seed,
+                lamports,
+                space,
+                owner,
+            } => DecodedInstruction::new(
+                "createAccountWithSeed",
+                &["source", "newAccount", "base"],
+                accounts,
+            )
+            .with_args(json!({
+                "base": base58(&base),
+                "seed": seed,
+                "lamports": lamports,
+                "space": space,
+                "owner": base58(&owner),
+            })),
+            SystemInstruction::AdvanceNonceAccount => DecodedInstruction::new(
+                "advanceNonce",
+                &["nonceAccount", "recentBlockhashesSysvar", "nonceAuthority"],
+                accounts,
+            ),
+            SystemInstruction::WithdrawNonceAccount(lamports) => DecodedInstruction::new(
+                "withdrawFromNonce",
+                &[
+                    "nonceAccount",
+                    "destination",
+                    "recentBlockhashesSysvar",
+                    "rentSysvar",
+                    "nonceAuthority",
+                ],
+                accounts,
+            )
+            .with_args(json!({ "lamports": lamports })),
+            SystemInstruction::InitializeNonceAccount(nonce_authority) => DecodedInstruction::new(
+                "initializeNonce",
+                &["nonceAccount", "recentBlockhashesSysvar", "rentSysvar"],
+                accounts,
+            )
+            .with_args(json!({ "nonceAuthority": base58(&nonce_authority) })),
+            SystemInstruction::AuthorizeNonceAccount(new_authorized) => DecodedInstruction::new(
+                "authorizeNonce",
+                &["nonceAccount", "nonceAuthority"],
+                accounts,
+            )
+            .with_args(json!({ "newAuthorized": base58(&new_authorized) })),
+            SystemInstruction::Allocate { space } => {
+                DecodedInstruction::new("allocate", &["account"], accounts)
+                    .with_args(json!({ "space": space }))
+            }
+            SystemInstruction::AllocateWithSeed {
+                base,
+                seed,
+                space,
+                owner,
+            } => DecodedInstruction::new("allocateWithSeed", &["account", "base"], accounts)
+                .with_args(json!({
+                    "base": base58(&base),
+                    "seed": seed,
+                    "space": space,
+                    "owner": base58(&owner),
+                })),
+            SystemInstruction::AssignWithSeed { base, seed, owner } => {
+                DecodedInstruction::new("assignWithSeed", &["account", "base"], accounts).with_args(
+                    json!({
+                        "base": base58(&base),
+                        "seed": seed,
+                        "owner": base58(&owner),
+                    }),
+                )
+            }
+            SystemInstruction::TransferWithSeed {
+                lamports,
+                from_seed,
+                from_owner,
+            } => DecodedInstruction::new(
+                "transferWithSeed",
+                &["source", "sourceBase", "destination"],
+                accounts,
+            )
+            .with_args(json!({
+                "lamports": lamports,
+                "sourceSeed": from_seed,
+                "sourceOwner": base58(&from_owner),
+            })),
+        };
+
+        Ok(decoded)
+    }
+}
+
+pub struct ComputeBudgetDecoder;
+
+impl InstructionDecoder for ComputeBudgetDecoder {
+    fn program_id(&self) -> PublicKey {
+        COMPUTE_BUDGET_PROGRAM_ID
+    }
+
+    fn program_name(&self) -> &str {
+        "compute-budget"
+    }
+
+    fn decode(&self, data: &[u8], accounts: &[PublicKey]) -> PoseidonResult<DecodedInstruction> {
+        let mut data = InstructionData { data };
+
+        let decoded = match data.u8()? {
+            0 => DecodedInstruction::new("requestUnits", &[], accounts).with_args(json!({
+                "units": data.u32()?,
+                "additionalFee": data.u32()?,
+            })),
+            1 => DecodedInstruction::new("requestHeapFrame", &[], accounts)
+                .with_args(json!({ "bytes": data.u32()? })),
+            2 => DecodedInstruction::new("setComputeUnitLimit", &[], accounts)
+                .with_args(json!({ "units": data.u32()? })),
+            3 => DecodedInstruction::new("setComputeUnitPrice", &[], accounts)
+                .with_args(json!({ "microLamports": data.u64()? })),
+            4 => DecodedInstruction::new("setLoadedAccountsDataSizeLimit", &[], accounts)
+                .with_args(json!({ "bytes": data.u32()? })),
+            _ => return Err(PoseidonError::InvalidInstructionData),
+        };
+
+        Ok(decoded)
+    }
+}
+
+pub struct Ed25519Decoder;
+
+impl InstructionDecoder for Ed25519Decoder {
+    fn program_id(&self) -> PublicKey {
+        ED25519_PROGRAM_ID
+    }
+
+    fn program_name(&self) -> &str {
+        "ed25519"
+    }
+
+    fn decode(&self, data: &[u8], accounts: &[PublicKey]) -> PoseidonResult<DecodedInstruction> {
+        let offsets = Ed25519ProgramCPI::parse(data)?
+            .iter()
+            .map(|offsets| {
+                json!({
+                    "signatureOffset": offsets.signature_offset,
+                    "signatureInstructionIndex": offsets.signature_instruction_index,
+                    "publicKeyOffset": offsets.public_key_offset,
+                    "publicKeyInstructionIndex": offsets.public_key_instruction_index,
+                    "messageDataOffset": offsets.message_data_offset,
+                    "messageDataSize": offsets.message_data_size,
+                    "messageInstructionIndex": offsets.message_instruction_index,
+                })
+            })
+            .collect::<Vec<serde_json::Value>>();
+
+        Ok(DecodedInstruction::new("verify", &[], accounts)
+            .with_args(json!({ "signatures": offsets })))
+    }
+}
+
+pub struct MemoDecoder;
+
+impl InstructionDecoder for MemoDecoder {
+    fn program_id(&self) -> PublicKey {
+        MEMO_PROGRAM_ID
+    }
+
+    fn program_name(&self) -> &str {
+        "spl-memo"
+    }
+
+    // The accounts of a memo are the signers that must sign it
+    fn decode(&self, data: &[u8], accounts: &[PublicKey]) -> PoseidonResult<DecodedInstruction> {
+        let memo = core::str::from_utf8(data).map_err(|_| PoseidonError::InvalidInstructionData)?;
+
+        Ok(DecodedInstruction::new("memo", &[], accounts).with_args(json!({ "memo": memo })))
+    }
+}
+
+/// Decodes the instructions of the SPL Token program. The signers of a multisig
+/// authority follow the named accounts and are named `remaining`.
+pub struct SplTokenDecoder;
+
+impl InstructionDecoder for SplTokenDecoder {
+    fn program_id(&self) -> PublicKey {
+        TOKEN_PROGRAM_ID
+    }
+
+    fn program_name(&self) -> &str {
+        "spl-token"
+    }
+
+    fn decode(&self, data: &[u8], accounts: &[PublicKey]) -> PoseidonResult<DecodedInstruction> {
+        let mut data = InstructionData { data };
+
+        let decoded = match data.u8()? {
+            0 => DecodedInstruction::new("initializeMint", &["mint", "rentSysvar"], accounts)
+                .with_args(json!({
+                    "decimals": data.u8()?,
+                    "mintAuthority": base58(&data.public_key()?),
+                    "freezeAuthority": data.optional_public_key()?,
+                })),
+            1 => DecodedInstruction::new(
+                "initializeAccount",
+                &["account", "mint", "owner", "rentSysvar"],
+                accounts,
+            ),
+            2 => {
+                DecodedInstruction::new("initializeMultisig", &["multisig", "rentSysvar"], accounts)
+                    .with_args(json!({ "m": data.u8()? }))
+            }
+            3 => DecodedInstruction::new(
+                "transfer",
+                &["source", "destination", "authority"],
+                accounts,
+            )
+            .with_args(json!({ "amount": data.u64()? })),
+            4 => DecodedInstruction::new("approve", &["source", "delegate", "owner"], accounts)
+                .with_args(json!({ "amount": data.u64()? })),
+            5 => DecodedInstruction::new("revoke", &["source", "owner"], accounts),
+            6 => {
+                let authority_type = match data.u8()? {
+                    0 => "mintTokens",
+                    1 => "freezeAccount",
+                    2 => "accountOwner",
+                    3 => "closeAccount",
+                    _ => return Err(PoseidonError::InvalidInstructionData),
+                };
+
+                DecodedInstruction::new("setAuthority", &["account", "authority"], accounts)
+                    .with_args(json!({
+                        "authorityType": authority_type,
+                        "newAuthority": data.optional_public_key()?,
+                    }))
+            }
+            7 => DecodedInstruction::new("mintTo", &["mint", "account", "mintAuthority"], accounts)
+                .with_args(json!({ "amount": data.u64()? })),
+            8 => DecodedInstruction::new("burn", &["account", "mint", "authority"], accounts)
+                .with_args(json!({ "amount": data.u64()? })),
+            9 => DecodedInstruction::new(
+                "closeAccount",
+                &["account", "destination", "owner"],
+                accounts,
+            ),
+            10 => DecodedInstruction::new(
+                "freezeAccount",
+                &["account", "mint", "freezeAuthority"],
+                accounts,
+            ),
+            11 => DecodedInstruction::new(
+                "thawAccount",
+                &["account", "mint", "freezeAuthority"],
+                accounts,
+            ),
+            12 => DecodedInstruction::new(
+                "transferChecked",
+                &["source", "mint", "destination", "authority"],
+                accounts,
+            )
+            .with_args(json!({
+                "amount": data.u64()?,
+                "decimals": data.u8()?,
+            })),
+            13 => DecodedInstruction::new(
+                "approveChecked",
+                &["source", "mint", "delegate", "owner"],
+                accounts,
+            )
+            .with_args(json!({
+                "amount": data.u64()?,
+                "decimals": data.u8()?,
+            })),
+            14 => DecodedInstruction::new(
+                "mintToChecked",
+                &["mint", "account", "mintAuthority"],
+                accounts,
+            )
+            .with_args(json!({
+                "amount": data.u64()?,
+                "decimals": data.u8()?,
+            })),
+            15 => {
+                DecodedInstruction::new("burnChecked", &["account", "mint", "authority"], accounts)
+                    .with_args(json!({
+                        "amount": data.u64()?,
+                        "decimals": data.u8()?,
+                    }))
+            }
+            16 => DecodedInstruction::new(
+                "initializeAccount2",
+                &["account", "mint", "rentSysvar"],
+                accounts,
+            )
+            .with_args(json!({ "owner": base58(&data.public_key()?) })),
+            17 => DecodedInstruction::new("syncNative", &["account"], accounts),
+            18 => DecodedInstruction::new("initializeAccount3", &["account", "mint"], accounts)
+                .with_args(json!({ "owner": base58(&data.public_key()?) })),
+            19 => DecodedInstruction::new("initializeMultisig2", &["multisig"], accounts)
+                .with_args(json!({ "m": data.u8()? })),
+            20 => {
+                DecodedInstruction::new("initializeMint2", &["mint"], accounts).with_args(json!({
+                    "decimals": data.u8()?,
+                    "mintAuthority": base58(&data.public_key()?),
+                    "freezeAuthority": data.optional_public_key()?,
+                }))
+            }
+            21 => DecodedInstruction::new("getAccountDataSize", &["mint"], accounts),
+            22 => DecodedInstruction::new("initializeImmutableOwner", &["account"], accounts),
+            23 => DecodedInstruction::new("amountToUiAmount", &["mint"], accounts)
+                .with_args(json!({ "amount": data.u64()? })),
+            24 => {
+                let ui_amount = core::str::from_utf8(data.data)
+                    .map_err(|_| PoseidonError::InvalidInstructionData)?;
+
+                DecodedInstruction::new("uiAmountToAmount", &["mint"], accounts)
+                    .with_args(json!({ "uiAmount": ui_amount }))
+            }
+            _ => return Err(PoseidonError::InvalidInstructionData),
+        };
+
+        Ok(decoded)
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::MessageHeader;
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    const PAYER: PublicKey = [1; 32];
+    const RECIPIENT: PublicKey = [2; 32];
+    const MINT: PublicKey = [3; 32];
+    const COUNTER_PROGRAM_ID: PublicKey = [9; 32];
+
+    #[derive(BorshSerialize, BorshDeserialize, Serialize)]
+    enum CounterInstruction {
+        Increment { by: u64 },
+    }
+
+    struct CounterDecoder;
+
+    impl InstructionDecoder for CounterDecoder {
+        fn program_id(&self) -> PublicKey {
+            COUNTER_PROGRAM_ID
+        }
+
+        fn program_name(&self) -> &str {
+            "counter"
+        }
+
+        fn decode(
+            &self,
+            data: &[u8],
+            accounts: &[PublicKey],
+        ) -> PoseidonResult<DecodedInstruction> {
+            let instruction = CounterInstruction::try_from_slice(data)
+                .map_err(|_| PoseidonError::InvalidInstructionData)?;
+
+            Ok(DecodedInstruction::new("increment", &["counter"], accounts)
+                .with_args(serde_json::to_value(instruction).unwrap()))
+        }
+    }
+
+    fn message() -> Message {
+        let mut token_transfer = vec![12];
+        token_transfer.extend_from_slice(&2_500_000u64.to_le_bytes());
+        token_transfer.push(6);
+
+        let mut compute_unit_price = vec![3];
+        compute_unit_price.extend_from_slice(&10_000u64.to_le_bytes());
+
+        Message {
+            header: MessageHeader::default(),
+            account_keys: vec![
+                PAYER,
+                RECIPIENT,
+                MINT,
+                SYSTEM_PROGRAM_ID,
+                COMPUTE_BUDGET_PROGRAM_ID,
+                MEMO_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+                COUNTER_PROGRAM_ID,
+            ],
+            recent_blockhash: [0; 32],
+            instructions: vec![
+                CompiledInstruction {
+                    program_id_index: 4,
+                    accounts: vec![],
+                    data: compute_unit_price,
+                },
+                CompiledInstruction {
+                    program_id_index: 3,
+                    accounts: vec![0, 1],
+                    data: bincode::serialize(&SystemInstruction::Transfer { lamports: 5000 })
+                        .unwrap(),
+                },
+                CompiledInstruction {
+                    program_id_index: 5,
+                    accounts: vec![0],
+                    data: b"gm".to_vec(),
+                },
+                CompiledInstruction {
+                    program_id_index: 6,
+                    accounts: vec![1, 2, 1, 0],
+                    data: token_transfer,
+                },
+                CompiledInstruction {
+                    program_id_index: 7,
+                    accounts: vec![1],
+                    data: CounterInstruction::Increment { by: 3 }
+                        .try_to_vec()
+                        .unwrap(),
+                },
+            ],
+        }
+    }
+
+    fn decoded(instruction: &ResolvedInstruction) -> (&str, &DecodedInstruction) {
+        match instruction {
+            ResolvedInstruction::Decoded {
+                program,
+                instruction,
+                ..
+            } => (program, instruction),
+            _ => panic!("Expected a decoded instruction"),
+        }
+    }
+
+    #[test]
+    fn decode_builtin_programs() {
+        let registry = InstructionDecoderRegistry::new();
+        let instructions = registry.decode_message(&message()).unwrap();
+
+        let (program, compute_unit_price) = decoded(&instructions[0]);
+        assert_eq!(program, "compute-budget");
+        assert_eq!(compute_unit_price.name, "setComputeUnitPrice");
+        assert_eq!(compute_unit_price.args["microLamports"], 10_000);
+
+        let (program, transfer) = decoded(&instructions[1]);
+        assert_eq!(program, "system");
+        assert_eq!(transfer.name, "transfer");
+        assert_eq!(transfer.account("source"), Some(PAYER));
+        assert_eq!(transfer.account("destination"), Some(RECIPIENT));
+        assert_eq!(transfer.args["lamports"], 5000);
+
+        let (_, memo) = decoded(&instructions[2]);
+        assert_eq!(memo.args["memo"], "gm");
+        assert_eq!(memo.accounts[0].name, "remaining");
+
+        let (_, token_transfer) = decoded(&instructions[3]);
+        assert_eq!(token_transfer.name, "transferChecked");
+        assert_eq!(token_transfer.account("mint"), Some(MINT));
+        assert_eq!(token_transfer.account("authority"), Some(PAYER));
+        assert_eq!(token_transfer.args["amount"], 2_500_000);
+        assert_eq!(token_transfer.args["decimals"], 6);
+
+        assert_eq!(
+            instructions[4],
+            ResolvedInstruction::Unknown {
+                program_id: COUNTER_PROGRAM_ID,
+                accounts: vec![RECIPIENT],
+                data: vec![0, 3, 0, 0, 0, 0, 0, 0, 0],
+            }
+        );
+    }
+
+    #[test]
+    fn custom_decoders() {
+        let mut registry = InstructionDecoderRegistry::empty();
+        registry.add_decoder(CounterDecoder);
+
+        let instructions = registry.decode_message(&message()).unwrap();
+        assert!(matches!(
+            instructions[1],
+            ResolvedInstruction::Unknown { .. }
+        ));
+
+        let (program, increment) = decoded(&instructions[4]);
+        assert_eq!(program, "counter");
+        assert_eq!(increment.account("counter"), Some(RECIPIENT));
+        assert_eq!(increment.args["Increment"]["by"], 3);
+
+        let mut message = message();
+        message.instructions[2].data = vec![0xff, 0xfe];
+        let instructions = InstructionDecoderRegistry::new()
+            .decode_message(&message)
+            .unwrap();
+        assert!(matches!(
+            &instructions[2],
+            ResolvedInstruction::Failed { program, data, .. }
+                if program == "spl-memo" && data == &[0xff, 0xfe]
+        ));
+        let (_, token_transfer) = decoded(&instructions[3]);
+        assert_eq!(token_transfer.name, "transferChecked");
+
+        message.instructions[1].accounts.push(42);
+        assert!(matches!(
+            InstructionDecoderRegistry::new().decode_message(&message),
+            Err(PoseidonError::AccountIndexNotFoundInMessageAccounts)
+        ));
+    }
+}
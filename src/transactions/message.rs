@@ -70,33 +70,23 @@ impl Message {
                     None => return Err(PoseidonError::ProgramIdNotFound),
                 };
 
-                // FIXME make this more efficient. do it in one loop or flat_map
-                let account_indexes = message_builder
-                    .instructions
+                let account_indexes = instruction
+                    .accounts
                     .iter()
-                    .map(|instruction| {
-                        instruction
-                            .accounts
+                    .map(|account_meta| {
+                        match self
+                            .account_keys
                             .iter()
-                            .map(|account_meta| {
-                                match self
-                                    .account_keys
-                                    .iter()
-                                    .enumerate()
-                                    .find(|(_, public_key)| *public_key == &account_meta.pubkey)
-                                {
-                                    Some((index, _)) => Ok(index as u8),
-                                    None => Err(PoseidonError::PublicKeyNotFoundInMessageAccounts),
-                                }
-                            })
-                            .collect::<Result<Vec<u8>, PoseidonError>>()
-                            .map_err(|_| PoseidonError::AccountIndexNotFoundInMessageAccounts)
+                            .enumerate()
+                            .find(|(_, public_key)| *public_key == &account_meta.pubkey)
+                        {
+                            Some((index, _)) => Ok(index as u8),
+                            None => Err(PoseidonError::PublicKeyNotFoundInMessageAccounts),
+                        }
                     })
-                    .collect::<Result<Vec<Vec<u8>>, PoseidonError>>()
+                    .collect::<Result<Vec<u8>, PoseidonError>>()
                     .map_err(|_| PoseidonError::AccountIndexNotFoundInMessageAccounts)?;
 
-                let account_indexes: Vec<u8> = account_indexes.into_iter().flatten().collect();
-
                 self.instructions.push(CompiledInstruction {
                     program_id_index,
                     accounts: account_indexes,
@@ -163,3 +153,58 @@ pub struct MessageHeader {
     /// The last num_readonly_unsigned_accounts of the unsigned keys are read-only accounts.
     pub num_readonly_unsigned_accounts: u8,
 }
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::{AccountMeta, Instruction};
+
+    #[test]
+    fn compile_instruction_accounts() {
+        let payer = [1; 32];
+        let (first_account, second_account) = ([2; 32], [3; 32]);
+        let (first_program, second_program) = ([7; 32], [8; 32]);
+
+        let mut message_builder = MessageBuilder::new();
+        message_builder
+            .add_payer(payer)
+            .add_instruction(Instruction {
+                program_id: first_program,
+                accounts: vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(first_account, false),
+                ],
+                data: vec![1],
+            })
+            .add_instruction(Instruction {
+                program_id: second_program,
+                accounts: vec![
+                    AccountMeta::new_readonly(second_account, false),
+                    AccountMeta::new(first_account, false),
+                ],
+                data: vec![2],
+            })
+            .build();
+
+        let mut message = Message::new();
+        message.build(message_builder).unwrap();
+
+        assert_eq!(
+            message.account_keys,
+            vec![
+                payer,
+                first_account,
+                second_account,
+                first_program,
+                second_program
+            ]
+        );
+        assert_eq!(message.instructions.len(), 2);
+        assert_eq!(message.instructions[0].program_id_index, 3);
+        assert_eq!(message.instructions[0].accounts, vec![0, 1]);
+        assert_eq!(message.instructions[0].data, vec![1]);
+        assert_eq!(message.instructions[1].program_id_index, 4);
+        assert_eq!(message.instructions[1].accounts, vec![2, 1]);
+        assert_eq!(message.instructions[1].data, vec![2]);
+    }
+}
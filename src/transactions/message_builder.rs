@@ -138,10 +138,7 @@ impl fmt::Debug for MessageBuilder {
             )
             .field(
                 "payer",
-                &match self.payer {
-                    Some(payer) => Some(bs58::encode(payer).into_string()),
-                    None => None,
-                },
+                &self.payer.map(|payer| bs58::encode(payer).into_string()),
             )
             .finish()
     }
@@ -33,3 +33,30 @@ pub use errors::*;
 
 mod ed25519_program;
 pub use ed25519_program::*;
+
+mod secp256k1_program;
+pub use secp256k1_program::*;
+
+mod stake_state;
+pub use stake_state::*;
+
+mod stake_instruction;
+pub use stake_instruction::*;
+
+mod vote_state;
+pub use vote_state::*;
+
+mod address_lookup_table_state;
+pub use address_lookup_table_state::*;
+
+mod address_lookup_table_instruction;
+pub use address_lookup_table_instruction::*;
+
+mod bpf_loader_upgradeable;
+pub use bpf_loader_upgradeable::*;
+
+mod sysvar;
+pub use sysvar::*;
+
+mod instruction_decoder;
+pub use instruction_decoder::*;
@@ -1,6 +1,6 @@
 use crate::{
     AccountMeta, Instruction, PdaPublicKey, PoseidonError, PoseidonResult, PublicKey,
-    SystemInstruction, MAX_SEED_LEN, PDA_MARKER,
+    SystemInstruction, MAX_SEEDS, MAX_SEED_LEN, PDA_MARKER,
 };
 use core::fmt;
 use serde::{Deserialize, Serialize};
@@ -31,6 +31,12 @@ pub struct PdaBuilder {
     lamports: u64,
 }
 
+impl Default for PdaBuilder {
+    fn default() -> Self {
+        PdaBuilder::new()
+    }
+}
+
 impl PdaBuilder {
     pub fn new() -> Self {
         PdaBuilder {
@@ -95,9 +101,9 @@ impl PdaBuilder {
         }
 
         let mut hasher = Sha256::new();
-        hasher.update(&self.base);
+        hasher.update(self.base);
         hasher.update(&self.seed);
-        hasher.update(&self.owner);
+        hasher.update(self.owner);
 
         let sha256_pda: [u8; 32] = hasher.finalize().into();
 
@@ -106,6 +112,56 @@ impl PdaBuilder {
         Ok(sha256_pda)
     }
 
+    /// Create a program address from the `seeds` and the `program_id`.
+    /// The address must not lie on the ed25519 curve so that no private key can sign for it.
+    pub fn create_program_address(
+        seeds: &[&[u8]],
+        program_id: &PublicKey,
+    ) -> PoseidonResult<PdaPublicKey> {
+        use sha2::{Digest, Sha256};
+
+        if seeds.len() > MAX_SEEDS {
+            return Err(PoseidonError::MaxSeedsExceeded);
+        }
+
+        if seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+            return Err(PoseidonError::MaxSeedLengthExceeded);
+        }
+
+        let mut hasher = Sha256::new();
+        seeds.iter().for_each(|seed| hasher.update(seed));
+        hasher.update(program_id);
+        hasher.update(PDA_MARKER);
+
+        let sha256_pda: [u8; 32] = hasher.finalize().into();
+
+        if ed25519_dalek::PublicKey::from_bytes(&sha256_pda).is_ok() {
+            return Err(PoseidonError::InvalidSeeds);
+        }
+
+        Ok(sha256_pda)
+    }
+
+    /// Find a valid program address and its bump seed, starting from a bump seed of `255`
+    pub fn find_program_address(
+        seeds: &[&[u8]],
+        program_id: &PublicKey,
+    ) -> PoseidonResult<(PdaPublicKey, u8)> {
+        for bump_seed in (0..=u8::MAX).rev() {
+            let bump_seed_slice = [bump_seed];
+            let mut seeds_with_bump = seeds.to_vec();
+            seeds_with_bump.push(&bump_seed_slice);
+
+            match PdaBuilder::create_program_address(&seeds_with_bump, program_id) {
+                Ok(address) => return Ok((address, bump_seed)),
+                Err(PoseidonError::InvalidSeeds) => continue,
+                Err(error) => return Err(error),
+            }
+        }
+
+        Err(PoseidonError::UnableToFindViableProgramAddress)
+    }
+
     pub fn pda_pk_base58(&self) -> String {
         bs58::encode(&self.to_public_key).into_string()
     }
@@ -152,3 +208,61 @@ impl fmt::Debug for PdaBuilder {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::{Utilities, BPF_LOADER_UPGRADEABLE_PROGRAM_ID};
+
+    fn address(base58: &str) -> PublicKey {
+        Utilities::base58_to_u32_array(base58).unwrap()
+    }
+
+    // The vectors of `Pubkey::create_program_address` in `solana-program`
+    #[test]
+    fn create_program_address() {
+        let program_id = BPF_LOADER_UPGRADEABLE_PROGRAM_ID;
+        let public_key = address("SeedPubey1111111111111111111111111111111111");
+
+        assert_eq!(
+            PdaBuilder::create_program_address(&[b"", &[1]], &program_id).unwrap(),
+            address("BwqrghZA2htAcqq8dzP1WDAhTXYTYWj7CHxF5j7TDBAe")
+        );
+        assert_eq!(
+            PdaBuilder::create_program_address(&["☉".as_bytes(), &[0]], &program_id).unwrap(),
+            address("13yWmRpaTR4r5nAktwLqMpRNr28tnVUZw26rTvPSSB19")
+        );
+        assert_eq!(
+            PdaBuilder::create_program_address(&[b"Talking", b"Squirrels"], &program_id).unwrap(),
+            address("2fnQrngrQT4SeLcdToJAD96phoEjNL2man2kfRLCASVk")
+        );
+        assert_eq!(
+            PdaBuilder::create_program_address(&[&public_key, &[1]], &program_id).unwrap(),
+            address("976ymqVnfE32QFe6NfGDctSvVa36LWnvYxhU6G2232YL")
+        );
+
+        assert!(matches!(
+            PdaBuilder::create_program_address(&[&[127; MAX_SEED_LEN + 1]], &program_id),
+            Err(PoseidonError::MaxSeedLengthExceeded)
+        ));
+        assert!(matches!(
+            PdaBuilder::create_program_address(&[b"seed".as_slice(); MAX_SEEDS + 1], &program_id),
+            Err(PoseidonError::MaxSeedsExceeded)
+        ));
+    }
+
+    // The example of the program derived address documentation of Solana
+    #[test]
+    fn find_program_address() {
+        let program_id = address("11111111111111111111111111111111");
+
+        assert!(matches!(
+            PdaBuilder::create_program_address(&[b"helloWorld", &[255]], &program_id),
+            Err(PoseidonError::InvalidSeeds)
+        ));
+        assert_eq!(
+            PdaBuilder::find_program_address(&[b"helloWorld"], &program_id).unwrap(),
+            (address("46GZzzetjCURsdFPb7rcnspbEMnCBXe9kpjrsZAkKb6X"), 254)
+        );
+    }
+}
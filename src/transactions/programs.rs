@@ -7,8 +7,134 @@ pub const ED25519_PROGRAM_ID: [u8; 32] = [
     244, 138, 100, 252, 202, 112, 68, 128, 0, 0, 0,
 ];
 
+/// Secp256k1 Verify Program
+pub const SECP256K1_PROGRAM_ID: [u8; 32] = [
+    4, 198, 252, 32, 240, 80, 204, 240, 85, 132, 215, 33, 28, 159, 140, 245, 158, 193, 71, 133,
+    187, 22, 106, 30, 40, 48, 232, 18, 32, 0, 0, 0,
+];
+
 /// SysVar Instructions Program
 pub const SYSVAR_INSTRUCTIONS: [u8; 32] = [
     6, 167, 213, 23, 24, 123, 209, 102, 53, 218, 212, 4, 85, 253, 194, 192, 193, 36, 198, 143, 33,
     86, 117, 165, 219, 186, 203, 95, 8, 0, 0, 0,
 ];
+
+/// Stake Program
+pub const STAKE_PROGRAM_ID: [u8; 32] = [
+    6, 161, 216, 23, 145, 55, 84, 42, 152, 52, 55, 189, 254, 42, 122, 178, 85, 127, 83, 92, 138,
+    120, 114, 43, 104, 164, 157, 192, 0, 0, 0, 0,
+];
+
+/// Stake Config Account
+pub const STAKE_CONFIG_ID: [u8; 32] = [
+    6, 161, 216, 23, 165, 2, 5, 11, 104, 7, 145, 230, 206, 109, 184, 142, 30, 91, 113, 80, 246, 31,
+    198, 121, 10, 78, 180, 209, 0, 0, 0, 0,
+];
+
+/// Vote Program
+pub const VOTE_PROGRAM_ID: [u8; 32] = [
+    7, 97, 72, 29, 53, 116, 116, 187, 124, 77, 118, 36, 235, 211, 189, 179, 216, 53, 94, 115, 209,
+    16, 67, 252, 13, 163, 83, 128, 0, 0, 0, 0,
+];
+
+/// Address Lookup Table Program
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: [u8; 32] = [
+    2, 119, 166, 175, 151, 51, 155, 122, 200, 141, 24, 146, 201, 4, 70, 245, 0, 2, 48, 146, 102,
+    246, 46, 83, 193, 24, 36, 73, 130, 0, 0, 0,
+];
+
+/// BPF Loader Upgradeable Program
+pub const BPF_LOADER_UPGRADEABLE_PROGRAM_ID: [u8; 32] = [
+    2, 168, 246, 145, 78, 136, 161, 176, 226, 16, 21, 62, 247, 99, 174, 43, 0, 194, 185, 61, 22,
+    193, 36, 210, 192, 83, 122, 16, 4, 128, 0, 0,
+];
+
+/// SysVar Clock Account
+pub const SYSVAR_CLOCK: [u8; 32] = [
+    6, 167, 213, 23, 24, 199, 116, 201, 40, 86, 99, 152, 105, 29, 94, 182, 139, 94, 184, 163, 155,
+    75, 109, 92, 115, 85, 91, 33, 0, 0, 0, 0,
+];
+
+/// SysVar Rent Account
+pub const SYSVAR_RENT: [u8; 32] = [
+    6, 167, 213, 23, 25, 44, 92, 81, 33, 140, 201, 76, 61, 74, 241, 127, 88, 218, 238, 8, 155, 161,
+    253, 68, 227, 219, 217, 138, 0, 0, 0, 0,
+];
+
+/// SysVar Stake History Account
+pub const SYSVAR_STAKE_HISTORY: [u8; 32] = [
+    6, 167, 213, 23, 25, 53, 132, 208, 254, 237, 155, 179, 67, 29, 19, 32, 107, 229, 68, 40, 27,
+    87, 184, 86, 108, 197, 55, 95, 244, 0, 0, 0,
+];
+
+/// SysVar Owner Program
+pub const SYSVAR_PROGRAM_ID: [u8; 32] = [
+    6, 167, 213, 23, 24, 117, 247, 41, 199, 61, 147, 64, 143, 33, 97, 32, 6, 126, 216, 140, 118,
+    224, 140, 40, 127, 193, 148, 96, 0, 0, 0, 0,
+];
+
+/// SysVar Epoch Schedule Account
+pub const SYSVAR_EPOCH_SCHEDULE: [u8; 32] = [
+    6, 167, 213, 23, 24, 220, 63, 238, 2, 211, 228, 127, 1, 0, 248, 176, 84, 247, 148, 46, 96, 89,
+    30, 63, 80, 135, 25, 168, 5, 0, 0, 0,
+];
+
+/// SysVar Fees Account
+pub const SYSVAR_FEES: [u8; 32] = [
+    6, 167, 213, 23, 24, 226, 90, 141, 131, 80, 60, 37, 26, 122, 240, 113, 38, 253, 114, 0, 223,
+    111, 196, 237, 82, 106, 156, 144, 0, 0, 0, 0,
+];
+
+/// SysVar Recent Blockhashes Account
+pub const SYSVAR_RECENT_BLOCKHASHES: [u8; 32] = [
+    6, 167, 213, 23, 25, 44, 86, 142, 224, 138, 132, 95, 115, 210, 151, 136, 207, 3, 92, 49, 69,
+    178, 26, 179, 68, 216, 6, 46, 169, 64, 0, 0,
+];
+
+/// SysVar Rewards Account
+pub const SYSVAR_REWARDS: [u8; 32] = [
+    6, 167, 213, 23, 25, 44, 97, 55, 206, 224, 146, 217, 182, 146, 62, 225, 204, 214, 25, 3, 250,
+    130, 184, 161, 97, 145, 87, 141, 128, 0, 0, 0,
+];
+
+/// SysVar Slot Hashes Account
+pub const SYSVAR_SLOT_HASHES: [u8; 32] = [
+    6, 167, 213, 23, 25, 47, 10, 175, 198, 242, 101, 227, 251, 119, 204, 122, 218, 130, 197, 41,
+    208, 190, 59, 19, 110, 45, 0, 85, 32, 0, 0, 0,
+];
+
+/// SysVar Slot History Account
+pub const SYSVAR_SLOT_HISTORY: [u8; 32] = [
+    6, 167, 213, 23, 25, 47, 10, 175, 200, 117, 226, 225, 132, 87, 124, 80, 105, 207, 200, 70, 73,
+    227, 235, 146, 120, 47, 149, 141, 72, 0, 0, 0,
+];
+
+/// SysVar Epoch Rewards Account
+pub const SYSVAR_EPOCH_REWARDS: [u8; 32] = [
+    6, 167, 213, 23, 24, 220, 63, 238, 2, 165, 88, 191, 131, 206, 102, 225, 68, 66, 42, 28, 52,
+    149, 11, 39, 193, 134, 155, 90, 156, 0, 0, 0,
+];
+
+/// SysVar Last Restart Slot Account
+pub const SYSVAR_LAST_RESTART_SLOT: [u8; 32] = [
+    6, 167, 213, 23, 25, 6, 221, 225, 205, 63, 148, 125, 202, 180, 200, 244, 244, 245, 27, 173, 15,
+    152, 19, 184, 0, 210, 137, 71, 31, 192, 0, 0,
+];
+
+/// Compute Budget Program
+pub const COMPUTE_BUDGET_PROGRAM_ID: [u8; 32] = [
+    3, 6, 70, 111, 229, 33, 23, 50, 255, 236, 173, 186, 114, 195, 155, 231, 188, 140, 229, 187,
+    197, 247, 18, 107, 44, 67, 155, 58, 64, 0, 0, 0,
+];
+
+/// SPL Memo Program
+pub const MEMO_PROGRAM_ID: [u8; 32] = [
+    5, 74, 83, 90, 153, 41, 33, 6, 77, 36, 232, 113, 96, 218, 56, 124, 124, 53, 181, 221, 188, 146,
+    187, 129, 228, 31, 168, 64, 65, 5, 68, 141,
+];
+
+/// SPL Token Program
+pub const TOKEN_PROGRAM_ID: [u8; 32] = [
+    6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172, 28, 180, 133, 237,
+    95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169,
+];
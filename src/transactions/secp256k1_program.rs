@@ -0,0 +1,161 @@
+use crate::{Instruction, PoseidonError, PoseidonResult, SECP256K1_PROGRAM_ID};
+use serde::{Deserialize, Serialize};
+
+pub const HASHED_PUBKEY_SERIALIZED_SIZE: usize = 20;
+pub const SECP256K1_SIGNATURE_SERIALIZED_SIZE: usize = 64;
+pub const SECP256K1_SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 11;
+// The number of signatures is a single byte with no padding
+pub const SECP256K1_SIGNATURE_OFFSETS_START: usize = 1;
+
+/// An Ethereum address, the last 20 bytes of the keccak256 hash of the public key
+pub type EthAddress = [u8; HASHED_PUBKEY_SERIALIZED_SIZE];
+/// A 64 byte secp256k1 signature followed by its recovery id
+pub type RecoverableSignature = [u8; SECP256K1_SIGNATURE_SERIALIZED_SIZE + 1];
+
+/// ### Build a native secp256k1 signature verification instruction
+/// Each signature is checked against the Ethereum address that should have
+/// produced it. Several signatures can be verified by the same instruction.
+///
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn example(eth_address: EthAddress, recoverable_signature: RecoverableSignature, other_eth_address: EthAddress, other_recoverable_signature: RecoverableSignature) -> PoseidonResult<()> {
+/// let instruction = Secp256k1Program::new()
+///     .add_signature(eth_address, recoverable_signature, b"first message")
+///     .add_signature(other_eth_address, other_recoverable_signature, b"second message")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Secp256k1Program {
+    signatures: Vec<(EthAddress, RecoverableSignature, Vec<u8>)>,
+    instruction_index: u8,
+}
+
+impl Secp256k1Program {
+    pub fn new() -> Self {
+        Secp256k1Program {
+            signatures: Vec::default(),
+            instruction_index: u8::default(),
+        }
+    }
+
+    /// Add the `eth_address`, the 65 byte recoverable `signature` and the `message` that was signed
+    pub fn add_signature(
+        &mut self,
+        eth_address: EthAddress,
+        signature: RecoverableSignature,
+        message: &[u8],
+    ) -> &mut Self {
+        self.signatures
+            .push((eth_address, signature, message.to_owned()));
+
+        self
+    }
+
+    /// The position of this instruction in the transaction. The secp256k1 program
+    /// reads the signatures from the instruction at this index, which defaults to `0`.
+    pub fn add_instruction_index(&mut self, instruction_index: u8) -> &mut Self {
+        self.instruction_index = instruction_index;
+
+        self
+    }
+
+    pub fn build(&self) -> PoseidonResult<Instruction> {
+        let num_signatures = u8::try_from(self.signatures.len())
+            .map_err(|_| PoseidonError::InstructionDataTooLarge)?;
+
+        let data_start = SECP256K1_SIGNATURE_OFFSETS_START
+            .saturating_add(self.signatures.len() * SECP256K1_SIGNATURE_OFFSETS_SERIALIZED_SIZE);
+
+        let mut instruction_data = vec![num_signatures];
+        let mut signature_data = Vec::<u8>::default();
+
+        for (eth_address, signature, message) in &self.signatures {
+            let eth_address_offset = data_start.saturating_add(signature_data.len());
+            let signature_offset = eth_address_offset.saturating_add(HASHED_PUBKEY_SERIALIZED_SIZE);
+            let message_data_offset = signature_offset
+                .saturating_add(SECP256K1_SIGNATURE_SERIALIZED_SIZE)
+                .saturating_add(1);
+
+            let offsets = SecpSignatureOffsets {
+                signature_offset: Secp256k1Program::to_u16(signature_offset)?,
+                signature_instruction_index: self.instruction_index,
+                eth_address_offset: Secp256k1Program::to_u16(eth_address_offset)?,
+                eth_address_instruction_index: self.instruction_index,
+                message_data_offset: Secp256k1Program::to_u16(message_data_offset)?,
+                message_data_size: Secp256k1Program::to_u16(message.len())?,
+                message_instruction_index: self.instruction_index,
+            };
+
+            instruction_data.extend_from_slice(&bincode::serialize(&offsets)?);
+
+            signature_data.extend_from_slice(eth_address);
+            signature_data.extend_from_slice(signature);
+            signature_data.extend_from_slice(message);
+        }
+
+        debug_assert_eq!(instruction_data.len(), data_start);
+
+        instruction_data.extend_from_slice(&signature_data);
+
+        Ok(Instruction {
+            program_id: SECP256K1_PROGRAM_ID,
+            accounts: vec![],
+            data: instruction_data,
+        })
+    }
+
+    fn to_u16(value: usize) -> PoseidonResult<u16> {
+        u16::try_from(value).map_err(|_| PoseidonError::InstructionDataTooLarge)
+    }
+}
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecpSignatureOffsets {
+    pub signature_offset: u16, // offset to [signature,recovery_id] of 64+1 bytes
+    pub signature_instruction_index: u8, // instruction index to find signature
+    pub eth_address_offset: u16, // offset to ethereum address of 20 bytes
+    pub eth_address_instruction_index: u8, // instruction index to find ethereum address
+    pub message_data_offset: u16, // offset to start of message data
+    pub message_data_size: u16, // size of message data
+    pub message_instruction_index: u8, // instruction index to find message data
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+
+    #[test]
+    fn secp256k1_instruction_layout() {
+        let instruction = Secp256k1Program::new()
+            .add_signature([1u8; 20], [2u8; 65], b"hello")
+            .add_signature([3u8; 20], [4u8; 65], b"world!")
+            .add_instruction_index(1)
+            .build()
+            .unwrap();
+
+        let data = &instruction.data;
+        assert_eq!(instruction.program_id, SECP256K1_PROGRAM_ID);
+        assert_eq!(data[0], 2);
+
+        let data_start = 1 + 2 * SECP256K1_SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        assert_eq!(data.len(), data_start + (20 + 65) * 2 + 5 + 6);
+
+        let second = bincode::deserialize::<SecpSignatureOffsets>(&data[12..23]).unwrap();
+        let eth_address_offset = second.eth_address_offset as usize;
+        let signature_offset = second.signature_offset as usize;
+        let message_data_offset = second.message_data_offset as usize;
+
+        assert_eq!(second.signature_instruction_index, 1);
+        assert_eq!(
+            &data[eth_address_offset..eth_address_offset + 20],
+            &[3u8; 20]
+        );
+        assert_eq!(&data[signature_offset..signature_offset + 65], &[4u8; 65]);
+        assert_eq!(
+            &data[message_data_offset..message_data_offset + second.message_data_size as usize],
+            b"world!"
+        );
+    }
+}
@@ -0,0 +1,573 @@
+use crate::{
+    AccountMeta, Authorized, Instruction, Lockup, PoseidonResult, PublicKey, SystemInstruction,
+    UnixTimestamp, STAKE_CONFIG_ID, STAKE_PROGRAM_ID, STAKE_STATE_SIZE, SYSTEM_PROGRAM_ID,
+    SYSVAR_CLOCK, SYSVAR_RENT, SYSVAR_STAKE_HISTORY,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StakeAuthorize {
+    Staker,
+    Withdrawer,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct LockupArgs {
+    pub unix_timestamp: Option<UnixTimestamp>,
+    pub epoch: Option<u64>,
+    pub custodian: Option<PublicKey>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct LockupCheckedArgs {
+    pub unix_timestamp: Option<UnixTimestamp>,
+    pub epoch: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizeWithSeedArgs {
+    pub new_authorized_pubkey: PublicKey,
+    pub stake_authorize: StakeAuthorize,
+    pub authority_seed: String,
+    pub authority_owner: PublicKey,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizeCheckedWithSeedArgs {
+    pub stake_authorize: StakeAuthorize,
+    pub authority_seed: String,
+    pub authority_owner: PublicKey,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum StakeInstruction {
+    /// Initialize a stake with lockup and authorization information
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Uninitialized stake account
+    ///   1. `[]` Rent sysvar
+    Initialize(Authorized, Lockup),
+
+    /// Authorize a key to manage stake or withdrawal
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account to be updated
+    ///   1. `[]` Clock sysvar
+    ///   2. `[SIGNER]` The stake or withdraw authority
+    ///   3. Optional: `[SIGNER]` Lockup authority, if updating StakeAuthorize::Withdrawer before
+    ///      lockup expiration
+    Authorize(PublicKey, StakeAuthorize),
+
+    /// Delegate a stake to a particular vote account
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account to be delegated
+    ///   1. `[]` Vote account to which this stake will be delegated
+    ///   2. `[]` Clock sysvar
+    ///   3. `[]` Stake history sysvar that carries stake warmup/cooldown history
+    ///   4. `[]` Address of config account that carries stake config
+    ///   5. `[SIGNER]` Stake authority
+    DelegateStake,
+
+    /// Split `u64` lamports from stake account into another stake account.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account to be split; must be in the Initialized or Stake state
+    ///   1. `[WRITE]` Uninitialized stake account that will take the split-off amount
+    ///   2. `[SIGNER]` Stake authority
+    Split(u64),
+
+    /// Withdraw unstaked lamports from the stake account
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account from which to withdraw
+    ///   1. `[WRITE]` Recipient account
+    ///   2. `[]` Clock sysvar
+    ///   3. `[]` Stake history sysvar that carries stake warmup/cooldown history
+    ///   4. `[SIGNER]` Withdraw authority
+    ///   5. Optional: `[SIGNER]` Lockup authority, if before lockup expiration
+    ///
+    /// The `u64` parameter is the lamports to withdraw, which must leave the
+    /// account balance above the rent exempt reserve or at zero.
+    Withdraw(u64),
+
+    /// Deactivates the stake in the account
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Delegated stake account
+    ///   1. `[]` Clock sysvar
+    ///   2. `[SIGNER]` Stake authority
+    Deactivate,
+
+    /// Set stake lockup
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` Lockup authority or withdraw authority
+    SetLockup(LockupArgs),
+
+    /// Merge two stake accounts.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Destination stake account for the merge
+    ///   1. `[WRITE]` Source stake account for to merge. This account will be drained
+    ///   2. `[]` Clock sysvar
+    ///   3. `[]` Stake history sysvar that carries stake warmup/cooldown history
+    ///   4. `[SIGNER]` Stake authority
+    Merge,
+
+    /// Authorize a key to manage stake or withdrawal with a derived key
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account to be updated
+    ///   1. `[SIGNER]` Base key of stake or withdraw authority
+    ///   2. `[]` Clock sysvar
+    ///   3. Optional: `[SIGNER]` Lockup authority, if updating StakeAuthorize::Withdrawer before
+    ///      lockup expiration
+    AuthorizeWithSeed(AuthorizeWithSeedArgs),
+
+    /// Initialize a stake with authorization information
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Uninitialized stake account
+    ///   1. `[]` Rent sysvar
+    ///   2. `[]` The stake authority
+    ///   3. `[SIGNER]` The withdraw authority
+    InitializeChecked,
+
+    /// Authorize a key to manage stake or withdrawal
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account to be updated
+    ///   1. `[]` Clock sysvar
+    ///   2. `[SIGNER]` The stake or withdraw authority
+    ///   3. `[SIGNER]` The new stake or withdraw authority
+    ///   4. Optional: `[SIGNER]` Lockup authority, if updating StakeAuthorize::Withdrawer before
+    ///      lockup expiration
+    AuthorizeChecked(StakeAuthorize),
+
+    /// Authorize a key to manage stake or withdrawal with a derived key
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account to be updated
+    ///   1. `[SIGNER]` Base key of stake or withdraw authority
+    ///   2. `[]` Clock sysvar
+    ///   3. `[SIGNER]` The new stake or withdraw authority
+    ///   4. Optional: `[SIGNER]` Lockup authority, if updating StakeAuthorize::Withdrawer before
+    ///      lockup expiration
+    AuthorizeCheckedWithSeed(AuthorizeCheckedWithSeedArgs),
+
+    /// Set stake lockup
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` Lockup authority or withdraw authority
+    ///   2. Optional: `[SIGNER]` New lockup authority
+    SetLockupChecked(LockupCheckedArgs),
+
+    /// Get the minimum stake delegation, in lamports
+    ///
+    /// # Account references
+    ///   None
+    GetMinimumDelegation,
+
+    /// Deactivate stake delegated to a vote account that has been delinquent for at least
+    /// `MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION` epochs.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Delegated stake account
+    ///   1. `[]` Delinquent vote account for the delegated stake account
+    ///   2. `[]` Reference vote account that has voted at least once in the last
+    ///      `MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION` epochs
+    DeactivateDelinquent,
+}
+
+/// ### Build Stake Program instructions
+/// Each method yields an `Instruction` whose accounts are laid out in the order
+/// documented on the matching `StakeInstruction` variant.
+///
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn example(payer: PublicKey, stake_account: PublicKey, vote_account: PublicKey, lamports: u64) -> PoseidonResult<()> {
+/// let instructions = StakeProgram::create_stake_account(
+///     payer,
+///     stake_account,
+///     Authorized::auto(payer),
+///     Lockup::default(),
+///     lamports,
+/// )?;
+/// let delegate = StakeProgram::delegate_stake(stake_account, payer, vote_account)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StakeProgram;
+
+impl StakeProgram {
+    pub fn initialize(
+        stake_public_key: PublicKey,
+        authorized: Authorized,
+        lockup: Lockup,
+    ) -> PoseidonResult<Instruction> {
+        StakeProgram::instruction(
+            StakeInstruction::Initialize(authorized, lockup),
+            vec![
+                AccountMeta::new(stake_public_key, false),
+                AccountMeta::new_readonly(SYSVAR_RENT, false),
+            ],
+        )
+    }
+
+    pub fn initialize_checked(
+        stake_public_key: PublicKey,
+        authorized: Authorized,
+    ) -> PoseidonResult<Instruction> {
+        StakeProgram::instruction(
+            StakeInstruction::InitializeChecked,
+            vec![
+                AccountMeta::new(stake_public_key, false),
+                AccountMeta::new_readonly(SYSVAR_RENT, false),
+                AccountMeta::new_readonly(authorized.staker, false),
+                AccountMeta::new_readonly(authorized.withdrawer, true),
+            ],
+        )
+    }
+
+    /// Create a new account owned by the Stake Program using `SystemInstruction::CreateAccount`
+    /// and initialize it. Both `from_public_key` and `stake_public_key` must sign.
+    pub fn create_stake_account(
+        from_public_key: PublicKey,
+        stake_public_key: PublicKey,
+        authorized: Authorized,
+        lockup: Lockup,
+        lamports: u64,
+    ) -> PoseidonResult<Vec<Instruction>> {
+        let create_account = SystemInstruction::CreateAccount {
+            lamports,
+            space: STAKE_STATE_SIZE as u64,
+            owner: STAKE_PROGRAM_ID,
+        };
+
+        Ok(vec![
+            Instruction {
+                program_id: SYSTEM_PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(from_public_key, true),
+                    AccountMeta::new(stake_public_key, true),
+                ],
+                data: bincode::serialize(&create_account)?,
+            },
+            StakeProgram::initialize(stake_public_key, authorized, lockup)?,
+        ])
+    }
+
+    pub fn authorize(
+        stake_public_key: PublicKey,
+        authority: PublicKey,
+        new_authority: PublicKey,
+        stake_authorize: StakeAuthorize,
+        custodian: Option<PublicKey>,
+    ) -> PoseidonResult<Instruction> {
+        let mut accounts = vec![
+            AccountMeta::new(stake_public_key, false),
+            AccountMeta::new_readonly(SYSVAR_CLOCK, false),
+            AccountMeta::new_readonly(authority, true),
+        ];
+        if let Some(custodian) = custodian {
+            accounts.push(AccountMeta::new_readonly(custodian, true));
+        }
+
+        StakeProgram::instruction(
+            StakeInstruction::Authorize(new_authority, stake_authorize),
+            accounts,
+        )
+    }
+
+    pub fn authorize_checked(
+        stake_public_key: PublicKey,
+        authority: PublicKey,
+        new_authority: PublicKey,
+        stake_authorize: StakeAuthorize,
+        custodian: Option<PublicKey>,
+    ) -> PoseidonResult<Instruction> {
+        let mut accounts = vec![
+            AccountMeta::new(stake_public_key, false),
+            AccountMeta::new_readonly(SYSVAR_CLOCK, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(new_authority, true),
+        ];
+        if let Some(custodian) = custodian {
+            accounts.push(AccountMeta::new_readonly(custodian, true));
+        }
+
+        StakeProgram::instruction(
+            StakeInstruction::AuthorizeChecked(stake_authorize),
+            accounts,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn authorize_with_seed(
+        stake_public_key: PublicKey,
+        authority_base: PublicKey,
+        authority_seed: &str,
+        authority_owner: PublicKey,
+        new_authority: PublicKey,
+        stake_authorize: StakeAuthorize,
+        custodian: Option<PublicKey>,
+    ) -> PoseidonResult<Instruction> {
+        let mut accounts = vec![
+            AccountMeta::new(stake_public_key, false),
+            AccountMeta::new_readonly(authority_base, true),
+            AccountMeta::new_readonly(SYSVAR_CLOCK, false),
+        ];
+        if let Some(custodian) = custodian {
+            accounts.push(AccountMeta::new_readonly(custodian, true));
+        }
+
+        let args = AuthorizeWithSeedArgs {
+            new_authorized_pubkey: new_authority,
+            stake_authorize,
+            authority_seed: authority_seed.to_owned(),
+            authority_owner,
+        };
+
+        StakeProgram::instruction(StakeInstruction::AuthorizeWithSeed(args), accounts)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn authorize_checked_with_seed(
+        stake_public_key: PublicKey,
+        authority_base: PublicKey,
+        authority_seed: &str,
+        authority_owner: PublicKey,
+        new_authority: PublicKey,
+        stake_authorize: StakeAuthorize,
+        custodian: Option<PublicKey>,
+    ) -> PoseidonResult<Instruction> {
+        let mut accounts = vec![
+            AccountMeta::new(stake_public_key, false),
+            AccountMeta::new_readonly(authority_base, true),
+            AccountMeta::new_readonly(SYSVAR_CLOCK, false),
+            AccountMeta::new_readonly(new_authority, true),
+        ];
+        if let Some(custodian) = custodian {
+            accounts.push(AccountMeta::new_readonly(custodian, true));
+        }
+
+        let args = AuthorizeCheckedWithSeedArgs {
+            stake_authorize,
+            authority_seed: authority_seed.to_owned(),
+            authority_owner,
+        };
+
+        StakeProgram::instruction(StakeInstruction::AuthorizeCheckedWithSeed(args), accounts)
+    }
+
+    pub fn delegate_stake(
+        stake_public_key: PublicKey,
+        authority: PublicKey,
+        vote_public_key: PublicKey,
+    ) -> PoseidonResult<Instruction> {
+        StakeProgram::instruction(
+            StakeInstruction::DelegateStake,
+            vec![
+                AccountMeta::new(stake_public_key, false),
+                AccountMeta::new_readonly(vote_public_key, false),
+                AccountMeta::new_readonly(SYSVAR_CLOCK, false),
+                AccountMeta::new_readonly(SYSVAR_STAKE_HISTORY, false),
+                AccountMeta::new_readonly(STAKE_CONFIG_ID, false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+        )
+    }
+
+    pub fn deactivate(
+        stake_public_key: PublicKey,
+        authority: PublicKey,
+    ) -> PoseidonResult<Instruction> {
+        StakeProgram::instruction(
+            StakeInstruction::Deactivate,
+            vec![
+                AccountMeta::new(stake_public_key, false),
+                AccountMeta::new_readonly(SYSVAR_CLOCK, false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+        )
+    }
+
+    pub fn withdraw(
+        stake_public_key: PublicKey,
+        withdraw_authority: PublicKey,
+        recipient: PublicKey,
+        lamports: u64,
+        custodian: Option<PublicKey>,
+    ) -> PoseidonResult<Instruction> {
+        let mut accounts = vec![
+            AccountMeta::new(stake_public_key, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(SYSVAR_CLOCK, false),
+            AccountMeta::new_readonly(SYSVAR_STAKE_HISTORY, false),
+            AccountMeta::new_readonly(withdraw_authority, true),
+        ];
+        if let Some(custodian) = custodian {
+            accounts.push(AccountMeta::new_readonly(custodian, true));
+        }
+
+        StakeProgram::instruction(StakeInstruction::Withdraw(lamports), accounts)
+    }
+
+    /// Split `lamports` into `split_stake_public_key`. The new account is allocated
+    /// and assigned to the Stake Program first so it must sign the transaction.
+    pub fn split(
+        stake_public_key: PublicKey,
+        authority: PublicKey,
+        lamports: u64,
+        split_stake_public_key: PublicKey,
+    ) -> PoseidonResult<Vec<Instruction>> {
+        let allocate = SystemInstruction::Allocate {
+            space: STAKE_STATE_SIZE as u64,
+        };
+        let assign = SystemInstruction::Assign {
+            owner: STAKE_PROGRAM_ID,
+        };
+
+        Ok(vec![
+            Instruction {
+                program_id: SYSTEM_PROGRAM_ID,
+                accounts: vec![AccountMeta::new(split_stake_public_key, true)],
+                data: bincode::serialize(&allocate)?,
+            },
+            Instruction {
+                program_id: SYSTEM_PROGRAM_ID,
+                accounts: vec![AccountMeta::new(split_stake_public_key, true)],
+                data: bincode::serialize(&assign)?,
+            },
+            StakeProgram::instruction(
+                StakeInstruction::Split(lamports),
+                vec![
+                    AccountMeta::new(stake_public_key, false),
+                    AccountMeta::new(split_stake_public_key, false),
+                    AccountMeta::new_readonly(authority, true),
+                ],
+            )?,
+        ])
+    }
+
+    pub fn merge(
+        destination_stake_public_key: PublicKey,
+        source_stake_public_key: PublicKey,
+        authority: PublicKey,
+    ) -> PoseidonResult<Instruction> {
+        StakeProgram::instruction(
+            StakeInstruction::Merge,
+            vec![
+                AccountMeta::new(destination_stake_public_key, false),
+                AccountMeta::new(source_stake_public_key, false),
+                AccountMeta::new_readonly(SYSVAR_CLOCK, false),
+                AccountMeta::new_readonly(SYSVAR_STAKE_HISTORY, false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+        )
+    }
+
+    pub fn set_lockup(
+        stake_public_key: PublicKey,
+        lockup: LockupArgs,
+        custodian: PublicKey,
+    ) -> PoseidonResult<Instruction> {
+        StakeProgram::instruction(
+            StakeInstruction::SetLockup(lockup),
+            vec![
+                AccountMeta::new(stake_public_key, false),
+                AccountMeta::new_readonly(custodian, true),
+            ],
+        )
+    }
+
+    pub fn set_lockup_checked(
+        stake_public_key: PublicKey,
+        lockup: LockupArgs,
+        custodian: PublicKey,
+    ) -> PoseidonResult<Instruction> {
+        let mut accounts = vec![
+            AccountMeta::new(stake_public_key, false),
+            AccountMeta::new_readonly(custodian, true),
+        ];
+        if let Some(new_custodian) = lockup.custodian {
+            accounts.push(AccountMeta::new_readonly(new_custodian, true));
+        }
+
+        let args = LockupCheckedArgs {
+            unix_timestamp: lockup.unix_timestamp,
+            epoch: lockup.epoch,
+        };
+
+        StakeProgram::instruction(StakeInstruction::SetLockupChecked(args), accounts)
+    }
+
+    fn instruction(
+        stake_instruction: StakeInstruction,
+        accounts: Vec<AccountMeta>,
+    ) -> PoseidonResult<Instruction> {
+        Ok(Instruction {
+            program_id: STAKE_PROGRAM_ID,
+            accounts,
+            data: bincode::serialize(&stake_instruction)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use crate::*;
+
+    #[test]
+    fn stake_instruction_layout() {
+        let stake = [1u8; 32];
+        let authority = [2u8; 32];
+
+        let deactivate = StakeProgram::deactivate(stake, authority).unwrap();
+        assert_eq!(deactivate.program_id, STAKE_PROGRAM_ID);
+        assert_eq!(deactivate.data, vec![5, 0, 0, 0]);
+        assert_eq!(deactivate.accounts[1].pubkey, SYSVAR_CLOCK);
+        assert!(deactivate.accounts[2].is_signer);
+
+        let withdraw = StakeProgram::withdraw(stake, authority, authority, 42, None).unwrap();
+        assert_eq!(withdraw.data, vec![4, 0, 0, 0, 42, 0, 0, 0, 0, 0, 0, 0]);
+
+        let initialize =
+            StakeProgram::initialize(stake, Authorized::auto(authority), Lockup::default())
+                .unwrap();
+        // discriminant + staker + withdrawer + unix_timestamp + epoch + custodian
+        assert_eq!(initialize.data.len(), 4 + 32 + 32 + 8 + 8 + 32);
+    }
+
+    #[test]
+    fn decode_stake_state() {
+        let stake_state = StakeState::Stake(
+            Meta {
+                rent_exempt_reserve: 2_282_880,
+                authorized: Authorized::auto([7u8; 32]),
+                lockup: Lockup::default(),
+            },
+            Stake {
+                delegation: Delegation {
+                    voter_pubkey: [9u8; 32],
+                    stake: 1_000_000_000,
+                    activation_epoch: 300,
+                    ..Delegation::default()
+                },
+                credits_observed: 12,
+            },
+        );
+
+        let mut data = bincode::serialize(&stake_state).unwrap();
+        data.resize(STAKE_STATE_SIZE, 0);
+
+        let decoded = StakeState::from_bytes(&data).unwrap();
+        assert_eq!(decoded, stake_state);
+        assert_eq!(decoded.delegation().unwrap().voter_pubkey, [9u8; 32]);
+        assert!(!decoded.delegation().unwrap().is_deactivating());
+    }
+}
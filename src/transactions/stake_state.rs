@@ -0,0 +1,168 @@
+use crate::{PoseidonResult, PublicKey, UnixTimestamp};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// The size in bytes of a Stake account
+pub const STAKE_STATE_SIZE: usize = 200;
+
+/// The state of a Stake account as laid out by the Stake program.
+///
+/// Decode it from the data returned by `GetAccountInfo`
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example(stake_account: &str) -> PoseidonResult<()> {
+/// let account = GetAccountInfo::process(stake_account).await?.result.value;
+/// let stake_state = StakeState::from_bytes(&account.decode_data()?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub enum StakeState {
+    #[default]
+    Uninitialized,
+    Initialized(Meta),
+    Stake(Meta, Stake),
+    RewardsPool,
+}
+
+impl StakeState {
+    pub fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        Ok(bincode::deserialize::<StakeState>(data)?)
+    }
+
+    pub fn meta(&self) -> Option<&Meta> {
+        match self {
+            StakeState::Initialized(meta) | StakeState::Stake(meta, _) => Some(meta),
+            _ => None,
+        }
+    }
+
+    pub fn stake(&self) -> Option<&Stake> {
+        match self {
+            StakeState::Stake(_, stake) => Some(stake),
+            _ => None,
+        }
+    }
+
+    pub fn delegation(&self) -> Option<&Delegation> {
+        self.stake().map(|stake| &stake.delegation)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Meta {
+    /// Lamports reserved to keep the account rent exempt
+    pub rent_exempt_reserve: u64,
+    /// The authorities of the account
+    pub authorized: Authorized,
+    /// The lockup in force on the account
+    pub lockup: Lockup,
+}
+
+/// The authorities allowed to delegate and withdraw from a Stake account
+#[derive(Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Authorized {
+    /// Authority allowed to delegate and deactivate the stake
+    pub staker: PublicKey,
+    /// Authority allowed to withdraw lamports from the account
+    pub withdrawer: PublicKey,
+}
+
+impl Authorized {
+    pub fn new(staker: PublicKey, withdrawer: PublicKey) -> Self {
+        Authorized { staker, withdrawer }
+    }
+
+    /// Make the same public key the staker and the withdrawer
+    pub fn auto(authority: PublicKey) -> Self {
+        Authorized {
+            staker: authority,
+            withdrawer: authority,
+        }
+    }
+}
+
+impl fmt::Debug for Authorized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Authorized")
+            .field("staker", &bs58::encode(&self.staker).into_string())
+            .field("withdrawer", &bs58::encode(&self.withdrawer).into_string())
+            .finish()
+    }
+}
+
+/// A lockup prevents withdrawals until both the `unix_timestamp`
+/// and the `epoch` have been passed, unless the `custodian` signs
+#[derive(Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Lockup {
+    /// UnixTimestamp at which this stake will allow withdrawal
+    pub unix_timestamp: UnixTimestamp,
+    /// Epoch height at which this stake will allow withdrawal
+    pub epoch: u64,
+    /// Custodian signature on a transaction exempts the operation from lockup constraints
+    pub custodian: PublicKey,
+}
+
+impl fmt::Debug for Lockup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lockup")
+            .field("unix_timestamp", &self.unix_timestamp)
+            .field("epoch", &self.epoch)
+            .field("custodian", &bs58::encode(&self.custodian).into_string())
+            .finish()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Stake {
+    pub delegation: Delegation,
+    /// Credits observed is credits from vote account state when delegated or redeemed
+    pub credits_observed: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Delegation {
+    /// The vote account the stake is delegated to
+    pub voter_pubkey: PublicKey,
+    /// Activated stake amount, set at delegate() time
+    pub stake: u64,
+    /// Epoch at which this stake was activated, `u64::MAX` for bootstrap stakes
+    pub activation_epoch: u64,
+    /// Epoch the stake was deactivated, `u64::MAX` if not deactivated
+    pub deactivation_epoch: u64,
+    /// How much stake can be activated or deactivated per epoch
+    pub warmup_cooldown_rate: f64,
+}
+
+impl Default for Delegation {
+    fn default() -> Self {
+        Delegation {
+            voter_pubkey: PublicKey::default(),
+            stake: u64::default(),
+            activation_epoch: u64::default(),
+            deactivation_epoch: u64::MAX,
+            warmup_cooldown_rate: 0.25,
+        }
+    }
+}
+
+impl Delegation {
+    pub fn is_deactivating(&self) -> bool {
+        self.deactivation_epoch != u64::MAX
+    }
+}
+
+impl fmt::Debug for Delegation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Delegation")
+            .field(
+                "voter_pubkey",
+                &bs58::encode(&self.voter_pubkey).into_string(),
+            )
+            .field("stake", &self.stake)
+            .field("activation_epoch", &self.activation_epoch)
+            .field("deactivation_epoch", &self.deactivation_epoch)
+            .field("warmup_cooldown_rate", &self.warmup_cooldown_rate)
+            .finish()
+    }
+}
@@ -0,0 +1,530 @@
+use crate::{
+    AccountMeta, FeeCalculator, Instruction, PoseidonError, PoseidonResult, PublicKey,
+    UnixTimestamp, SYSVAR_CLOCK, SYSVAR_EPOCH_SCHEDULE, SYSVAR_FEES, SYSVAR_INSTRUCTIONS,
+    SYSVAR_RENT, SYSVAR_SLOT_HASHES, SYSVAR_SLOT_HISTORY, SYSVAR_STAKE_HISTORY,
+};
+use borsh::BorshSerialize;
+use serde::{Deserialize, Serialize};
+
+/// The number of bytes of account metadata stored alongside the data of every account
+pub const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+/// Default rental rate in lamports per byte-year
+pub const DEFAULT_LAMPORTS_PER_BYTE_YEAR: u64 = 3480;
+/// Default amount of time in years an account must be able to pay rent for to be rent exempt
+pub const DEFAULT_EXEMPTION_THRESHOLD: f64 = 2.0;
+/// Default percentage of collected rent that is burned
+pub const DEFAULT_BURN_PERCENT: u8 = 50;
+
+/// The number of slots tracked by the `SlotHistory` sysvar
+pub const SLOT_HISTORY_MAX_ENTRIES: u64 = 1024 * 1024;
+
+/// A sysvar account whose data can be decoded into `Self`.
+/// `GetSysvar` of the RPC client fetches and decodes a sysvar in one call.
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn example(account_data: &[u8]) -> PoseidonResult<()> {
+/// let clock = Clock::from_bytes(account_data)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Sysvar: Sized {
+    /// The address of the sysvar account
+    const ADDRESS: PublicKey;
+
+    fn from_bytes(data: &[u8]) -> PoseidonResult<Self>;
+}
+
+/// The `Clock` sysvar holding the cluster time
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Clock {
+    /// The current slot
+    pub slot: u64,
+    /// The timestamp of the first slot in this epoch
+    pub epoch_start_timestamp: UnixTimestamp,
+    /// The current epoch
+    pub epoch: u64,
+    /// The future epoch for which the leader schedule has most recently been calculated
+    pub leader_schedule_epoch: u64,
+    /// The approximate real world time of the current slot
+    pub unix_timestamp: UnixTimestamp,
+}
+
+impl Sysvar for Clock {
+    const ADDRESS: PublicKey = SYSVAR_CLOCK;
+
+    fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        Ok(bincode::deserialize::<Clock>(data)?)
+    }
+}
+
+/// The `Rent` sysvar holding the rent configuration of the cluster.
+///
+/// Fetch it once, for example with a `RentCache`, and compute rent exemption
+/// locally instead of calling `getMinimumBalanceForRentExemption` for every account
+/// ```no_run
+/// # use poseidon_client::*;
+/// # fn example(rent: Rent, my_borsh_state: Vec<u8>) -> PoseidonResult<()> {
+/// let lamports = rent.minimum_balance(STAKE_STATE_SIZE);
+/// let lamports = rent.minimum_balance_for_value(&my_borsh_state)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Rent {
+    /// Rental rate in lamports per byte-year
+    pub lamports_per_byte_year: u64,
+    /// Amount of time in years an account must be able to pay rent for to be rent exempt
+    pub exemption_threshold: f64,
+    /// The percentage of collected rent that is burned
+    pub burn_percent: u8,
+}
+
+impl Rent {
+    /// The minimum lamports an account holding `data_len` bytes needs to be rent exempt
+    pub fn minimum_balance(&self, data_len: usize) -> u64 {
+        let bytes = ACCOUNT_STORAGE_OVERHEAD.saturating_add(data_len as u64);
+
+        (bytes.saturating_mul(self.lamports_per_byte_year) as f64 * self.exemption_threshold) as u64
+    }
+
+    /// The minimum balance of an account holding the Borsh serialized `value`.
+    /// Unlike `core::mem::size_of` this accounts for the length of `String`s and `Vec`s.
+    pub fn minimum_balance_for_value<T: BorshSerialize>(&self, value: &T) -> PoseidonResult<u64> {
+        Ok(self.minimum_balance(value.try_to_vec()?.len()))
+    }
+
+    /// Whether `lamports` are enough for an account holding `data_len` bytes to be rent exempt
+    pub fn is_exempt(&self, lamports: u64, data_len: usize) -> bool {
+        lamports >= self.minimum_balance(data_len)
+    }
+}
+
+impl Default for Rent {
+    fn default() -> Self {
+        Rent {
+            lamports_per_byte_year: DEFAULT_LAMPORTS_PER_BYTE_YEAR,
+            exemption_threshold: DEFAULT_EXEMPTION_THRESHOLD,
+            burn_percent: DEFAULT_BURN_PERCENT,
+        }
+    }
+}
+
+impl Sysvar for Rent {
+    const ADDRESS: PublicKey = SYSVAR_RENT;
+
+    fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        Ok(bincode::deserialize::<Rent>(data)?)
+    }
+}
+
+/// The `EpochSchedule` sysvar describing how slots are grouped into epochs
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochSchedule {
+    /// The maximum number of slots in each epoch
+    pub slots_per_epoch: u64,
+    /// The number of slots before the beginning of an epoch to calculate its leader schedule
+    pub leader_schedule_slot_offset: u64,
+    /// Whether epochs start short and grow
+    pub warmup: bool,
+    /// The first epoch with `slots_per_epoch` slots
+    pub first_normal_epoch: u64,
+    /// The first slot of `first_normal_epoch`
+    pub first_normal_slot: u64,
+}
+
+impl Sysvar for EpochSchedule {
+    const ADDRESS: PublicKey = SYSVAR_EPOCH_SCHEDULE;
+
+    fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        Ok(bincode::deserialize::<EpochSchedule>(data)?)
+    }
+}
+
+/// The deprecated `Fees` sysvar
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Fees {
+    pub fee_calculator: FeeCalculator,
+}
+
+impl Sysvar for Fees {
+    const ADDRESS: PublicKey = SYSVAR_FEES;
+
+    fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        Ok(bincode::deserialize::<Fees>(data)?)
+    }
+}
+
+/// The `SlotHashes` sysvar holding the most recent `(slot, bank hash)` pairs, newest first
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SlotHashes(pub Vec<(u64, [u8; 32])>);
+
+impl SlotHashes {
+    /// The bank hash of `slot` if it is still tracked
+    pub fn get(&self, slot: u64) -> Option<&[u8; 32]> {
+        self.0
+            .iter()
+            .find(|(hash_slot, _)| *hash_slot == slot)
+            .map(|(_, hash)| hash)
+    }
+}
+
+impl Sysvar for SlotHashes {
+    const ADDRESS: PublicKey = SYSVAR_SLOT_HASHES;
+
+    fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        Ok(bincode::deserialize::<SlotHashes>(data)?)
+    }
+}
+
+/// The `SlotHistory` sysvar, a bitvector of the slots present over the last
+/// `SLOT_HISTORY_MAX_ENTRIES` slots
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SlotHistory {
+    pub bits: Vec<u64>,
+    pub next_slot: u64,
+}
+
+/// Whether a slot is present in the `SlotHistory`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotHistoryCheck {
+    Future,
+    TooOld,
+    Found,
+    NotFound,
+}
+
+impl SlotHistory {
+    pub fn newest(&self) -> u64 {
+        self.next_slot.saturating_sub(1)
+    }
+
+    pub fn oldest(&self) -> u64 {
+        self.next_slot.saturating_sub(SLOT_HISTORY_MAX_ENTRIES)
+    }
+
+    pub fn check(&self, slot: u64) -> SlotHistoryCheck {
+        if slot > self.newest() {
+            SlotHistoryCheck::Future
+        } else if slot < self.oldest() {
+            SlotHistoryCheck::TooOld
+        } else {
+            let index = slot % SLOT_HISTORY_MAX_ENTRIES;
+            let word = self
+                .bits
+                .get((index / 64) as usize)
+                .copied()
+                .unwrap_or_default();
+
+            if word & (1 << (index % 64)) != 0 {
+                SlotHistoryCheck::Found
+            } else {
+                SlotHistoryCheck::NotFound
+            }
+        }
+    }
+}
+
+impl Sysvar for SlotHistory {
+    const ADDRESS: PublicKey = SYSVAR_SLOT_HISTORY;
+
+    fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        let layout = bincode::deserialize::<SlotHistoryLayout>(data)?;
+
+        Ok(SlotHistory {
+            bits: layout.bits.unwrap_or_default(),
+            next_slot: layout.next_slot,
+        })
+    }
+}
+
+// The layout of the `bv::BitVec<u64>` used by the runtime followed by the next slot
+#[derive(Deserialize)]
+struct SlotHistoryLayout {
+    bits: Option<Vec<u64>>,
+    _len: u64,
+    next_slot: u64,
+}
+
+/// The `StakeHistory` sysvar holding the cluster stake of recent epochs, newest first
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct StakeHistory(pub Vec<(u64, StakeHistoryEntry)>);
+
+impl StakeHistory {
+    pub fn get(&self, epoch: u64) -> Option<&StakeHistoryEntry> {
+        self.0
+            .iter()
+            .find(|(entry_epoch, _)| *entry_epoch == epoch)
+            .map(|(_, entry)| entry)
+    }
+}
+
+impl Sysvar for StakeHistory {
+    const ADDRESS: PublicKey = SYSVAR_STAKE_HISTORY;
+
+    fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        Ok(bincode::deserialize::<StakeHistory>(data)?)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct StakeHistoryEntry {
+    /// Effective stake at this epoch
+    pub effective: u64,
+    /// Sum of the portion of stakes not fully warmed up
+    pub activating: u64,
+    /// Requested to be cooled down, not fully deactivated yet
+    pub deactivating: u64,
+}
+
+/// The `Instructions` sysvar holding the instructions of the transaction being executed.
+/// It is only populated while a transaction executes so it is decoded from the
+/// data a program sees rather than fetched from the RPC.
+///
+/// The data is laid out as the number of instructions as a `u16`, the `u16` offset of
+/// each instruction, the instructions themselves and finally the `u16` index of the
+/// instruction currently executing.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SysvarInstructions {
+    pub instructions: Vec<Instruction>,
+    pub current_index: u16,
+}
+
+impl SysvarInstructions {
+    const IS_SIGNER_BIT: u8 = 0;
+    const IS_WRITABLE_BIT: u8 = 1;
+
+    /// Serialize `instructions` the way the runtime lays them out in the sysvar account
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = (self.instructions.len() as u16).to_le_bytes().to_vec();
+        let offsets_start = data.len();
+        data.resize(offsets_start + self.instructions.len() * 2, 0);
+
+        self.instructions
+            .iter()
+            .enumerate()
+            .for_each(|(index, instruction)| {
+                let offset = offsets_start + index * 2;
+                let start = (data.len() as u16).to_le_bytes();
+                data[offset..offset + 2].copy_from_slice(&start);
+
+                data.extend_from_slice(&(instruction.accounts.len() as u16).to_le_bytes());
+                instruction.accounts.iter().for_each(|account| {
+                    let mut flags = 0u8;
+                    if account.is_signer {
+                        flags |= 1 << SysvarInstructions::IS_SIGNER_BIT;
+                    }
+                    if account.is_writable {
+                        flags |= 1 << SysvarInstructions::IS_WRITABLE_BIT;
+                    }
+                    data.push(flags);
+                    data.extend_from_slice(&account.pubkey);
+                });
+                data.extend_from_slice(&instruction.program_id);
+                data.extend_from_slice(&(instruction.data.len() as u16).to_le_bytes());
+                data.extend_from_slice(&instruction.data);
+            });
+
+        data.extend_from_slice(&self.current_index.to_le_bytes());
+
+        data
+    }
+
+    fn read_u16(data: &[u8], offset: &mut usize) -> PoseidonResult<u16> {
+        let bytes = SysvarInstructions::read_bytes(data, offset, 2)?;
+
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_public_key(data: &[u8], offset: &mut usize) -> PoseidonResult<PublicKey> {
+        let bytes = SysvarInstructions::read_bytes(data, offset, 32)?;
+
+        bytes
+            .try_into()
+            .map_err(|_| PoseidonError::ErrorConvertingToU832)
+    }
+
+    fn read_bytes<'d>(data: &'d [u8], offset: &mut usize, len: usize) -> PoseidonResult<&'d [u8]> {
+        let end = offset.saturating_add(len);
+        let bytes = data
+            .get(*offset..end)
+            .ok_or(PoseidonError::InvalidAccountData)?;
+        *offset = end;
+
+        Ok(bytes)
+    }
+}
+
+impl Sysvar for SysvarInstructions {
+    const ADDRESS: PublicKey = SYSVAR_INSTRUCTIONS;
+
+    fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        let mut offset = 0usize;
+        let num_instructions = SysvarInstructions::read_u16(data, &mut offset)?;
+
+        let instructions = (0..num_instructions as usize)
+            .map(|index| {
+                let mut offset = index * 2 + 2;
+                let mut offset = SysvarInstructions::read_u16(data, &mut offset)? as usize;
+
+                let num_accounts = SysvarInstructions::read_u16(data, &mut offset)?;
+                let accounts = (0..num_accounts)
+                    .map(|_| {
+                        let flags = SysvarInstructions::read_bytes(data, &mut offset, 1)?[0];
+
+                        Ok(AccountMeta {
+                            pubkey: SysvarInstructions::read_public_key(data, &mut offset)?,
+                            is_signer: flags & (1 << SysvarInstructions::IS_SIGNER_BIT) != 0,
+                            is_writable: flags & (1 << SysvarInstructions::IS_WRITABLE_BIT) != 0,
+                        })
+                    })
+                    .collect::<PoseidonResult<Vec<AccountMeta>>>()?;
+
+                let program_id = SysvarInstructions::read_public_key(data, &mut offset)?;
+                let data_len = SysvarInstructions::read_u16(data, &mut offset)? as usize;
+                let instruction_data =
+                    SysvarInstructions::read_bytes(data, &mut offset, data_len)?.to_vec();
+
+                Ok(Instruction {
+                    program_id,
+                    accounts,
+                    data: instruction_data,
+                })
+            })
+            .collect::<PoseidonResult<Vec<Instruction>>>()?;
+
+        let mut offset = data.len().saturating_sub(2);
+        let current_index = SysvarInstructions::read_u16(data, &mut offset)?;
+
+        Ok(SysvarInstructions {
+            instructions,
+            current_index,
+        })
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+    use crate::STAKE_STATE_SIZE;
+
+    #[test]
+    fn decode_sysvars() {
+        let clock = Clock {
+            slot: 200_000_000,
+            epoch_start_timestamp: 1_680_000_000,
+            epoch: 460,
+            leader_schedule_epoch: 461,
+            unix_timestamp: 1_680_100_000,
+        };
+        let data = bincode::serialize(&clock).unwrap();
+        assert_eq!(data.len(), 40);
+        assert_eq!(Clock::from_bytes(&data).unwrap(), clock);
+
+        let mut data = 3480u64.to_le_bytes().to_vec();
+        data.extend_from_slice(&2f64.to_le_bytes());
+        data.push(50);
+        let rent = Rent::from_bytes(&data).unwrap();
+        assert_eq!(rent.lamports_per_byte_year, 3480);
+        assert_eq!(rent.exemption_threshold, 2.0);
+        assert_eq!(rent.burn_percent, 50);
+
+        // Sysvar accounts are allocated for their maximum size
+        let stake_history = StakeHistory(vec![(
+            10,
+            StakeHistoryEntry {
+                effective: 1,
+                activating: 2,
+                deactivating: 3,
+            },
+        )]);
+        let mut data = bincode::serialize(&stake_history).unwrap();
+        data.resize(16_392, 0);
+        let decoded = StakeHistory::from_bytes(&data).unwrap();
+        assert_eq!(decoded.get(10).unwrap().activating, 2);
+        assert!(decoded.get(11).is_none());
+    }
+
+    #[test]
+    fn rent_exemption() {
+        #[derive(BorshSerialize)]
+        struct Profile {
+            name: String,
+            scores: Vec<u16>,
+        }
+
+        let rent = Rent::default();
+        assert_eq!(rent.minimum_balance(0), 890_880);
+        assert_eq!(rent.minimum_balance(STAKE_STATE_SIZE), 2_282_880);
+        assert!(rent.is_exempt(2_282_880, STAKE_STATE_SIZE));
+        assert!(!rent.is_exempt(2_282_879, STAKE_STATE_SIZE));
+
+        let profile = Profile {
+            name: "poseidon".to_owned(),
+            scores: vec![1, 2, 3],
+        };
+        // 4 + 8 bytes for the name and 4 + 6 bytes for the scores
+        assert_eq!(
+            rent.minimum_balance_for_value(&profile).unwrap(),
+            rent.minimum_balance(22)
+        );
+    }
+
+    #[test]
+    fn slot_history_check() {
+        let mut bits = vec![0u64; (SLOT_HISTORY_MAX_ENTRIES / 64) as usize];
+        bits[1] = 1 << 2;
+
+        let mut data = vec![1u8];
+        data.extend_from_slice(&(bits.len() as u64).to_le_bytes());
+        bits.iter()
+            .for_each(|word| data.extend_from_slice(&word.to_le_bytes()));
+        data.extend_from_slice(&SLOT_HISTORY_MAX_ENTRIES.to_le_bytes());
+        data.extend_from_slice(&(SLOT_HISTORY_MAX_ENTRIES + 100).to_le_bytes());
+
+        let slot_history = SlotHistory::from_bytes(&data).unwrap();
+        assert_eq!(
+            slot_history.check(SLOT_HISTORY_MAX_ENTRIES + 66),
+            SlotHistoryCheck::Found
+        );
+        assert_eq!(
+            slot_history.check(SLOT_HISTORY_MAX_ENTRIES + 67),
+            SlotHistoryCheck::NotFound
+        );
+        assert_eq!(
+            slot_history.check(SLOT_HISTORY_MAX_ENTRIES + 100),
+            SlotHistoryCheck::Future
+        );
+        assert_eq!(slot_history.check(99), SlotHistoryCheck::TooOld);
+    }
+
+    #[test]
+    fn instructions_sysvar_layout() {
+        let sysvar = SysvarInstructions {
+            instructions: vec![
+                Instruction {
+                    program_id: [9u8; 32],
+                    accounts: vec![
+                        AccountMeta::new([1u8; 32], true),
+                        AccountMeta::new_readonly([2u8; 32], false),
+                    ],
+                    data: vec![1, 2, 3],
+                },
+                Instruction {
+                    program_id: [8u8; 32],
+                    accounts: vec![],
+                    data: vec![],
+                },
+            ],
+            current_index: 1,
+        };
+
+        let data = sysvar.to_bytes();
+        assert_eq!(&data[0..2], &2u16.to_le_bytes());
+        assert_eq!(&data[2..4], &6u16.to_le_bytes());
+        assert_eq!(data[8], 0b11);
+        assert_eq!(data[41], 0);
+
+        assert_eq!(SysvarInstructions::from_bytes(&data).unwrap(), sysvar);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Message, PoseidonResult, Signature};
+use crate::{Message, PoseidonError, PoseidonResult, Signature};
 use core::fmt;
 use generic_array::GenericArray;
 use serde::{Deserialize, Serialize};
@@ -19,12 +19,44 @@ impl Transaction {
     }
 
     pub fn add_signature(&mut self, signature: [u8; 64]) -> &mut Self {
-        self.signatures
-            .push(GenericArray::clone_from_slice(&signature));
+        self.signatures.push(GenericArray::from_array(signature));
 
         self
     }
 
+    /// Sign the message with every keypair required by the message header.
+    /// The signatures are added in the order of the `account_keys` of the `Message`
+    /// so the `keypairs` can be provided in any order.
+    pub fn sign(&mut self, keypairs: &[&ed25519_dalek::Keypair]) -> PoseidonResult<&mut Self> {
+        use ed25519_dalek::Signer;
+
+        let message_bytes = self.message.to_bytes()?;
+        let num_required_signatures = self.message.header.num_required_signatures as usize;
+
+        let signatures = self
+            .message
+            .account_keys
+            .iter()
+            .take(num_required_signatures)
+            .map(|public_key| {
+                match keypairs
+                    .iter()
+                    .find(|keypair| &keypair.public.to_bytes() == public_key)
+                {
+                    Some(keypair) => Ok(keypair.sign(&message_bytes).to_bytes()),
+                    None => Err(PoseidonError::MissingSigner),
+                }
+            })
+            .collect::<PoseidonResult<Vec<[u8; 64]>>>()?;
+
+        self.signatures.clear();
+        signatures.iter().for_each(|signature| {
+            self.add_signature(*signature);
+        });
+
+        Ok(self)
+    }
+
     pub fn to_bytes(&self) -> PoseidonResult<Vec<u8>> {
         Ok(bincode::serialize(&self)?)
     }
@@ -0,0 +1,271 @@
+use crate::{PoseidonResult, PublicKey, UnixTimestamp};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+/// The number of prior voters kept in the vote account
+const MAX_PRIOR_VOTERS: usize = 32;
+
+/// The state of a Vote account, decoded from any of the layouts
+/// the Vote program has stored on chain.
+///
+/// Decode it from the data returned by `GetAccountInfo`
+/// ```no_run
+/// # use poseidon_client::*;
+/// # async fn example(vote_account: &str) -> PoseidonResult<()> {
+/// let account = GetAccountInfo::process(vote_account).await?.result.value;
+/// let vote_state = VoteState::from_bytes(&account.decode_data()?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct VoteState {
+    /// The validator identity that signs the votes in this account
+    pub node_pubkey: PublicKey,
+    /// The signer for withdrawals
+    pub authorized_withdrawer: PublicKey,
+    /// Percentage (0-100) that represents what part of a rewards
+    /// payout should be given to this `VoteAccount`
+    pub commission: u8,
+    /// The most recent votes, oldest first
+    pub votes: Vec<Lockout>,
+    /// The slot of the most recent rooted vote
+    pub root_slot: Option<u64>,
+    /// The authorized voters keyed by the epoch they become active
+    pub authorized_voters: BTreeMap<u64, PublicKey>,
+    /// History of how many credits earned by the end of each epoch
+    pub epoch_credits: Vec<EpochCredits>,
+    /// Most recent timestamp submitted with a vote
+    pub last_timestamp: BlockTimestamp,
+}
+
+impl VoteState {
+    pub fn from_bytes(data: &[u8]) -> PoseidonResult<Self> {
+        let versioned = bincode::deserialize::<VoteStateVersions>(data)?;
+
+        Ok(versioned.into())
+    }
+
+    /// The authorized voter for the given `epoch`
+    pub fn authorized_voter(&self, epoch: u64) -> Option<PublicKey> {
+        self.authorized_voters
+            .range(..=epoch)
+            .next_back()
+            .map(|(_, voter)| *voter)
+    }
+
+    /// The credits earned in the most recent epoch
+    pub fn credits(&self) -> u64 {
+        self.epoch_credits
+            .last()
+            .map(|epoch_credits| epoch_credits.credits)
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for VoteState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let authorized_voters = self
+            .authorized_voters
+            .iter()
+            .map(|(epoch, voter)| (*epoch, bs58::encode(voter).into_string()))
+            .collect::<Vec<(u64, String)>>();
+
+        f.debug_struct("VoteState")
+            .field(
+                "node_pubkey",
+                &bs58::encode(&self.node_pubkey).into_string(),
+            )
+            .field(
+                "authorized_withdrawer",
+                &bs58::encode(&self.authorized_withdrawer).into_string(),
+            )
+            .field("commission", &self.commission)
+            .field("votes", &self.votes)
+            .field("root_slot", &self.root_slot)
+            .field("authorized_voters", &authorized_voters)
+            .field("epoch_credits", &self.epoch_credits)
+            .field("last_timestamp", &self.last_timestamp)
+            .finish()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Lockout {
+    pub slot: u64,
+    pub confirmation_count: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BlockTimestamp {
+    pub slot: u64,
+    pub timestamp: UnixTimestamp,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct EpochCredits {
+    pub epoch: u64,
+    pub credits: u64,
+    pub previous_credits: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+enum VoteStateVersions {
+    V0_23_5(Box<VoteState0_23_5>),
+    V1_14_11(Box<VoteState1_14_11>),
+    Current(Box<VoteStateCurrent>),
+}
+
+#[derive(Serialize, Deserialize)]
+struct VoteState0_23_5 {
+    node_pubkey: PublicKey,
+    authorized_voter: PublicKey,
+    authorized_voter_epoch: u64,
+    prior_voters: PriorVoters<(PublicKey, u64, u64, u64)>,
+    authorized_withdrawer: PublicKey,
+    commission: u8,
+    votes: VecDeque<Lockout>,
+    root_slot: Option<u64>,
+    epoch_credits: Vec<EpochCredits>,
+    last_timestamp: BlockTimestamp,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VoteState1_14_11 {
+    node_pubkey: PublicKey,
+    authorized_withdrawer: PublicKey,
+    commission: u8,
+    votes: VecDeque<Lockout>,
+    root_slot: Option<u64>,
+    authorized_voters: BTreeMap<u64, PublicKey>,
+    prior_voters: PriorVoters<(PublicKey, u64, u64)>,
+    is_prior_voters_empty: bool,
+    epoch_credits: Vec<EpochCredits>,
+    last_timestamp: BlockTimestamp,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VoteStateCurrent {
+    node_pubkey: PublicKey,
+    authorized_withdrawer: PublicKey,
+    commission: u8,
+    votes: VecDeque<LandedVote>,
+    root_slot: Option<u64>,
+    authorized_voters: BTreeMap<u64, PublicKey>,
+    prior_voters: PriorVoters<(PublicKey, u64, u64)>,
+    is_prior_voters_empty: bool,
+    epoch_credits: Vec<EpochCredits>,
+    last_timestamp: BlockTimestamp,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LandedVote {
+    latency: u8,
+    lockout: Lockout,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PriorVoters<T> {
+    buf: [T; MAX_PRIOR_VOTERS],
+    idx: u64,
+}
+
+impl From<VoteStateVersions> for VoteState {
+    fn from(versioned: VoteStateVersions) -> Self {
+        match versioned {
+            VoteStateVersions::V0_23_5(state) => {
+                let mut authorized_voters = BTreeMap::new();
+                authorized_voters.insert(state.authorized_voter_epoch, state.authorized_voter);
+
+                VoteState {
+                    node_pubkey: state.node_pubkey,
+                    authorized_withdrawer: state.authorized_withdrawer,
+                    commission: state.commission,
+                    votes: state.votes.into(),
+                    root_slot: state.root_slot,
+                    authorized_voters,
+                    epoch_credits: state.epoch_credits,
+                    last_timestamp: state.last_timestamp,
+                }
+            }
+            VoteStateVersions::V1_14_11(state) => VoteState {
+                node_pubkey: state.node_pubkey,
+                authorized_withdrawer: state.authorized_withdrawer,
+                commission: state.commission,
+                votes: state.votes.into(),
+                root_slot: state.root_slot,
+                authorized_voters: state.authorized_voters,
+                epoch_credits: state.epoch_credits,
+                last_timestamp: state.last_timestamp,
+            },
+            VoteStateVersions::Current(state) => VoteState {
+                node_pubkey: state.node_pubkey,
+                authorized_withdrawer: state.authorized_withdrawer,
+                commission: state.commission,
+                votes: state
+                    .votes
+                    .into_iter()
+                    .map(|landed_vote| landed_vote.lockout)
+                    .collect(),
+                root_slot: state.root_slot,
+                authorized_voters: state.authorized_voters,
+                epoch_credits: state.epoch_credits,
+                last_timestamp: state.last_timestamp,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+
+    #[test]
+    fn decode_current_vote_state() {
+        let mut authorized_voters = BTreeMap::new();
+        authorized_voters.insert(400, [3u8; 32]);
+        authorized_voters.insert(410, [4u8; 32]);
+
+        let state = VoteStateVersions::Current(Box::new(VoteStateCurrent {
+            node_pubkey: [1u8; 32],
+            authorized_withdrawer: [2u8; 32],
+            commission: 7,
+            votes: VecDeque::from(vec![LandedVote {
+                latency: 1,
+                lockout: Lockout {
+                    slot: 1_000,
+                    confirmation_count: 31,
+                },
+            }]),
+            root_slot: Some(969),
+            authorized_voters,
+            prior_voters: PriorVoters {
+                buf: [([0u8; 32], 0, 0); MAX_PRIOR_VOTERS],
+                idx: 31,
+            },
+            is_prior_voters_empty: true,
+            epoch_credits: vec![EpochCredits {
+                epoch: 410,
+                credits: 5_000,
+                previous_credits: 4_000,
+            }],
+            last_timestamp: BlockTimestamp {
+                slot: 1_000,
+                timestamp: 1_660_000_000,
+            },
+        }));
+
+        let mut data = bincode::serialize(&state).unwrap();
+        // Vote accounts are allocated larger than their serialized state
+        data.resize(3_762, 0);
+
+        let vote_state = VoteState::from_bytes(&data).unwrap();
+        assert_eq!(vote_state.node_pubkey, [1u8; 32]);
+        assert_eq!(vote_state.commission, 7);
+        assert_eq!(vote_state.votes[0].slot, 1_000);
+        assert_eq!(vote_state.root_slot, Some(969));
+        assert_eq!(vote_state.authorized_voter(405), Some([3u8; 32]));
+        assert_eq!(vote_state.authorized_voter(411), Some([4u8; 32]));
+        assert_eq!(vote_state.credits(), 5_000);
+    }
+}
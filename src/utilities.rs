@@ -20,7 +20,7 @@ impl Utilities {
     pub fn base58_to_binary(value: Base58Value) -> PoseidonResult<Vec<u8>> {
         match bs58::decode(&value).into_vec() {
             Ok(decoded) => Ok(decoded),
-            Err(_) => return Err(PoseidonError::InvalidBase58ForPublicKey),
+            Err(_) => Err(PoseidonError::InvalidBase58ForPublicKey),
         }
     }
 }
@@ -0,0 +1,570 @@
+use crate::{PdaBuilder, PoseidonError, PoseidonResult, PublicKey};
+use ed25519_dalek::{Keypair, SecretKey, PUBLIC_KEY_LENGTH, SECRET_KEY_LENGTH};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// The attempts a thread makes before it checks whether to stop
+const ATTEMPTS_PER_BATCH: u64 = 64;
+
+// The length of the longest Base58 encoded public key
+const MAX_ADDRESS_LENGTH: usize = 44;
+
+/// Cancels a running `VanityGrinder` from another thread or the progress callback.
+/// A cancelled token stays cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct VanityCancelToken(Arc<AtomicBool>);
+
+impl VanityCancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The progress reported while grinding
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VanityProgress {
+    pub attempts: u64,
+    pub elapsed: Duration,
+    /// The expected number of attempts to find a match
+    pub difficulty: f64,
+}
+
+impl VanityProgress {
+    pub fn attempts_per_second(&self) -> f64 {
+        self.attempts as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Every attempt is independent so the expected time is the same
+    /// however long the grinder has already run
+    pub fn estimated_time(&self) -> Option<Duration> {
+        let attempts_per_second = self.attempts_per_second();
+
+        if attempts_per_second > 0.0 {
+            Some(Duration::from_secs_f64(
+                self.difficulty / attempts_per_second,
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// An address derived with `create_with_seed` by `VanityGrinder::grind_with_seed`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VanitySeedAddress {
+    pub seed: String,
+    pub public_key: PublicKey,
+}
+
+/// ### Search for keypairs whose Base58 address has a prefix or suffix
+/// The search runs on every available core unless `add_threads` is set.
+/// `grind` returns `None` if it was cancelled before a match was found.
+/// ```no_run
+/// # use poseidon_client::*;
+/// # use std::time::Duration;
+/// # fn main() -> PoseidonResult<()> {
+/// let mut grinder = VanityGrinder::new();
+/// grinder.add_prefix("pos").change_case_sensitive(false);
+///
+/// let cancel = VanityCancelToken::default();
+/// grinder.add_cancel_token(cancel.clone());
+/// let keypair = grinder.grind(|progress| {
+///     println!("{} attempts, about {:?}", progress.attempts, progress.estimated_time());
+///     if progress.elapsed > Duration::from_secs(600) {
+///         cancel.cancel();
+///     }
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct VanityGrinder {
+    prefix: String,
+    suffix: String,
+    case_sensitive: bool,
+    threads: usize,
+    progress_interval: Duration,
+    cancel: VanityCancelToken,
+}
+
+impl Default for VanityGrinder {
+    fn default() -> Self {
+        VanityGrinder::new()
+    }
+}
+
+impl VanityGrinder {
+    pub fn new() -> Self {
+        VanityGrinder {
+            prefix: String::default(),
+            suffix: String::default(),
+            case_sensitive: true,
+            threads: thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1),
+            progress_interval: Duration::from_secs(1),
+            cancel: VanityCancelToken::default(),
+        }
+    }
+
+    pub fn add_prefix(&mut self, prefix: &str) -> &mut Self {
+        self.prefix = prefix.to_owned();
+
+        self
+    }
+
+    pub fn add_suffix(&mut self, suffix: &str) -> &mut Self {
+        self.suffix = suffix.to_owned();
+
+        self
+    }
+
+    pub fn add_threads(&mut self, threads: usize) -> &mut Self {
+        self.threads = threads.max(1);
+
+        self
+    }
+
+    /// How often the progress callback is called, every second by default
+    pub fn add_progress_interval(&mut self, progress_interval: Duration) -> &mut Self {
+        self.progress_interval = progress_interval;
+
+        self
+    }
+
+    /// Patterns are case sensitive by default
+    pub fn change_case_sensitive(&mut self, case_sensitive: bool) -> &mut Self {
+        self.case_sensitive = case_sensitive;
+
+        self
+    }
+
+    /// Cancels the grinds of this grinder and its clones. Once cancelled every grind
+    /// returns `None`, use `add_cancel_token` with a new token to grind again.
+    pub fn cancel_token(&self) -> VanityCancelToken {
+        self.cancel.clone()
+    }
+
+    /// Replace the token that cancels the grinds of this grinder
+    pub fn add_cancel_token(&mut self, cancel: VanityCancelToken) -> &mut Self {
+        self.cancel = cancel;
+
+        self
+    }
+
+    /// The expected number of attempts to find a match. A case insensitive
+    /// character matches every letter of the Base58 alphabet it can be.
+    /// The prefix is weighed by the share of 32 byte public keys whose address
+    /// starts with it, a 44 character address can only start with `1` to `J`.
+    /// Patterns longer than an address never match and are infinitely difficult.
+    pub fn difficulty(&self) -> f64 {
+        // Checked before the case variants of the prefix are enumerated
+        if self.is_too_long() {
+            return f64::INFINITY;
+        }
+
+        let prefix_probability = self
+            .prefix
+            .chars()
+            .fold(vec![String::default()], |prefixes, character| {
+                prefixes
+                    .iter()
+                    .flat_map(|prefix| {
+                        self.variants(character)
+                            .map(move |variant| format!("{prefix}{variant}"))
+                    })
+                    .collect()
+            })
+            .iter()
+            .map(|prefix| prefix_probability(prefix))
+            .sum::<f64>();
+
+        let suffix_probability = self
+            .suffix
+            .chars()
+            .map(|character| self.matching_characters(character) as f64 / 58.0)
+            .product::<f64>();
+
+        1.0 / (prefix_probability * suffix_probability)
+    }
+
+    /// Grind keypairs until one matches or the grinder is cancelled
+    pub fn grind(&self, progress: impl FnMut(&VanityProgress)) -> PoseidonResult<Option<Keypair>> {
+        self.validate()?;
+
+        self.run(progress, |_, _| {
+            let mut secrets = [0u8; SECRET_KEY_LENGTH * ATTEMPTS_PER_BATCH as usize];
+            getrandom::getrandom(&mut secrets).map_err(|_| PoseidonError::RandomnessUnavailable)?;
+
+            Ok(secrets.chunks_exact(SECRET_KEY_LENGTH).find_map(|secret| {
+                let secret = SecretKey::from_bytes(secret).ok()?;
+                let public: ed25519_dalek::PublicKey = (&secret).into();
+
+                self.is_match(public.as_bytes())
+                    .then_some(Keypair { secret, public })
+            }))
+        })
+    }
+
+    /// Grind the seed of `create_with_seed` addresses of `base` owned by `owner`
+    /// until one matches or the grinder is cancelled
+    pub fn grind_with_seed(
+        &self,
+        base: PublicKey,
+        owner: PublicKey,
+        progress: impl FnMut(&VanityProgress),
+    ) -> PoseidonResult<Option<VanitySeedAddress>> {
+        self.validate()?;
+        // Checks the owner before the threads start
+        PdaBuilder::new()
+            .add_base(base)
+            .add_owner(owner)
+            .derive_public_key()?;
+
+        self.run(progress, |thread_index, batch| {
+            let mut builder = PdaBuilder::new();
+            builder.add_base(base).add_owner(owner);
+
+            // Each thread grinds its own range of counters
+            let first = (batch * self.threads as u64 + thread_index as u64) * ATTEMPTS_PER_BATCH;
+
+            Ok((first..first + ATTEMPTS_PER_BATCH).find_map(|counter| {
+                let seed = counter.to_string();
+                let public_key = builder.add_seed(&seed).derive_public_key().ok()?;
+
+                self.is_match(&public_key)
+                    .then_some(VanitySeedAddress { seed, public_key })
+            }))
+        })
+    }
+
+    fn validate(&self) -> PoseidonResult<()> {
+        if self.prefix.is_empty() && self.suffix.is_empty() {
+            return Err(PoseidonError::InvalidVanityPattern(String::default()));
+        }
+
+        if self.is_too_long() {
+            return Err(PoseidonError::InvalidVanityPattern(format!(
+                "{}{}",
+                self.prefix, self.suffix
+            )));
+        }
+
+        match self
+            .prefix
+            .chars()
+            .chain(self.suffix.chars())
+            .find(|character| self.matching_characters(*character) == 0)
+        {
+            Some(character) => Err(PoseidonError::InvalidVanityPattern(character.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn is_too_long(&self) -> bool {
+        self.prefix.chars().count() > MAX_ADDRESS_LENGTH
+            || self.suffix.chars().count() > MAX_ADDRESS_LENGTH
+    }
+
+    fn matching_characters(&self, character: char) -> usize {
+        self.variants(character).count()
+    }
+
+    // The characters of the Base58 alphabet that `character` matches
+    fn variants(&self, character: char) -> impl Iterator<Item = char> + '_ {
+        BASE58_ALPHABET.chars().filter(move |base58| {
+            if self.case_sensitive {
+                *base58 == character
+            } else {
+                base58.eq_ignore_ascii_case(&character)
+            }
+        })
+    }
+
+    fn is_match(&self, public_key: &[u8]) -> bool {
+        let address = bs58::encode(public_key).into_string();
+
+        if self.case_sensitive {
+            address.starts_with(&self.prefix) && address.ends_with(&self.suffix)
+        } else {
+            let address = address.to_ascii_lowercase();
+
+            address.starts_with(&self.prefix.to_ascii_lowercase())
+                && address.ends_with(&self.suffix.to_ascii_lowercase())
+        }
+    }
+
+    // Runs `attempt` with the thread index and batch number on every thread until a
+    // batch finds a match or fails, reporting progress from the calling thread
+    fn run<T: Send>(
+        &self,
+        mut progress: impl FnMut(&VanityProgress),
+        attempt: impl Fn(usize, u64) -> PoseidonResult<Option<T>> + Sync,
+    ) -> PoseidonResult<Option<T>> {
+        let started = Instant::now();
+        let attempts = AtomicU64::new(0);
+        let done = AtomicBool::new(false);
+        let found = Mutex::new(None);
+        let difficulty = self.difficulty();
+
+        thread::scope(|scope| {
+            for thread_index in 0..self.threads {
+                let (attempts, done, found, attempt) = (&attempts, &done, &found, &attempt);
+
+                scope.spawn(move || {
+                    let mut batch = 0u64;
+
+                    while !done.load(Ordering::Relaxed) && !self.cancel.is_cancelled() {
+                        let result = attempt(thread_index, batch).transpose();
+                        if let Some(result) = result {
+                            if let Ok(mut found) = found.lock() {
+                                found.get_or_insert(result);
+                            }
+                            done.store(true, Ordering::Relaxed);
+                        }
+
+                        attempts.fetch_add(ATTEMPTS_PER_BATCH, Ordering::Relaxed);
+                        batch += 1;
+                    }
+                });
+            }
+
+            let mut last_progress = Instant::now();
+            while !done.load(Ordering::Relaxed) && !self.cancel.is_cancelled() {
+                thread::sleep(self.progress_interval.min(Duration::from_millis(50)));
+
+                if last_progress.elapsed() >= self.progress_interval {
+                    last_progress = Instant::now();
+                    progress(&VanityProgress {
+                        attempts: attempts.load(Ordering::Relaxed),
+                        elapsed: started.elapsed(),
+                        difficulty,
+                    });
+                }
+            }
+
+            done.store(true, Ordering::Relaxed);
+        });
+
+        found.into_inner().ok().flatten().transpose()
+    }
+}
+
+// The share of 32 byte public keys whose Base58 address starts with `prefix`.
+// Each leading `1` is a leading zero byte and the rest of the prefix holds the
+// most significant digits of the remaining bytes read as a big endian number.
+fn prefix_probability(prefix: &str) -> f64 {
+    let zero_bytes = prefix
+        .chars()
+        .take_while(|character| *character == '1')
+        .count();
+    let digits = &prefix[zero_bytes..];
+    let leading_zeros = 256f64.powi(-(zero_bytes as i32));
+
+    if digits.is_empty() {
+        return leading_zeros;
+    }
+
+    let bytes = PUBLIC_KEY_LENGTH.saturating_sub(zero_bytes) as i32;
+    let range = 256f64.powi(bytes);
+    // Smaller numbers have another leading zero byte and start with another `1`
+    let smallest = 256f64.powi(bytes - 1);
+    let value = digits.chars().fold(0.0, |value, character| {
+        value * 58.0 + BASE58_ALPHABET.find(character).unwrap_or_default() as f64
+    });
+
+    // The numbers of every address length that start with the digits
+    let matching = (digits.len()..=PUBLIC_KEY_LENGTH * 2)
+        .map(|length| {
+            let scale = 58f64.powi((length - digits.len()) as i32);
+            let low = (value * scale)
+                .max(58f64.powi(length as i32 - 1))
+                .max(smallest);
+            let high = ((value + 1.0) * scale)
+                .min(58f64.powi(length as i32))
+                .min(range);
+
+            (high - low).max(0.0)
+        })
+        .sum::<f64>();
+
+    leading_zeros * matching / range
+}
+
+#[cfg(test)]
+mod sanity_checks {
+    use super::*;
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!(
+            (value - expected).abs() / expected < 1e-9,
+            "{value} != {expected}"
+        );
+    }
+
+    #[test]
+    fn difficulty() {
+        let mut grinder = VanityGrinder::new();
+        grinder.add_suffix("ab");
+        assert_close(grinder.difficulty(), 58.0 * 58.0);
+
+        // Only the 43 character addresses can start with `ab`
+        grinder.add_suffix("").add_prefix("ab");
+        assert_close(grinder.difficulty(), 2f64.powi(256) / 58f64.powi(41));
+
+        // Every address that starts with `1` has a leading zero byte
+        grinder.add_prefix("1");
+        assert_close(grinder.difficulty(), 256.0);
+
+        // 44 character addresses start with `1` to `J` so `A` is more likely than `z`
+        let a = grinder.add_prefix("A").difficulty();
+        let z = grinder.add_prefix("z").difficulty();
+        assert!(a < 58.0 && z > 58.0);
+
+        // `o` has no upper case letter in the Base58 alphabet
+        grinder
+            .add_prefix("")
+            .change_case_sensitive(false)
+            .add_suffix("ao");
+        assert_close(grinder.difficulty(), 29.0 * 58.0);
+
+        grinder.add_prefix("0OIl");
+        assert!(matches!(
+            grinder.grind(|_| {}),
+            Err(PoseidonError::InvalidVanityPattern(character)) if character == "0"
+        ));
+        assert!(matches!(
+            VanityGrinder::new().grind(|_| {}),
+            Err(PoseidonError::InvalidVanityPattern(_))
+        ));
+    }
+
+    #[test]
+    fn attempt_errors_stop_grinding() {
+        let mut grinder = VanityGrinder::new();
+        grinder.add_prefix("zzzzzzzzzz").add_threads(2);
+
+        let result = grinder.run(
+            |_| {},
+            |_, _| Err::<Option<()>, _>(PoseidonError::RandomnessUnavailable),
+        );
+        assert!(matches!(result, Err(PoseidonError::RandomnessUnavailable)));
+    }
+
+    #[test]
+    fn grind_keypairs() {
+        let mut grinder = VanityGrinder::new();
+        grinder.add_prefix("P").add_threads(2);
+
+        let keypair = grinder.grind(|_| {}).unwrap().unwrap();
+        assert!(bs58::encode(keypair.public.as_bytes())
+            .into_string()
+            .starts_with('P'));
+
+        grinder
+            .add_prefix("")
+            .add_suffix("z")
+            .change_case_sensitive(false);
+        let keypair = grinder.grind(|_| {}).unwrap().unwrap();
+        assert!(bs58::encode(keypair.public.as_bytes())
+            .into_string()
+            .to_lowercase()
+            .ends_with('z'));
+    }
+
+    #[test]
+    fn grind_seeds() {
+        let base = [1; 32];
+        let owner = [2; 32];
+
+        let mut grinder = VanityGrinder::new();
+        grinder.add_prefix("Se").add_threads(2);
+
+        let found = grinder
+            .grind_with_seed(base, owner, |_| {})
+            .unwrap()
+            .unwrap();
+        let public_key = PdaBuilder::new()
+            .add_base(base)
+            .add_owner(owner)
+            .add_seed(&found.seed)
+            .derive_public_key()
+            .unwrap();
+        assert_eq!(public_key, found.public_key);
+        assert!(bs58::encode(public_key).into_string().starts_with("Se"));
+    }
+
+    #[test]
+    fn cancel() {
+        let mut grinder = VanityGrinder::new();
+        grinder
+            .add_prefix("zzzzzzzzzz")
+            .add_threads(2)
+            .add_progress_interval(Duration::from_millis(10));
+
+        let cancel = grinder.cancel_token();
+        let mut reports = Vec::new();
+        let keypair = grinder
+            .grind(|progress| {
+                reports.push(*progress);
+                cancel.cancel();
+            })
+            .unwrap();
+
+        assert!(keypair.is_none());
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].difficulty, grinder.difficulty());
+        assert!(reports[0].estimated_time().is_some() || reports[0].attempts == 0);
+
+        // The token stays cancelled for the grinder and its clones
+        grinder.add_prefix("P");
+        assert!(grinder.grind(|_| {}).unwrap().is_none());
+        assert!(grinder.clone().grind(|_| {}).unwrap().is_none());
+
+        grinder.add_cancel_token(VanityCancelToken::default());
+        assert!(grinder.grind(|_| {}).unwrap().is_some());
+    }
+
+    #[test]
+    fn cancel_before_grinding() {
+        let mut grinder = VanityGrinder::new();
+        grinder.add_prefix("P").add_threads(2);
+
+        grinder.cancel_token().cancel();
+        assert!(grinder.grind(|_| {}).unwrap().is_none());
+    }
+
+    #[test]
+    fn pattern_longer_than_an_address() {
+        let mut grinder = VanityGrinder::new();
+        grinder
+            .add_prefix(&"a".repeat(MAX_ADDRESS_LENGTH + 1))
+            .change_case_sensitive(false);
+
+        assert_eq!(grinder.difficulty(), f64::INFINITY);
+        assert!(matches!(
+            grinder.grind(|_| {}),
+            Err(PoseidonError::InvalidVanityPattern(_))
+        ));
+
+        grinder
+            .add_prefix("")
+            .add_suffix(&"z".repeat(MAX_ADDRESS_LENGTH + 1));
+        assert!(matches!(
+            grinder.grind(|_| {}),
+            Err(PoseidonError::InvalidVanityPattern(_))
+        ));
+    }
+}